@@ -0,0 +1,4 @@
+-- First-seen timestamps per commitment level (confirmation latency tracking)
+ALTER TABLE slots ADD COLUMN processed_at BIGINT;
+ALTER TABLE slots ADD COLUMN confirmed_at BIGINT;
+ALTER TABLE slots ADD COLUMN finalized_at BIGINT;
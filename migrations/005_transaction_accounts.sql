@@ -0,0 +1,13 @@
+-- Normalized account → transaction index (wallet history).
+-- Populated by store_transaction going forward; rows indexed before this migration are not backfilled.
+
+CREATE TABLE IF NOT EXISTS transaction_accounts (
+    account TEXT NOT NULL,
+    signature TEXT NOT NULL,
+    slot BIGINT NOT NULL,
+    PRIMARY KEY (account, signature)
+);
+
+CREATE INDEX IF NOT EXISTS idx_transaction_accounts_account_slot ON transaction_accounts(account, slot);
+CREATE INDEX IF NOT EXISTS idx_transaction_accounts_signature ON transaction_accounts(signature);
+CREATE INDEX IF NOT EXISTS idx_transaction_accounts_slot ON transaction_accounts(slot);
@@ -0,0 +1,6 @@
+-- Persist decoded transaction details (program, instruction count, compute units)
+ALTER TABLE transactions ADD COLUMN program TEXT;
+ALTER TABLE transactions ADD COLUMN instruction_count BIGINT;
+ALTER TABLE transactions ADD COLUMN compute_units BIGINT;
+
+CREATE INDEX IF NOT EXISTS idx_transactions_program ON transactions(program);
@@ -0,0 +1,19 @@
+-- Every executed instruction (top-level and inner/CPI), in execution order per transaction.
+-- `position` is the execution order; `inner_index` is NULL for top-level instructions.
+
+CREATE TABLE IF NOT EXISTS instructions (
+    signature TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    slot BIGINT NOT NULL,
+    instruction_index INTEGER NOT NULL,
+    inner_index INTEGER,
+    stack_height INTEGER,
+    program_id TEXT NOT NULL,
+    accounts TEXT NOT NULL,
+    data TEXT,
+    parsed TEXT,
+    PRIMARY KEY (signature, position)
+);
+
+CREATE INDEX IF NOT EXISTS idx_instructions_program_slot ON instructions(program_id, slot);
+CREATE INDEX IF NOT EXISTS idx_instructions_slot ON instructions(slot);
@@ -0,0 +1,2 @@
+-- Decoded account data (JSON) from the owner program's decoder, next to the raw bytes.
+ALTER TABLE accounts ADD COLUMN decoded TEXT;
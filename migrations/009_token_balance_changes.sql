@@ -0,0 +1,16 @@
+-- SPL token account balance changes per transaction (from pre/post token balances).
+-- Raw amounts are TEXT: u64 token amounts can exceed BIGINT.
+CREATE TABLE IF NOT EXISTS token_balance_changes (
+    signature TEXT NOT NULL,
+    account TEXT NOT NULL,
+    slot BIGINT NOT NULL,
+    owner TEXT,
+    mint TEXT NOT NULL,
+    pre TEXT NOT NULL,
+    post TEXT NOT NULL,
+    decimals INTEGER NOT NULL,
+    PRIMARY KEY (signature, account)
+);
+
+CREATE INDEX IF NOT EXISTS idx_token_balance_changes_owner_slot ON token_balance_changes(owner, slot);
+CREATE INDEX IF NOT EXISTS idx_token_balance_changes_slot ON token_balance_changes(slot);
@@ -0,0 +1,13 @@
+-- Per-account lamport balances before/after each transaction (from meta pre/post balances).
+CREATE TABLE IF NOT EXISTS sol_balance_changes (
+    signature TEXT NOT NULL,
+    account TEXT NOT NULL,
+    slot BIGINT NOT NULL,
+    position INTEGER NOT NULL,
+    pre BIGINT NOT NULL,
+    post BIGINT NOT NULL,
+    PRIMARY KEY (signature, account)
+);
+
+CREATE INDEX IF NOT EXISTS idx_sol_balance_changes_account_slot ON sol_balance_changes(account, slot);
+CREATE INDEX IF NOT EXISTS idx_sol_balance_changes_slot ON sol_balance_changes(slot);
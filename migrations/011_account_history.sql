@@ -0,0 +1,19 @@
+-- Append-only account states; `accounts` keeps only the latest one.
+-- write_version orders Geyser updates within a slot (0 for RPC snapshots).
+ALTER TABLE accounts ADD COLUMN write_version BIGINT NOT NULL DEFAULT 0;
+
+CREATE TABLE IF NOT EXISTS account_history (
+    address TEXT NOT NULL,
+    slot BIGINT NOT NULL,
+    write_version BIGINT NOT NULL,
+    lamports BIGINT NOT NULL,
+    owner TEXT NOT NULL,
+    executable BOOLEAN NOT NULL,
+    data BYTEA,
+    rent_epoch BIGINT NOT NULL,
+    decoded TEXT,
+    recorded_at BIGINT NOT NULL,
+    PRIMARY KEY (address, slot, write_version)
+);
+
+CREATE INDEX IF NOT EXISTS idx_account_history_slot ON account_history(slot);
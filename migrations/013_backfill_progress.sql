@@ -0,0 +1,8 @@
+-- Resumable cursor per historical backfill range (`backfill --from --to`): the next slot to fetch.
+CREATE TABLE IF NOT EXISTS backfill_progress (
+    range_start BIGINT NOT NULL,
+    range_end BIGINT NOT NULL,
+    next_slot BIGINT NOT NULL,
+    updated_at BIGINT NOT NULL,
+    PRIMARY KEY (range_start, range_end)
+);
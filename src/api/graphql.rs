@@ -0,0 +1,635 @@
+//! GraphQL query API (`graphql` feature): `POST /graphql`, with GraphiQL at `GET /graphql`.
+//!
+//! Slots, transactions, accounts and wallets, with nested relations
+//! (slot → transactions → accounts → transactions …) and the same cursor pagination as the
+//! REST routes: list fields take `first` / `after` and return `{ items, nextCursor }`.
+
+use super::ApiState;
+use crate::core::types::{AccountState, Instruction, Slot, SolBalanceChange, Transaction};
+use crate::storage::cache::multi_cache::MultiCache;
+use crate::storage::database::{AccountHistoryRange, AccountVersion, Page, SlotRange, TxCursor};
+use async_graphql::http::GraphiQLSource;
+use async_graphql::{
+    Context, EmptyMutation, EmptySubscription, Json, Object, OutputType, Result, Schema,
+    SimpleObject,
+};
+use axum::response::Html;
+use axum::routing::get;
+use axum::Router;
+use base64::Engine;
+use std::sync::Arc;
+
+/// Nesting and cost caps so one query cannot fan out across the whole index.
+const MAX_DEPTH: usize = 10;
+const MAX_COMPLEXITY: usize = 2_000;
+/// Page sizes for list fields, below the REST limits since pages nest inside each other.
+const DEFAULT_FIRST: u32 = 20;
+const MAX_FIRST: u32 = 100;
+
+pub type IndexerSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+pub fn schema(cache: Arc<MultiCache>) -> IndexerSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(cache)
+        .limit_depth(MAX_DEPTH)
+        .limit_complexity(MAX_COMPLEXITY)
+        .finish()
+}
+
+pub(super) fn routes(cache: Arc<MultiCache>) -> Router<ApiState> {
+    let schema = schema(cache);
+    Router::new().route(
+        "/graphql",
+        get(|| async { Html(GraphiQLSource::build().endpoint("/graphql").finish()) }).post(
+            move |axum::Json(request): axum::Json<async_graphql::Request>| async move {
+                axum::Json(schema.execute(request).await)
+            },
+        ),
+    )
+}
+
+fn cache<'a>(ctx: &Context<'a>) -> &'a Arc<MultiCache> {
+    ctx.data_unchecked::<Arc<MultiCache>>()
+}
+
+#[derive(SimpleObject)]
+#[graphql(concrete(name = "SlotPage", params(SlotNode)))]
+#[graphql(concrete(name = "TransactionPage", params(TransactionNode)))]
+#[graphql(concrete(name = "AccountPage", params(AccountNode)))]
+#[graphql(concrete(name = "WalletPage", params(WalletNode)))]
+pub struct Connection<T: OutputType> {
+    items: Vec<T>,
+    /// Pass as `after` to fetch the next page; `null` on the last page.
+    next_cursor: Option<String>,
+}
+
+impl<T: OutputType> Connection<T> {
+    fn from_page<U>(page: Page<U>, node: impl Fn(U) -> T) -> Self {
+        Self {
+            items: page.items.into_iter().map(node).collect(),
+            next_cursor: page.next_cursor,
+        }
+    }
+}
+
+fn page_limit(first: Option<u32>) -> u32 {
+    first.unwrap_or(DEFAULT_FIRST).clamp(1, MAX_FIRST)
+}
+
+/// A list field costs its selection once per item it may return.
+fn page_complexity(first: Option<u32>, child_complexity: usize) -> usize {
+    page_limit(first) as usize * child_complexity
+}
+
+fn tx_cursor(after: Option<String>) -> Result<Option<TxCursor>> {
+    Ok(after.as_deref().map(str::parse).transpose()?)
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    async fn slot(&self, ctx: &Context<'_>, number: u64) -> Result<Option<SlotNode>> {
+        Ok(cache(ctx).get_slot(number).await?.map(SlotNode))
+    }
+
+    async fn latest_slot(&self, ctx: &Context<'_>) -> Result<Option<SlotNode>> {
+        Ok(cache(ctx).get_latest_slot().await?.map(SlotNode))
+    }
+
+    /// Slots newest first, optionally within `from..=to`.
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
+    async fn slots(
+        &self,
+        ctx: &Context<'_>,
+        from: Option<u64>,
+        to: Option<u64>,
+        first: Option<u32>,
+        after: Option<String>,
+    ) -> Result<Connection<SlotNode>> {
+        let range = SlotRange {
+            from,
+            to,
+            limit: page_limit(first),
+            cursor: after.as_deref().map(str::parse).transpose()?,
+        };
+        let page = cache(ctx).get_slots_range(range).await?;
+        Ok(Connection::from_page(page, SlotNode))
+    }
+
+    async fn transaction(
+        &self,
+        ctx: &Context<'_>,
+        signature: String,
+    ) -> Result<Option<TransactionNode>> {
+        Ok(cache(ctx)
+            .get_transaction(&signature)
+            .await?
+            .map(TransactionNode))
+    }
+
+    /// Transactions touching `account`, invoking `program`, or in `slot` (exactly one filter).
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
+    async fn transactions(
+        &self,
+        ctx: &Context<'_>,
+        account: Option<String>,
+        program: Option<String>,
+        slot: Option<u64>,
+        first: Option<u32>,
+        after: Option<String>,
+    ) -> Result<Connection<TransactionNode>> {
+        let cache = cache(ctx);
+        let limit = page_limit(first);
+        let page = match (account, program, slot) {
+            (Some(account), None, None) => {
+                cache
+                    .get_transactions_for_account(&account, limit, tx_cursor(after)?)
+                    .await?
+            }
+            (None, Some(program), None) => {
+                cache
+                    .get_transactions_for_program(&program, limit, tx_cursor(after)?)
+                    .await?
+            }
+            (None, None, Some(slot)) => cache.get_transactions_in_slot(slot, limit, after).await?,
+            _ => return Err("pass exactly one of account, program or slot".into()),
+        };
+        Ok(Connection::from_page(page, TransactionNode))
+    }
+
+    /// Current state, or the latest recorded state at or before `at_slot`.
+    async fn account(
+        &self,
+        ctx: &Context<'_>,
+        address: String,
+        at_slot: Option<u64>,
+    ) -> Result<Option<AccountNode>> {
+        let account = match at_slot {
+            Some(slot) => cache(ctx).get_account_at_slot(&address, slot).await?,
+            None => cache(ctx).get_account(&address).await?,
+        };
+        Ok(account.map(AccountNode))
+    }
+
+    /// Indexed accounts owned by `owner` (a program id), ordered by address.
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
+    async fn accounts(
+        &self,
+        ctx: &Context<'_>,
+        owner: String,
+        first: Option<u32>,
+        after: Option<String>,
+    ) -> Result<Connection<AccountNode>> {
+        let page = cache(ctx)
+            .get_program_accounts(&owner, page_limit(first), after)
+            .await?;
+        Ok(Connection::from_page(page, AccountNode))
+    }
+
+    /// Tracked wallets, ordered by address.
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
+    async fn wallets(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = true)] active_only: bool,
+        first: Option<u32>,
+        after: Option<String>,
+    ) -> Result<Connection<WalletNode>> {
+        let page = cache(ctx)
+            .list_wallets_page(active_only, page_limit(first), after)
+            .await?;
+        Ok(Connection::from_page(
+            page,
+            |(address, name, created_at)| WalletNode {
+                address,
+                name,
+                created_at,
+            },
+        ))
+    }
+}
+
+pub struct SlotNode(Slot);
+
+#[Object(name = "Slot")]
+impl SlotNode {
+    async fn slot(&self) -> u64 {
+        self.0.slot
+    }
+
+    async fn parent(&self) -> Option<u64> {
+        self.0.parent
+    }
+
+    /// `Processed`, `Confirmed`, `Finalized` or `Dead`.
+    async fn status(&self) -> &str {
+        self.0.status.as_str()
+    }
+
+    async fn timestamp(&self) -> i64 {
+        self.0.timestamp
+    }
+
+    async fn block_hash(&self) -> Option<&str> {
+        self.0.block_hash.as_deref()
+    }
+
+    async fn block_height(&self) -> Option<u64> {
+        self.0.block_height
+    }
+
+    async fn processed_at(&self) -> Option<i64> {
+        self.0.lifecycle.processed_at
+    }
+
+    async fn confirmed_at(&self) -> Option<i64> {
+        self.0.lifecycle.confirmed_at
+    }
+
+    async fn finalized_at(&self) -> Option<i64> {
+        self.0.lifecycle.finalized_at
+    }
+
+    async fn transaction_count(&self, ctx: &Context<'_>) -> Result<u64> {
+        Ok(cache(ctx).count_transactions_in_slot(self.0.slot).await?)
+    }
+
+    /// Transactions indexed for this slot, ordered by signature.
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
+    async fn transactions(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u32>,
+        after: Option<String>,
+    ) -> Result<Connection<TransactionNode>> {
+        let page = cache(ctx)
+            .get_transactions_in_slot(self.0.slot, page_limit(first), after)
+            .await?;
+        Ok(Connection::from_page(page, TransactionNode))
+    }
+}
+
+pub struct TransactionNode(Transaction);
+
+#[Object(name = "Transaction")]
+impl TransactionNode {
+    async fn signature(&self) -> &str {
+        &self.0.signature
+    }
+
+    async fn slot(&self) -> u64 {
+        self.0.slot
+    }
+
+    async fn block_time(&self) -> Option<i64> {
+        self.0.block_time
+    }
+
+    async fn fee(&self) -> u64 {
+        self.0.fee
+    }
+
+    async fn success(&self) -> bool {
+        self.0.success
+    }
+
+    async fn program(&self) -> Option<&str> {
+        self.0.program.as_deref()
+    }
+
+    async fn instruction_count(&self) -> Option<u32> {
+        self.0.instruction_count
+    }
+
+    async fn compute_units(&self) -> Option<u64> {
+        self.0.compute_units
+    }
+
+    async fn account_keys(&self) -> &[String] {
+        &self.0.accounts
+    }
+
+    /// The slot this transaction landed in.
+    async fn block(&self, ctx: &Context<'_>) -> Result<Option<SlotNode>> {
+        Ok(cache(ctx).get_slot(self.0.slot).await?.map(SlotNode))
+    }
+
+    /// Indexed accounts among `accountKeys` (accounts the indexer does not track are skipped).
+    async fn accounts(&self, ctx: &Context<'_>) -> Result<Vec<AccountNode>> {
+        let accounts = cache(ctx).get_accounts(&self.0.accounts).await?;
+        Ok(accounts.into_iter().map(AccountNode).collect())
+    }
+
+    async fn instructions(&self, ctx: &Context<'_>) -> Result<Vec<InstructionNode>> {
+        let mut instructions = cache(ctx).get_instructions(&self.0.signature).await?;
+        cache(ctx).decode_instructions(&mut instructions, &self.0.accounts);
+        Ok(instructions.into_iter().map(InstructionNode::from).collect())
+    }
+
+    async fn sol_balance_changes(&self, ctx: &Context<'_>) -> Result<Vec<SolBalanceChangeNode>> {
+        Ok(cache(ctx)
+            .get_sol_balance_changes(&self.0.signature)
+            .await?
+            .into_iter()
+            .map(SolBalanceChangeNode::from)
+            .collect())
+    }
+}
+
+pub struct AccountNode(AccountState);
+
+#[Object(name = "Account")]
+impl AccountNode {
+    async fn address(&self) -> &str {
+        &self.0.address
+    }
+
+    async fn slot(&self) -> u64 {
+        self.0.slot
+    }
+
+    async fn lamports(&self) -> u64 {
+        self.0.lamports
+    }
+
+    async fn owner(&self) -> &str {
+        &self.0.owner
+    }
+
+    async fn executable(&self) -> bool {
+        self.0.executable
+    }
+
+    async fn rent_epoch(&self) -> u64 {
+        self.0.rent_epoch
+    }
+
+    async fn write_version(&self) -> u64 {
+        self.0.write_version
+    }
+
+    async fn space(&self) -> usize {
+        self.0.data.len()
+    }
+
+    /// Base64 account data.
+    async fn data(&self) -> String {
+        base64::engine::general_purpose::STANDARD.encode(&self.0.data)
+    }
+
+    /// The owner program's decoded view (`{program, parsed}`), when a decoder is registered.
+    async fn decoded(&self, ctx: &Context<'_>) -> Option<Json<serde_json::Value>> {
+        let decoded = match &self.0.decoded {
+            Some(decoded) => Some(decoded.clone()),
+            None => cache(ctx).decode_account(&self.0).await,
+        };
+        decoded.map(|d| Json(serde_json::json!({ "program": d.program, "parsed": d.parsed })))
+    }
+
+    /// Transactions touching this account, newest first.
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
+    async fn transactions(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u32>,
+        after: Option<String>,
+    ) -> Result<Connection<TransactionNode>> {
+        let page = cache(ctx)
+            .get_transactions_for_account(&self.0.address, page_limit(first), tx_cursor(after)?)
+            .await?;
+        Ok(Connection::from_page(page, TransactionNode))
+    }
+
+    /// Recorded states, newest first.
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
+    async fn history(
+        &self,
+        ctx: &Context<'_>,
+        from_slot: Option<u64>,
+        to_slot: Option<u64>,
+        first: Option<u32>,
+        after: Option<String>,
+    ) -> Result<Connection<AccountNode>> {
+        let range = AccountHistoryRange {
+            from_slot,
+            to_slot,
+            limit: page_limit(first),
+            cursor: after.as_deref().map(str::parse::<AccountVersion>).transpose()?,
+        };
+        let page = cache(ctx)
+            .get_account_history(&self.0.address, range)
+            .await?;
+        Ok(Connection::from_page(page, AccountNode))
+    }
+}
+
+pub struct WalletNode {
+    address: String,
+    name: Option<String>,
+    created_at: i64,
+}
+
+#[Object(name = "Wallet")]
+impl WalletNode {
+    async fn address(&self) -> &str {
+        &self.address
+    }
+
+    async fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    async fn created_at(&self) -> i64 {
+        self.created_at
+    }
+
+    async fn account(&self, ctx: &Context<'_>) -> Result<Option<AccountNode>> {
+        Ok(cache(ctx).get_account(&self.address).await?.map(AccountNode))
+    }
+
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
+    async fn transactions(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u32>,
+        after: Option<String>,
+    ) -> Result<Connection<TransactionNode>> {
+        let page = cache(ctx)
+            .get_transactions_for_account(&self.address, page_limit(first), tx_cursor(after)?)
+            .await?;
+        Ok(Connection::from_page(page, TransactionNode))
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "Instruction")]
+pub struct InstructionNode {
+    index: u32,
+    inner_index: Option<u32>,
+    stack_height: Option<u32>,
+    program_id: String,
+    /// Indices into the transaction's `accountKeys`.
+    accounts: Vec<u8>,
+    data: Option<String>,
+    parsed: Option<Json<serde_json::Value>>,
+}
+
+impl From<Instruction> for InstructionNode {
+    fn from(ix: Instruction) -> Self {
+        Self {
+            index: ix.index,
+            inner_index: ix.inner_index,
+            stack_height: ix.stack_height,
+            program_id: ix.program_id,
+            accounts: ix.accounts,
+            data: ix.data,
+            parsed: ix.parsed.map(Json),
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "SolBalanceChange")]
+pub struct SolBalanceChangeNode {
+    account: String,
+    pre: u64,
+    post: u64,
+    delta: i64,
+}
+
+impl From<SolBalanceChange> for SolBalanceChangeNode {
+    fn from(change: SolBalanceChange) -> Self {
+        Self {
+            account: change.account,
+            pre: change.pre,
+            post: change.post,
+            delta: change.delta,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::database::DatabaseStorage;
+    use crate::testing::fixtures::sample_slot;
+    use crate::testing::mock_db::MockDatabase;
+    use crate::testing::mock_sources::sample_account;
+    use crate::utils::metrics::IndexerMetrics;
+
+    #[tokio::test]
+    async fn resolves_nested_slot_transactions_and_accounts() {
+        let db = Arc::new(MockDatabase::new());
+        db.store_slot(&sample_slot(42)).await.unwrap();
+        db.store_account(sample_account("wallet", 500)).await.unwrap();
+        for signature in ["sig1", "sig2"] {
+            db.store_transaction(Transaction {
+                signature: signature.into(),
+                slot: 42,
+                block_time: None,
+                fee: 5000,
+                success: true,
+                accounts: vec!["wallet".into(), "unindexed".into()],
+                program: None,
+                instruction_count: None,
+                compute_units: None,
+            })
+            .await
+            .unwrap();
+        }
+        let cache = Arc::new(MultiCache::new(10, 10, 10, db, IndexerMetrics::new()));
+
+        let response = schema(cache)
+            .execute(
+                r#"{
+                    slot(number: 42) {
+                        status
+                        transactions(first: 1) {
+                            items { signature accounts { address lamports } }
+                            nextCursor
+                        }
+                    }
+                }"#,
+            )
+            .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        let data = response.data.into_json().unwrap();
+        let page = &data["slot"]["transactions"];
+        assert_eq!(page["items"][0]["signature"], "sig1");
+        assert_eq!(page["items"][0]["accounts"][0]["lamports"], 500);
+        assert_eq!(page["items"][0]["accounts"].as_array().unwrap().len(), 1);
+        assert_eq!(page["nextCursor"], "sig1");
+    }
+
+    #[tokio::test]
+    async fn rejects_nested_pages_over_the_complexity_cap() {
+        let cache = Arc::new(MultiCache::new(
+            10,
+            10,
+            10,
+            Arc::new(MockDatabase::new()),
+            IndexerMetrics::new(),
+        ));
+        let schema = schema(cache);
+        let nested = r#"{
+            slots(first: 100) { items { transactions(first: 100) { items { signature } } } }
+        }"#;
+        let response = schema.execute(nested).await;
+        assert_eq!(response.errors[0].message, "Query is too complex.");
+
+        let small = r#"{
+            slots(first: 10) { items { transactions(first: 10) { items { signature } } } }
+        }"#;
+        let response = schema.execute(small).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+    }
+
+    #[tokio::test]
+    async fn transactions_require_exactly_one_filter() {
+        let cache = Arc::new(MultiCache::new(
+            10,
+            10,
+            10,
+            Arc::new(MockDatabase::new()),
+            IndexerMetrics::new(),
+        ));
+        let response = schema(cache)
+            .execute(r#"{ transactions(account: "a", program: "b") { nextCursor } }"#)
+            .await;
+        assert_eq!(
+            response.errors[0].message,
+            "pass exactly one of account, program or slot"
+        );
+    }
+
+    #[tokio::test]
+    async fn pages_wallets_by_address() {
+        let db = MockDatabase::with_wallets(vec!["w3".into(), "w1".into(), "w2".into()]);
+        let cache = Arc::new(MultiCache::new(
+            10,
+            10,
+            10,
+            Arc::new(db),
+            IndexerMetrics::new(),
+        ));
+        let schema = schema(cache);
+
+        let response = schema
+            .execute(r#"{ wallets(first: 2) { items { address } nextCursor } }"#)
+            .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(data["wallets"]["items"][0]["address"], "w1");
+        assert_eq!(data["wallets"]["items"][1]["address"], "w2");
+        assert_eq!(data["wallets"]["nextCursor"], "w2");
+
+        let response = schema
+            .execute(r#"{ wallets(first: 2, after: "w2") { items { address } nextCursor } }"#)
+            .await;
+        let data = response.data.into_json().unwrap();
+        assert_eq!(data["wallets"]["items"][0]["address"], "w3");
+        assert!(data["wallets"]["nextCursor"].is_null());
+    }
+}
@@ -1,17 +1,27 @@
+#[cfg(feature = "graphql")]
+pub mod graphql;
+mod rpc;
+mod stream;
+
 use axum::{
-    extract::{Path, Request, State},
+    extract::{Path, Query, Request, State},
     http::{header, StatusCode},
     middleware::{self, Next},
     response::{IntoResponse, Response},
-    routing::get,
+    routing::{get, post},
     Json, Router,
 };
-use crate::data_sources::solana_rpc::SolanaRpc;
-use crate::data_sources::YellowstoneSource;
+use crate::core::gaps::{GapDetector, DEFAULT_REPORT_WINDOW};
+use crate::data_sources::{SlotSource, YellowstoneSource};
 use crate::storage::cache::multi_cache::MultiCache;
+use crate::core::types::{AccountState, Slot, SolBalanceChange, Transaction};
+use crate::storage::database::{
+    AccountHistoryRange, AccountVersion, SlotRange, TokenChangeCursor, TxCursor,
+};
+use crate::utils::config::ReadinessConfig;
 use crate::utils::errors::{IndexerError, Result};
 use crate::utils::shutdown;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
@@ -20,13 +30,21 @@ use tokio::sync::broadcast;
 
 const READINESS_TIMEOUT: Duration = Duration::from_secs(5);
 
+/// Page size when `limit` is omitted on list endpoints.
+const DEFAULT_PAGE_LIMIT: u32 = 100;
+
+/// Upper bound on `limit` for list endpoints.
+const MAX_PAGE_LIMIT: u32 = 1000;
+
 #[derive(Clone)]
 pub struct ReadinessDeps {
     pub cache: Arc<MultiCache>,
-    pub rpc: Arc<SolanaRpc>,
+    pub rpc: Arc<dyn SlotSource>,
     pub yellowstone: Option<Arc<dyn YellowstoneSource>>,
     /// When set (indexer running), readiness requires an active gRPC stream.
     pub yellowstone_connected: Option<Arc<AtomicBool>>,
+    /// Chain-head lag and stall thresholds.
+    pub limits: ReadinessConfig,
 }
 
 #[derive(Clone)]
@@ -43,6 +61,8 @@ struct ApiState {
     cache: Arc<MultiCache>,
     api_key: Option<String>,
     readiness: Option<ReadinessDeps>,
+    /// Fired on server shutdown so open streams end and graceful shutdown can finish.
+    stream_shutdown: broadcast::Sender<()>,
 }
 
 #[derive(Serialize)]
@@ -54,6 +74,18 @@ struct HealthResponse {
 struct ReadinessResponse {
     status: &'static str,
     checks: HashMap<String, CheckResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lag: Option<IngestionLag>,
+}
+
+/// How far ingestion trails the chain, from the stored checkpoint.
+#[derive(Serialize)]
+struct IngestionLag {
+    checkpoint: u64,
+    /// `None` when the RPC check failed.
+    chain_head: Option<u64>,
+    slots_behind: Option<u64>,
+    seconds_since_last_slot: u64,
 }
 
 #[derive(Serialize)]
@@ -63,24 +95,106 @@ struct CheckResult {
     detail: Option<String>,
 }
 
+#[derive(Deserialize)]
+struct SlotRangeParams {
+    from: Option<u64>,
+    to: Option<u64>,
+    limit: Option<u32>,
+    cursor: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GapParams {
+    from: Option<u64>,
+    to: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct SlotParams {
+    #[serde(default)]
+    include_tx_count: bool,
+}
+
+#[derive(Deserialize)]
+struct AccountParams {
+    /// Include the owner program's decoded view of `data`.
+    #[serde(default)]
+    decoded: bool,
+    /// Point-in-time lookup: the latest recorded state at or before this slot.
+    at_slot: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct AccountHistoryParams {
+    from_slot: Option<u64>,
+    to_slot: Option<u64>,
+    limit: Option<u32>,
+    cursor: Option<String>,
+}
+
+#[derive(Serialize)]
+struct SlotResponse {
+    #[serde(flatten)]
+    slot: Slot,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    transaction_count: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct TransactionResponse {
+    #[serde(flatten)]
+    transaction: Transaction,
+    /// Accounts whose lamport balance the transaction changed, in account order.
+    sol_balance_changes: Vec<SolBalanceChange>,
+}
+
+#[derive(Deserialize)]
+struct PageParams {
+    limit: Option<u32>,
+    cursor: Option<String>,
+}
+
 #[derive(Serialize)]
 struct ErrorBody {
     error: String,
 }
 
 pub fn router(cache: Arc<MultiCache>, api_key: Option<String>, readiness: Option<ReadinessDeps>) -> Router {
-    let state = ApiState {
+    build_router(ApiState {
         cache,
         api_key,
         readiness,
-    };
+        stream_shutdown: broadcast::channel(1).0,
+    })
+}
+
+fn build_router(state: ApiState) -> Router {
     let mut app = Router::new()
         .route("/health", get(health))
         .route("/ready", get(ready))
+        .route("/metrics", get(metrics))
+        .route("/slots", get(list_slots))
         .route("/slots/latest", get(latest_slot))
         .route("/slots/:number", get(slot_by_number))
+        .route("/slots/:number/transactions", get(slot_transactions))
         .route("/transactions/:signature", get(transaction_by_sig))
-        .route("/accounts/:address", get(account_by_address));
+        .route("/transactions/:signature/instructions", get(transaction_instructions))
+        .route("/accounts/:address", get(account_by_address))
+        .route("/accounts/:address/history", get(account_history))
+        .route("/accounts/:address/transactions", get(account_transactions))
+        .route("/accounts/:address/token-transfers", get(account_token_transfers))
+        .route("/accounts/:address/balance-history", get(account_balance_history))
+        .route("/programs/:program_id/transactions", get(program_transactions))
+        .route("/programs/:program_id/accounts", get(program_accounts))
+        .route("/webhooks/dead-letters", get(webhook_dead_letters))
+        .route("/admin/gaps", get(slot_gaps))
+        .route("/rpc", post(rpc::handle))
+        .merge(stream::routes());
+
+    #[cfg(feature = "graphql")]
+    {
+        app = app.merge(graphql::routes(state.cache.clone()));
+    }
 
     if state.api_key.is_some() {
         app = app.route_layer(middleware::from_fn_with_state(
@@ -147,7 +261,13 @@ pub async fn serve_until_shutdown(
 
     let host = if bind_localhost { "127.0.0.1" } else { "0.0.0.0" };
     let addr = format!("{host}:{port}");
-    let app = router(cache, api_key.clone(), readiness);
+    let (stream_shutdown, _) = broadcast::channel(1);
+    let app = build_router(ApiState {
+        cache,
+        api_key: api_key.clone(),
+        readiness,
+        stream_shutdown: stream_shutdown.clone(),
+    });
     let listener = tokio::net::TcpListener::bind(&addr)
         .await
         .map_err(|e| IndexerError::ConfigError(format!("Failed to bind {addr}: {e}")))?;
@@ -162,6 +282,7 @@ pub async fn serve_until_shutdown(
     axum::serve(listener, app.into_make_service())
         .with_graceful_shutdown(async move {
             let _ = shutdown.recv().await;
+            let _ = stream_shutdown.send(());
             tracing::info!("HTTP API shutting down");
         })
         .await
@@ -175,6 +296,20 @@ async fn health() -> Json<HealthResponse> {
     Json(HealthResponse { status: "ok" })
 }
 
+/// Prometheus scrape endpoint (text exposition format).
+async fn metrics(State(state): State<ApiState>) -> Response {
+    let yellowstone_connected = state
+        .readiness
+        .as_ref()
+        .and_then(|deps| deps.yellowstone_connected.as_ref())
+        .map(|flag| flag.load(Ordering::Relaxed));
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        state.cache.metrics().render_prometheus(yellowstone_connected),
+    )
+        .into_response()
+}
+
 /// Readiness — DB, RPC, and gRPC (when configured) must be healthy.
 async fn ready(State(state): State<ApiState>) -> Response {
     let Some(deps) = &state.readiness else {
@@ -189,12 +324,13 @@ async fn ready(State(state): State<ApiState>) -> Response {
                         detail: Some("readiness dependencies not configured".into()),
                     },
                 )]),
+                lag: None,
             }),
         )
             .into_response();
     };
 
-    let checks = run_readiness_checks(deps).await;
+    let (checks, lag) = run_readiness_checks(deps).await;
     let all_ok = checks.values().all(|c| c.ok);
     let status = if all_ok { "ready" } else { "not_ready" };
     let code = if all_ok {
@@ -203,10 +339,12 @@ async fn ready(State(state): State<ApiState>) -> Response {
         StatusCode::SERVICE_UNAVAILABLE
     };
 
-    (code, Json(ReadinessResponse { status, checks })).into_response()
+    (code, Json(ReadinessResponse { status, checks, lag })).into_response()
 }
 
-async fn run_readiness_checks(deps: &ReadinessDeps) -> HashMap<String, CheckResult> {
+async fn run_readiness_checks(
+    deps: &ReadinessDeps,
+) -> (HashMap<String, CheckResult>, Option<IngestionLag>) {
     let mut checks = HashMap::new();
 
     checks.insert(
@@ -223,13 +361,17 @@ async fn run_readiness_checks(deps: &ReadinessDeps) -> HashMap<String, CheckResu
         },
     );
 
+    let mut chain_head = None;
     checks.insert(
         "rpc".into(),
         match tokio::time::timeout(READINESS_TIMEOUT, deps.rpc.current_slot()).await {
-            Ok(Ok(slot)) => CheckResult {
-                ok: true,
-                detail: Some(format!("chain head slot {slot}")),
-            },
+            Ok(Ok(slot)) => {
+                chain_head = Some(slot);
+                CheckResult {
+                    ok: true,
+                    detail: Some(format!("chain head slot {slot}")),
+                }
+            }
             Ok(Err(e)) => CheckResult {
                 ok: false,
                 detail: Some(e.to_string()),
@@ -246,7 +388,80 @@ async fn run_readiness_checks(deps: &ReadinessDeps) -> HashMap<String, CheckResu
 
     checks.insert("grpc".into(), check_grpc(deps).await);
 
-    checks
+    let (ingestion, lag) = check_ingestion(deps, chain_head).await;
+    checks.insert("ingestion".into(), ingestion);
+
+    (checks, lag)
+}
+
+/// Fails when the checkpoint trails the chain head by more than `max_slot_lag` slots, or no
+/// slot has been stored for `max_stall_secs` (a connected but stalled pipeline).
+async fn check_ingestion(
+    deps: &ReadinessDeps,
+    chain_head: Option<u64>,
+) -> (CheckResult, Option<IngestionLag>) {
+    let (checkpoint, updated_at) = match deps.cache.get_checkpoint_status().await {
+        Ok(Some(status)) => status,
+        Ok(None) => {
+            let check = CheckResult {
+                ok: false,
+                detail: Some("no slot stored yet".into()),
+            };
+            return (check, None);
+        }
+        Err(e) => {
+            let check = CheckResult {
+                ok: false,
+                detail: Some(e.to_string()),
+            };
+            return (check, None);
+        }
+    };
+
+    let lag = IngestionLag {
+        checkpoint,
+        chain_head,
+        slots_behind: chain_head.map(|head| head.saturating_sub(checkpoint)),
+        seconds_since_last_slot: chrono::Utc::now()
+            .timestamp()
+            .saturating_sub(updated_at)
+            .max(0) as u64,
+    };
+    let metrics = deps.cache.metrics();
+    if let Some(behind) = lag.slots_behind {
+        metrics.chain_head_lag_slots.store(behind, Ordering::Relaxed);
+    }
+    metrics
+        .last_slot_age_secs
+        .store(lag.seconds_since_last_slot, Ordering::Relaxed);
+
+    let limits = deps.limits;
+    let check = if lag.seconds_since_last_slot > limits.max_stall_secs {
+        CheckResult {
+            ok: false,
+            detail: Some(format!(
+                "no slot stored for {}s (max {}s)",
+                lag.seconds_since_last_slot, limits.max_stall_secs
+            )),
+        }
+    } else if let Some(behind) = lag.slots_behind.filter(|&b| b > limits.max_slot_lag) {
+        CheckResult {
+            ok: false,
+            detail: Some(format!(
+                "{behind} slots behind chain head (max {})",
+                limits.max_slot_lag
+            )),
+        }
+    } else {
+        CheckResult {
+            ok: true,
+            detail: Some(match lag.slots_behind {
+                Some(behind) => format!("{behind} slots behind chain head"),
+                None => format!("checkpoint slot {checkpoint}"),
+            }),
+        }
+    };
+    (check, Some(lag))
 }
 
 async fn check_grpc(deps: &ReadinessDeps) -> CheckResult {
@@ -297,10 +512,64 @@ async fn latest_slot(State(state): State<ApiState>) -> Response {
     }
 }
 
-async fn slot_by_number(State(state): State<ApiState>, Path(number): Path<u64>) -> Response {
-    match state.cache.get_slot(number).await {
-        Ok(Some(slot)) => Json(slot).into_response(),
-        Ok(None) => not_found(&format!("Slot {number} not found")),
+/// Newest-first slot listing; pass `next_cursor` from the previous page as `cursor`.
+async fn list_slots(State(state): State<ApiState>, Query(params): Query<SlotRangeParams>) -> Response {
+    let cursor = match params.cursor.as_deref().map(str::parse::<u64>).transpose() {
+        Ok(cursor) => cursor,
+        Err(_) => return bad_request("cursor must be a slot number"),
+    };
+    let range = SlotRange {
+        from: params.from,
+        to: params.to,
+        limit: page_limit(params.limit),
+        cursor,
+    };
+
+    match state.cache.get_slots_range(range).await {
+        Ok(page) => Json(page).into_response(),
+        Err(e) => api_error(e),
+    }
+}
+
+async fn slot_by_number(
+    State(state): State<ApiState>,
+    Path(number): Path<u64>,
+    Query(params): Query<SlotParams>,
+) -> Response {
+    let slot = match state.cache.get_slot(number).await {
+        Ok(Some(slot)) => slot,
+        Ok(None) => return not_found(&format!("Slot {number} not found")),
+        Err(e) => return api_error(e),
+    };
+
+    let transaction_count = if params.include_tx_count {
+        match state.cache.count_transactions_in_slot(number).await {
+            Ok(count) => Some(count),
+            Err(e) => return api_error(e),
+        }
+    } else {
+        None
+    };
+
+    Json(SlotResponse {
+        slot,
+        transaction_count,
+    })
+    .into_response()
+}
+
+/// Block contents for a slot, ordered by signature (cursor is the last signature seen).
+async fn slot_transactions(
+    State(state): State<ApiState>,
+    Path(number): Path<u64>,
+    Query(params): Query<PageParams>,
+) -> Response {
+    match state
+        .cache
+        .get_transactions_in_slot(number, page_limit(params.limit), params.cursor)
+        .await
+    {
+        Ok(page) => Json(page).into_response(),
         Err(e) => api_error(e),
     }
 }
@@ -309,9 +578,37 @@ async fn transaction_by_sig(
     State(state): State<ApiState>,
     Path(signature): Path<String>,
 ) -> Response {
-    match state.cache.get_transaction(&signature).await {
-        Ok(Some(tx)) => Json(tx).into_response(),
-        Ok(None) => not_found("Transaction not found"),
+    let transaction = match state.cache.get_transaction(&signature).await {
+        Ok(Some(tx)) => tx,
+        Ok(None) => return not_found("Transaction not found"),
+        Err(e) => return api_error(e),
+    };
+    match state.cache.get_sol_balance_changes(&signature).await {
+        Ok(sol_balance_changes) => Json(TransactionResponse {
+            transaction,
+            sol_balance_changes,
+        })
+        .into_response(),
+        Err(e) => api_error(e),
+    }
+}
+
+/// Top-level and inner instructions of a transaction, in execution order.
+async fn transaction_instructions(
+    State(state): State<ApiState>,
+    Path(signature): Path<String>,
+) -> Response {
+    let tx = match state.cache.get_transaction(&signature).await {
+        Ok(Some(tx)) => tx,
+        Ok(None) => return not_found("Transaction not found"),
+        Err(e) => return api_error(e),
+    };
+    match state.cache.get_instructions(&signature).await {
+        Ok(mut instructions) => {
+            // Stored before a decoder was registered for the program.
+            state.cache.decode_instructions(&mut instructions, &tx.accounts);
+            Json(instructions).into_response()
+        }
         Err(e) => api_error(e),
     }
 }
@@ -319,14 +616,238 @@ async fn transaction_by_sig(
 async fn account_by_address(
     State(state): State<ApiState>,
     Path(address): Path<String>,
+    Query(params): Query<AccountParams>,
+) -> Response {
+    let lookup = match params.at_slot {
+        Some(slot) => state.cache.get_account_at_slot(&address, slot).await,
+        None => state.cache.get_account(&address).await,
+    };
+    let mut account = match lookup {
+        Ok(Some(account)) => account,
+        Ok(None) => return not_found("Account not found"),
+        Err(e) => return api_error(e),
+    };
+
+    apply_decoded_view(&state, &mut account, params.decoded).await;
+    Json(account).into_response()
+}
+
+/// Recorded states of `address`, newest first (cursor is `<slot>:<write_version>`).
+async fn account_history(
+    State(state): State<ApiState>,
+    Path(address): Path<String>,
+    Query(params): Query<AccountHistoryParams>,
+    Query(view): Query<AccountParams>,
+) -> Response {
+    let cursor = match params
+        .cursor
+        .as_deref()
+        .map(str::parse::<AccountVersion>)
+        .transpose()
+    {
+        Ok(cursor) => cursor,
+        Err(e) => return bad_request(&e),
+    };
+    let range = AccountHistoryRange {
+        from_slot: params.from_slot,
+        to_slot: params.to_slot,
+        limit: page_limit(params.limit),
+        cursor,
+    };
+
+    let mut page = match state.cache.get_account_history(&address, range).await {
+        Ok(page) => page,
+        Err(e) => return api_error(e),
+    };
+    for account in &mut page.items {
+        apply_decoded_view(&state, account, view.decoded).await;
+    }
+    Json(page).into_response()
+}
+
+/// Strips the decoded view unless requested; decodes on the fly for accounts
+/// stored before a decoder was registered for their owner.
+async fn apply_decoded_view(state: &ApiState, account: &mut AccountState, decoded: bool) {
+    if !decoded {
+        account.decoded = None;
+    } else if account.decoded.is_none() {
+        account.decoded = state.cache.decode_account(account).await;
+    }
+}
+
+/// Transactions touching `address`, newest first (cursor is `<slot>:<signature>`).
+async fn account_transactions(
+    State(state): State<ApiState>,
+    Path(address): Path<String>,
+    Query(params): Query<PageParams>,
+) -> Response {
+    let cursor = match params.cursor.as_deref().map(str::parse::<TxCursor>).transpose() {
+        Ok(cursor) => cursor,
+        Err(e) => return bad_request(&e),
+    };
+
+    match state
+        .cache
+        .get_transactions_for_account(&address, page_limit(params.limit), cursor)
+        .await
+    {
+        Ok(page) => Json(page).into_response(),
+        Err(e) => api_error(e),
+    }
+}
+
+/// Balance changes of token accounts owned by `address`, newest first
+/// (cursor is `<slot>:<signature>:<account>`).
+async fn account_token_transfers(
+    State(state): State<ApiState>,
+    Path(address): Path<String>,
+    Query(params): Query<PageParams>,
+) -> Response {
+    let cursor = match params
+        .cursor
+        .as_deref()
+        .map(str::parse::<TokenChangeCursor>)
+        .transpose()
+    {
+        Ok(cursor) => cursor,
+        Err(e) => return bad_request(&e),
+    };
+
+    match state
+        .cache
+        .get_token_balance_changes_for_owner(&address, page_limit(params.limit), cursor)
+        .await
+    {
+        Ok(page) => Json(page).into_response(),
+        Err(e) => api_error(e),
+    }
+}
+
+/// Lamport balance changes of `address`, one entry per transaction, newest first
+/// (cursor is `<slot>:<signature>`).
+async fn account_balance_history(
+    State(state): State<ApiState>,
+    Path(address): Path<String>,
+    Query(params): Query<PageParams>,
 ) -> Response {
-    match state.cache.get_account(&address).await {
-        Ok(Some(account)) => Json(account).into_response(),
-        Ok(None) => not_found("Account not found"),
+    let cursor = match params.cursor.as_deref().map(str::parse::<TxCursor>).transpose() {
+        Ok(cursor) => cursor,
+        Err(e) => return bad_request(&e),
+    };
+
+    match state
+        .cache
+        .get_sol_balance_history(&address, page_limit(params.limit), cursor)
+        .await
+    {
+        Ok(page) => Json(page).into_response(),
+        Err(e) => api_error(e),
+    }
+}
+
+/// Transactions invoking `program_id` (including via CPI), newest first (cursor is `<slot>:<signature>`).
+async fn program_transactions(
+    State(state): State<ApiState>,
+    Path(program_id): Path<String>,
+    Query(params): Query<PageParams>,
+) -> Response {
+    let cursor = match params.cursor.as_deref().map(str::parse::<TxCursor>).transpose() {
+        Ok(cursor) => cursor,
+        Err(e) => return bad_request(&e),
+    };
+
+    match state
+        .cache
+        .get_transactions_for_program(&program_id, page_limit(params.limit), cursor)
+        .await
+    {
+        Ok(page) => Json(page).into_response(),
+        Err(e) => api_error(e),
+    }
+}
+
+/// Accounts owned by `program_id`, ordered by address (cursor is the last address seen).
+async fn program_accounts(
+    State(state): State<ApiState>,
+    Path(program_id): Path<String>,
+    Query(page): Query<PageParams>,
+    Query(params): Query<AccountParams>,
+) -> Response {
+    let mut page = match state
+        .cache
+        .get_program_accounts(&program_id, page_limit(page.limit), page.cursor)
+        .await
+    {
+        Ok(page) => page,
+        Err(e) => return api_error(e),
+    };
+
+    for account in &mut page.items {
+        apply_decoded_view(&state, account, params.decoded).await;
+    }
+    Json(page).into_response()
+}
+
+/// Webhook deliveries that exhausted their retries, most recently failed first (`limit` only).
+async fn webhook_dead_letters(
+    State(state): State<ApiState>,
+    Query(params): Query<PageParams>,
+) -> Response {
+    match state
+        .cache
+        .get_webhook_dead_letters(page_limit(params.limit))
+        .await
+    {
+        Ok(dead) => Json(dead).into_response(),
+        Err(e) => api_error(e),
+    }
+}
+
+/// Slot coverage of a finalized range: stored, skipped by the leader, or missed by the indexer.
+/// `to` defaults to just below the checkpoint, `from` to 5000 slots before `to`.
+async fn slot_gaps(State(state): State<ApiState>, Query(params): Query<GapParams>) -> Response {
+    let Some(deps) = &state.readiness else {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ErrorBody {
+                error: "gap detection needs an RPC connection".into(),
+            }),
+        )
+            .into_response();
+    };
+
+    let detector = GapDetector::new(deps.rpc.clone(), state.cache.clone());
+    let range = match detector
+        .range(params.from, params.to, DEFAULT_REPORT_WINDOW)
+        .await
+    {
+        Ok(Some(range)) => range,
+        Ok(None) => return not_found("No slots indexed yet; pass `to`"),
+        Err(e) => return api_error(e),
+    };
+    match detector.scan(range.0, range.1).await {
+        Ok(report) => Json(report).into_response(),
+        Err(IndexerError::ConfigError(message)) => bad_request(&message),
         Err(e) => api_error(e),
     }
 }
 
+fn page_limit(requested: Option<u32>) -> u32 {
+    requested
+        .unwrap_or(DEFAULT_PAGE_LIMIT)
+        .clamp(1, MAX_PAGE_LIMIT)
+}
+
+fn bad_request(message: &str) -> Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(ErrorBody {
+            error: message.to_string(),
+        }),
+    )
+        .into_response()
+}
+
 fn not_found(message: &str) -> Response {
     (
         StatusCode::NOT_FOUND,
@@ -352,6 +873,7 @@ mod tests {
     use super::*;
     use axum::{body::Body, http::Request};
     use crate::core::types::Transaction;
+    use crate::data_sources::solana_rpc::SolanaRpc;
     use crate::storage::database::DatabaseStorage;
     use crate::testing::fixtures::sample_slot;
     use crate::testing::mock_db::MockDatabase;
@@ -400,6 +922,64 @@ mod tests {
         assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
     }
 
+    #[tokio::test]
+    async fn metrics_exposes_prometheus_text() {
+        let cache = test_cache(Arc::new(MockDatabase::new()));
+        cache.store_slot(sample_slot(42)).await.unwrap();
+        let app = router(cache, None, None);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/metrics")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let text = String::from_utf8(body.to_vec()).unwrap();
+        assert!(text.contains("solana_indexer_slots_ingested_total 1\n"));
+        assert!(text.contains("solana_indexer_db_write_latency_seconds_count 1\n"));
+    }
+
+    #[tokio::test]
+    async fn ingestion_check_fails_when_checkpoint_trails_chain_head() {
+        let db = Arc::new(MockDatabase::new());
+        let cache = test_cache(db.clone());
+        let deps = ReadinessDeps {
+            cache: cache.clone(),
+            rpc: Arc::new(SolanaRpc::new("http://localhost", IndexerMetrics::new())),
+            yellowstone: None,
+            yellowstone_connected: None,
+            limits: ReadinessConfig::default(),
+        };
+
+        let (check, lag) = check_ingestion(&deps, Some(1_000)).await;
+        assert!(!check.ok);
+        assert!(lag.is_none());
+
+        db.set_checkpoint(900).await.unwrap();
+        let (check, lag) = check_ingestion(&deps, Some(1_000)).await;
+        assert!(check.ok, "{:?}", check.detail);
+        assert_eq!(lag.unwrap().slots_behind, Some(100));
+
+        let (check, _) = check_ingestion(&deps, Some(2_000)).await;
+        assert!(!check.ok);
+        assert_eq!(
+            check.detail.as_deref(),
+            Some("1100 slots behind chain head (max 150)")
+        );
+        assert_eq!(
+            cache.metrics().chain_head_lag_slots.load(Ordering::Relaxed),
+            1_100
+        );
+    }
+
     #[tokio::test]
     async fn latest_slot_returns_json() {
         let db = Arc::new(MockDatabase::new());
@@ -436,6 +1016,106 @@ mod tests {
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
     }
 
+    #[tokio::test]
+    async fn list_slots_paginates_newest_first() {
+        let db = Arc::new(MockDatabase::new());
+        for n in 1..=5 {
+            db.store_slot(&sample_slot(n)).await.unwrap();
+        }
+        let app = test_router(db);
+
+        let first = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/slots?limit=2")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(first.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(first.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let page: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(page["items"][0]["slot"], 5);
+        assert_eq!(page["items"][1]["slot"], 4);
+        assert_eq!(page["next_cursor"], "4");
+
+        let second = app
+            .oneshot(
+                Request::builder()
+                    .uri("/slots?limit=2&cursor=4&from=3")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(second.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let page: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(page["items"].as_array().unwrap().len(), 1);
+        assert_eq!(page["items"][0]["slot"], 3);
+        assert!(page["next_cursor"].is_null());
+    }
+
+    #[tokio::test]
+    async fn slot_transactions_and_count() {
+        let db = Arc::new(MockDatabase::new());
+        db.store_slot(&sample_slot(8)).await.unwrap();
+        for sig in ["b", "a"] {
+            db.store_transaction(Transaction {
+                signature: sig.into(),
+                slot: 8,
+                block_time: None,
+                fee: 5000,
+                success: true,
+                accounts: vec![],
+                program: None,
+                instruction_count: None,
+                compute_units: None,
+            })
+            .await
+            .unwrap();
+        }
+        let app = test_router(db);
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/slots/8?include_tx_count=true")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let slot: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(slot["slot"], 8);
+        assert_eq!(slot["transaction_count"], 2);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/slots/8/transactions")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let page: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(page["items"][0]["signature"], "a");
+        assert_eq!(page["items"][1]["signature"], "b");
+    }
+
     #[tokio::test]
     async fn transaction_endpoint_returns_stored_tx() {
         let db = Arc::new(MockDatabase::new());
@@ -445,12 +1125,26 @@ mod tests {
             block_time: None,
             fee: 100,
             success: true,
-            accounts: vec![],
+            accounts: vec!["payer".into(), "dest".into()],
+            program: None,
+            instruction_count: None,
+            compute_units: None,
         };
         db.store_transaction(tx).await.unwrap();
+        let changes = SolBalanceChange::from_balances(
+            "sigtest",
+            1,
+            &["payer".into(), "dest".into()],
+            &[1_000, 0],
+            &[400, 500],
+        );
+        db.store_sol_balance_changes("sigtest", &changes)
+            .await
+            .unwrap();
         let app = test_router(db);
 
         let response = app
+            .clone()
             .oneshot(
                 Request::builder()
                     .uri("/transactions/sigtest")
@@ -459,8 +1153,194 @@ mod tests {
             )
             .await
             .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["fee"], 100);
+        assert_eq!(json["sol_balance_changes"][0]["delta"], -600);
+
+        let history = app
+            .oneshot(
+                Request::builder()
+                    .uri("/accounts/dest/balance-history")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(history.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let page: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(page["items"][0]["signature"], "sigtest");
+        assert_eq!(page["items"][0]["delta"], 500);
+    }
+
+    #[tokio::test]
+    async fn account_endpoint_decodes_on_request() {
+        let cache = test_cache(Arc::new(MockDatabase::new()));
+        let mut mint = crate::testing::mock_sources::sample_account(
+            "So11111111111111111111111111111111111111112",
+            1_000_000,
+        );
+        mint.owner = crate::core::decoders::builtin::SPL_TOKEN_PROGRAM.into();
+        mint.data = vec![0; crate::core::decoders::builtin::MINT_LEN];
+        mint.data[44] = 9;
+        mint.data[45] = 1;
+        cache.store_account(mint).await.unwrap();
+        let app = router(cache, None, None);
+
+        let uri = "/accounts/So11111111111111111111111111111111111111112";
+        let raw = app
+            .clone()
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(raw.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(json.get("decoded").is_none());
+
+        let decoded = app
+            .oneshot(
+                Request::builder()
+                    .uri(format!("{uri}?decoded=true"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(decoded.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["decoded"]["program"], "spl-token");
+        assert_eq!(json["decoded"]["parsed"]["info"]["decimals"], 9);
+    }
+
+    #[tokio::test]
+    async fn instructions_endpoint_decodes_with_anchor_idl() {
+        const PROGRAM: &str = "Counter111111111111111111111111111111111111";
+        let db = Arc::new(MockDatabase::new());
+        db.store_transaction(Transaction {
+            signature: "sig1".into(),
+            slot: 7,
+            block_time: None,
+            fee: 5000,
+            success: true,
+            accounts: vec!["payer".into(), "counter1".into(), PROGRAM.into()],
+            program: Some(PROGRAM.into()),
+            instruction_count: Some(1),
+            compute_units: None,
+        })
+        .await
+        .unwrap();
+        let mut data = vec![1u8; 8];
+        data.extend_from_slice(&3u64.to_le_bytes());
+        db.store_instructions(
+            "sig1",
+            &[crate::core::types::Instruction {
+                signature: "sig1".into(),
+                slot: 7,
+                index: 0,
+                inner_index: None,
+                stack_height: Some(1),
+                program_id: PROGRAM.into(),
+                accounts: vec![1],
+                data: Some(bs58::encode(&data).into_string()),
+                parsed: None,
+            }],
+        )
+        .await
+        .unwrap();
+
+        // Registered after the instruction was stored: decoded when served.
+        let cache = test_cache(db);
+        let idl = crate::core::decoders::anchor::AnchorIdl::from_json(&serde_json::json!({
+            "address": PROGRAM,
+            "metadata": { "name": "counter" },
+            "instructions": [{
+                "name": "increment",
+                "discriminator": [1, 1, 1, 1, 1, 1, 1, 1],
+                "accounts": [{ "name": "counter", "writable": true }],
+                "args": [{ "name": "by", "type": "u64" }]
+            }]
+        }))
+        .unwrap();
+        cache.decoders().register_anchor(idl);
+        let app = router(cache, None, None);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/transactions/sig1/instructions")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let parsed = &json[0]["parsed"];
+        assert_eq!(parsed["type"], "increment");
+        assert_eq!(parsed["info"]["by"], 3);
+        assert_eq!(parsed["accounts"]["counter"], "counter1");
+    }
 
+    #[tokio::test]
+    async fn account_transactions_lists_wallet_history() {
+        let db = Arc::new(MockDatabase::new());
+        for (sig, slot, accounts) in [
+            ("a", 1, vec!["wallet".to_string()]),
+            ("b", 2, vec!["other".to_string()]),
+            ("c", 3, vec!["payer".to_string(), "wallet".to_string()]),
+        ] {
+            db.store_transaction(Transaction {
+                signature: sig.into(),
+                slot,
+                block_time: None,
+                fee: 5000,
+                success: true,
+                accounts,
+                program: None,
+                instruction_count: None,
+                compute_units: None,
+            })
+            .await
+            .unwrap();
+        }
+        let app = test_router(db);
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/accounts/wallet/transactions?limit=1")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
         assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let page: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(page["items"][0]["signature"], "c");
+        assert_eq!(page["next_cursor"], "3:c");
+
+        let bad_cursor = app
+            .oneshot(
+                Request::builder()
+                    .uri("/accounts/wallet/transactions?cursor=nope")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(bad_cursor.status(), StatusCode::BAD_REQUEST);
     }
 
     #[tokio::test]
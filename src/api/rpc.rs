@@ -0,0 +1,592 @@
+//! Solana JSON-RPC compatible read API (`POST /rpc`), answered from the index.
+//!
+//! Supports `getSlot`, `getBlockHeight`, `getTransaction`, `getSignaturesForAddress`,
+//! `getAccountInfo` and `getMultipleAccounts`, single or batched. Data the indexer has not
+//! stored is reported with [`NOT_INDEXED`] instead of being fetched upstream.
+
+use super::ApiState;
+use crate::core::types::{AccountState, Instruction, SlotStatus, Transaction};
+use crate::storage::database::{SlotRange, TxCursor};
+use crate::utils::errors::IndexerError;
+use axum::{body::Bytes, extract::State, Json};
+use base64::Engine;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+/// Server error: the requested slot, transaction or account is not in the index.
+pub const NOT_INDEXED: i64 = -32099;
+
+/// Same caps as Solana RPC nodes.
+const MAX_SIGNATURES: u32 = 1000;
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+const MAX_BASE58_BYTES: usize = 128;
+/// Requests answered per batch; larger batches are rejected whole.
+const MAX_BATCH_SIZE: usize = 100;
+/// Recent slots searched for a known block height (Yellowstone slots may lack one).
+const BLOCK_HEIGHT_LOOKBACK: u32 = 100;
+
+#[derive(Debug)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    fn invalid_params(message: impl Into<String>) -> Self {
+        Self::new(INVALID_PARAMS, message)
+    }
+
+    fn not_indexed(what: impl std::fmt::Display) -> Self {
+        Self::new(NOT_INDEXED, format!("{what} is not indexed"))
+    }
+}
+
+impl From<IndexerError> for RpcError {
+    fn from(e: IndexerError) -> Self {
+        Self::new(INTERNAL_ERROR, e.to_string())
+    }
+}
+
+type RpcResult = std::result::Result<Value, RpcError>;
+
+#[derive(Deserialize)]
+struct RpcRequest {
+    #[serde(default)]
+    id: Value,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AccountConfig {
+    encoding: Option<String>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TransactionConfig {
+    encoding: Option<String>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SignaturesConfig {
+    limit: Option<u32>,
+    before: Option<String>,
+    until: Option<String>,
+}
+
+/// `POST /rpc`: a JSON-RPC request or batch. Errors are reported in the body with HTTP 200.
+pub(super) async fn handle(State(state): State<ApiState>, body: Bytes) -> Json<Value> {
+    let request: Value = match serde_json::from_slice(&body) {
+        Ok(request) => request,
+        Err(e) => {
+            return Json(error_response(
+                Value::Null,
+                RpcError::new(PARSE_ERROR, e.to_string()),
+            ))
+        }
+    };
+
+    match request {
+        Value::Array(batch) if batch.is_empty() => Json(error_response(
+            Value::Null,
+            RpcError::new(INVALID_REQUEST, "empty batch"),
+        )),
+        Value::Array(batch) if batch.len() > MAX_BATCH_SIZE => Json(error_response(
+            Value::Null,
+            RpcError::new(
+                INVALID_REQUEST,
+                format!(
+                    "batch of {} requests exceeds the limit of {MAX_BATCH_SIZE}",
+                    batch.len()
+                ),
+            ),
+        )),
+        Value::Array(batch) => {
+            let mut responses = Vec::with_capacity(batch.len());
+            for request in batch {
+                responses.push(call(&state, request).await);
+            }
+            Json(Value::Array(responses))
+        }
+        request => Json(call(&state, request).await),
+    }
+}
+
+async fn call(state: &ApiState, request: Value) -> Value {
+    let request: RpcRequest = match serde_json::from_value(request) {
+        Ok(request) => request,
+        Err(e) => {
+            return error_response(Value::Null, RpcError::new(INVALID_REQUEST, e.to_string()))
+        }
+    };
+    match dispatch(state, &request.method, &request.params).await {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": request.id, "result": result }),
+        Err(e) => error_response(request.id, e),
+    }
+}
+
+fn error_response(id: Value, error: RpcError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": error.code, "message": error.message },
+    })
+}
+
+async fn dispatch(state: &ApiState, method: &str, params: &Value) -> RpcResult {
+    match method {
+        "getSlot" => get_slot(state).await,
+        "getBlockHeight" => get_block_height(state).await,
+        "getTransaction" => get_transaction(state, params).await,
+        "getSignaturesForAddress" => get_signatures_for_address(state, params).await,
+        "getAccountInfo" => get_account_info(state, params).await,
+        "getMultipleAccounts" => get_multiple_accounts(state, params).await,
+        _ => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("Method not found: {method}"),
+        )),
+    }
+}
+
+/// Positional parameter `index`, `None` when absent or `null`.
+fn param<T: DeserializeOwned>(params: &Value, index: usize) -> Result<Option<T>, RpcError> {
+    match params.get(index) {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => serde_json::from_value(value.clone())
+            .map(Some)
+            .map_err(|e| RpcError::invalid_params(format!("param {index}: {e}"))),
+    }
+}
+
+fn required<T: DeserializeOwned>(params: &Value, index: usize, name: &str) -> Result<T, RpcError> {
+    param(params, index)?.ok_or_else(|| RpcError::invalid_params(format!("missing {name}")))
+}
+
+async fn latest_slot(state: &ApiState) -> Result<u64, RpcError> {
+    match state.cache.get_latest_slot().await? {
+        Some(slot) => Ok(slot.slot),
+        None => Err(RpcError::not_indexed("Slot")),
+    }
+}
+
+async fn get_slot(state: &ApiState) -> RpcResult {
+    Ok(latest_slot(state).await?.into())
+}
+
+/// Block height of the most recent indexed slot that has one.
+async fn get_block_height(state: &ApiState) -> RpcResult {
+    let recent = state
+        .cache
+        .get_slots_range(SlotRange {
+            limit: BLOCK_HEIGHT_LOOKBACK,
+            ..Default::default()
+        })
+        .await?;
+    recent
+        .items
+        .iter()
+        .find_map(|s| s.block_height)
+        .map(Value::from)
+        .ok_or_else(|| RpcError::not_indexed("Block height"))
+}
+
+async fn get_transaction(state: &ApiState, params: &Value) -> RpcResult {
+    let signature: String = required(params, 0, "signature")?;
+    let config: TransactionConfig = param(params, 1)?.unwrap_or_default();
+    match config.encoding.as_deref() {
+        None | Some("json") => {}
+        Some(other) => {
+            return Err(RpcError::invalid_params(format!(
+                "unsupported encoding {other}; only json is indexed"
+            )))
+        }
+    }
+
+    let tx = state
+        .cache
+        .get_transaction(&signature)
+        .await?
+        .ok_or_else(|| RpcError::not_indexed(format!("Transaction {signature}")))?;
+    let instructions = state.cache.get_instructions(&signature).await?;
+    Ok(transaction_json(&tx, &instructions))
+}
+
+/// `json`-encoded transaction with what the index keeps: account keys, instructions, fee,
+/// compute units and success. Balances and logs are not stored and are omitted. Instructions
+/// indexed only in parsed form have `data: null` and carry their `parsed` payload instead.
+fn transaction_json(tx: &Transaction, instructions: &[Instruction]) -> Value {
+    let compiled = |ix: &Instruction| {
+        let mut value = json!({
+            "programIdIndex": tx.accounts.iter().position(|a| a == &ix.program_id),
+            "accounts": ix.accounts,
+            "data": ix.data,
+            "stackHeight": ix.stack_height,
+        });
+        if let (None, Some(parsed)) = (&ix.data, &ix.parsed) {
+            value["parsed"] = parsed.clone();
+        }
+        value
+    };
+    let top_level: Vec<Value> = instructions
+        .iter()
+        .filter(|ix| ix.inner_index.is_none())
+        .map(compiled)
+        .collect();
+    let mut inner: BTreeMap<u32, Vec<Value>> = BTreeMap::new();
+    for ix in instructions.iter().filter(|ix| ix.inner_index.is_some()) {
+        inner.entry(ix.index).or_default().push(compiled(ix));
+    }
+    let inner: Vec<Value> = inner
+        .into_iter()
+        .map(|(index, instructions)| json!({ "index": index, "instructions": instructions }))
+        .collect();
+
+    let (err, status) = if tx.success {
+        (Value::Null, json!({ "Ok": null }))
+    } else {
+        // The error detail is not indexed.
+        (
+            json!("TransactionFailed"),
+            json!({ "Err": "TransactionFailed" }),
+        )
+    };
+
+    json!({
+        "slot": tx.slot,
+        "blockTime": tx.block_time,
+        "meta": {
+            "err": err,
+            "status": status,
+            "fee": tx.fee,
+            "computeUnitsConsumed": tx.compute_units,
+            "innerInstructions": inner,
+        },
+        "transaction": {
+            "signatures": [tx.signature],
+            "message": {
+                "accountKeys": tx.accounts,
+                "instructions": top_level,
+            },
+        },
+    })
+}
+
+async fn get_signatures_for_address(state: &ApiState, params: &Value) -> RpcResult {
+    let address: String = required(params, 0, "address")?;
+    let config: SignaturesConfig = param(params, 1)?.unwrap_or_default();
+    let limit = config.limit.unwrap_or(MAX_SIGNATURES);
+    if !(1..=MAX_SIGNATURES).contains(&limit) {
+        return Err(RpcError::invalid_params(format!(
+            "limit must be between 1 and {MAX_SIGNATURES}"
+        )));
+    }
+
+    let cursor = match config.before {
+        Some(before) => {
+            let tx = state
+                .cache
+                .get_transaction(&before)
+                .await?
+                .ok_or_else(|| RpcError::not_indexed(format!("Transaction {before}")))?;
+            Some(TxCursor {
+                slot: tx.slot,
+                signature: tx.signature,
+            })
+        }
+        None => None,
+    };
+
+    let page = state
+        .cache
+        .get_transactions_for_account(&address, limit, cursor)
+        .await?;
+    let mut statuses: HashMap<u64, Option<&'static str>> = HashMap::new();
+    let mut signatures = Vec::new();
+    for tx in page
+        .items
+        .iter()
+        .take_while(|tx| config.until.as_ref() != Some(&tx.signature))
+    {
+        let status = match statuses.get(&tx.slot) {
+            Some(status) => *status,
+            None => {
+                let status = state
+                    .cache
+                    .get_slot(tx.slot)
+                    .await?
+                    .and_then(|s| confirmation_status(&s.status));
+                statuses.insert(tx.slot, status);
+                status
+            }
+        };
+        signatures.push(json!({
+            "signature": tx.signature,
+            "slot": tx.slot,
+            "err": if tx.success { Value::Null } else { json!("TransactionFailed") },
+            "memo": null,
+            "blockTime": tx.block_time,
+            "confirmationStatus": status,
+        }));
+    }
+    Ok(Value::Array(signatures))
+}
+
+fn confirmation_status(status: &SlotStatus) -> Option<&'static str> {
+    match status {
+        SlotStatus::Processed => Some("processed"),
+        SlotStatus::Confirmed => Some("confirmed"),
+        SlotStatus::Finalized => Some("finalized"),
+        SlotStatus::Dead => None,
+    }
+}
+
+async fn get_account_info(state: &ApiState, params: &Value) -> RpcResult {
+    let address: String = required(params, 0, "pubkey")?;
+    let config: AccountConfig = param(params, 1)?.unwrap_or_default();
+    let context_slot = latest_slot(state).await.unwrap_or_default();
+
+    let account = state
+        .cache
+        .get_account(&address)
+        .await?
+        .ok_or_else(|| RpcError::not_indexed(format!("Account {address}")))?;
+    let value = account_json(state, account, config.encoding.as_deref()).await?;
+    Ok(json!({ "context": { "slot": context_slot }, "value": value }))
+}
+
+/// Accounts that are not indexed are `null`, as Solana reports missing accounts.
+async fn get_multiple_accounts(state: &ApiState, params: &Value) -> RpcResult {
+    let addresses: Vec<String> = required(params, 0, "pubkeys")?;
+    if addresses.len() > MAX_MULTIPLE_ACCOUNTS {
+        return Err(RpcError::invalid_params(format!(
+            "too many pubkeys: max {MAX_MULTIPLE_ACCOUNTS}"
+        )));
+    }
+    let config: AccountConfig = param(params, 1)?.unwrap_or_default();
+    let context_slot = latest_slot(state).await.unwrap_or_default();
+
+    let mut values = Vec::with_capacity(addresses.len());
+    for address in &addresses {
+        values.push(match state.cache.get_account(address).await? {
+            Some(account) => account_json(state, account, config.encoding.as_deref()).await?,
+            None => Value::Null,
+        });
+    }
+    Ok(json!({ "context": { "slot": context_slot }, "value": values }))
+}
+
+/// Account in Solana's `UiAccount` shape. `encoding` defaults to `base58`, like RPC nodes.
+async fn account_json(
+    state: &ApiState,
+    account: AccountState,
+    encoding: Option<&str>,
+) -> RpcResult {
+    let space = account.data.len();
+    let data = match encoding.unwrap_or("base58") {
+        "base58" | "binary" => {
+            if space > MAX_BASE58_BYTES {
+                return Err(RpcError::invalid_params(format!(
+                    "Encoded binary (base 58) data should be less than {MAX_BASE58_BYTES} bytes, \
+                     please use Base64 encoding."
+                )));
+            }
+            json!([bs58::encode(&account.data).into_string(), "base58"])
+        }
+        "base64" => base64_data(&account),
+        "jsonParsed" => {
+            let decoded = match account.decoded.clone() {
+                Some(decoded) => Some(decoded),
+                None => state.cache.decode_account(&account).await,
+            };
+            match decoded {
+                Some(decoded) => {
+                    json!({ "program": decoded.program, "parsed": decoded.parsed, "space": space })
+                }
+                // RPC nodes fall back to base64 when no parser matches.
+                None => base64_data(&account),
+            }
+        }
+        other => {
+            return Err(RpcError::invalid_params(format!(
+                "unsupported encoding {other}"
+            )))
+        }
+    };
+
+    Ok(json!({
+        "lamports": account.lamports,
+        "owner": account.owner,
+        "data": data,
+        "executable": account.executable,
+        "rentEpoch": account.rent_epoch,
+        "space": space,
+    }))
+}
+
+fn base64_data(account: &AccountState) -> Value {
+    json!([
+        base64::engine::general_purpose::STANDARD.encode(&account.data),
+        "base64"
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::types::{Instruction, Transaction};
+    use crate::storage::cache::multi_cache::MultiCache;
+    use crate::storage::database::DatabaseStorage;
+    use crate::testing::fixtures::sample_slot;
+    use crate::testing::mock_db::MockDatabase;
+    use crate::testing::mock_sources::sample_account;
+    use crate::utils::metrics::IndexerMetrics;
+    use axum::{body::Body, http::Request};
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    async fn rpc(db: Arc<MockDatabase>, request: Value) -> Value {
+        let cache = Arc::new(MultiCache::new(10, 10, 10, db, IndexerMetrics::new()));
+        let response = crate::api::router(cache, None, None)
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/rpc")
+                    .header("content-type", "application/json")
+                    .body(Body::from(request.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn answers_batches_from_the_index() {
+        let db = Arc::new(MockDatabase::new());
+        db.store_slot(&sample_slot(42)).await.unwrap();
+        db.store_account(sample_account("wallet", 500))
+            .await
+            .unwrap();
+        db.store_transaction(Transaction {
+            signature: "sig1".into(),
+            slot: 42,
+            block_time: Some(1_700_000_000),
+            fee: 5000,
+            success: true,
+            accounts: vec!["wallet".into(), "dest".into()],
+            program: None,
+            instruction_count: None,
+            compute_units: Some(150),
+        })
+        .await
+        .unwrap();
+        let ix = Instruction {
+            signature: "sig1".into(),
+            slot: 42,
+            index: 0,
+            inner_index: None,
+            stack_height: None,
+            program_id: "11111111111111111111111111111111".into(),
+            accounts: vec![0, 1],
+            data: Some("3Bxs4h24hBtQy9rw".into()),
+            parsed: None,
+        };
+        let parsed_only = Instruction {
+            index: 1,
+            accounts: vec![],
+            data: None,
+            parsed: Some(json!({ "type": "transfer" })),
+            ..ix.clone()
+        };
+        db.store_instructions("sig1", &[ix, parsed_only])
+            .await
+            .unwrap();
+
+        let responses = rpc(
+            db,
+            json!([
+                { "jsonrpc": "2.0", "id": 1, "method": "getSlot" },
+                { "jsonrpc": "2.0", "id": 2, "method": "getAccountInfo",
+                  "params": ["wallet", { "encoding": "base64" }] },
+                { "jsonrpc": "2.0", "id": 3, "method": "getMultipleAccounts",
+                  "params": [["wallet", "missing"]] },
+                { "jsonrpc": "2.0", "id": 4, "method": "getTransaction", "params": ["sig1"] },
+                { "jsonrpc": "2.0", "id": 5, "method": "getSignaturesForAddress",
+                  "params": ["wallet", { "limit": 10 }] },
+            ]),
+        )
+        .await;
+
+        assert_eq!(responses[0]["result"], 42);
+        let account = &responses[1]["result"];
+        assert_eq!(account["context"]["slot"], 42);
+        assert_eq!(account["value"]["lamports"], 500);
+        assert_eq!(account["value"]["data"][1], "base64");
+        assert_eq!(responses[2]["result"]["value"][1], Value::Null);
+        let tx = &responses[3]["result"];
+        assert_eq!(tx["slot"], 42);
+        assert_eq!(tx["meta"]["fee"], 5000);
+        assert_eq!(tx["transaction"]["message"]["accountKeys"][1], "dest");
+        let instructions = tx["transaction"]["message"]["instructions"]
+            .as_array()
+            .unwrap();
+        assert_eq!(instructions.len(), 2);
+        assert_eq!(instructions[0]["data"], "3Bxs4h24hBtQy9rw");
+        assert_eq!(instructions[1]["data"], Value::Null);
+        assert_eq!(instructions[1]["parsed"]["type"], "transfer");
+        let signatures = responses[4]["result"].as_array().unwrap();
+        assert_eq!(signatures[0]["signature"], "sig1");
+        assert_eq!(signatures[0]["blockTime"], 1_700_000_000);
+    }
+
+    #[tokio::test]
+    async fn reports_unindexed_data_and_unknown_methods_as_errors() {
+        let db = Arc::new(MockDatabase::new());
+        let responses = rpc(
+            db,
+            json!([
+                { "jsonrpc": "2.0", "id": 1, "method": "getTransaction", "params": ["nope"] },
+                { "jsonrpc": "2.0", "id": 2, "method": "getAccountInfo", "params": ["nope"] },
+                { "jsonrpc": "2.0", "id": 3, "method": "sendTransaction", "params": [] },
+                { "jsonrpc": "2.0", "id": 4, "method": "getTransaction" },
+            ]),
+        )
+        .await;
+
+        assert_eq!(responses[0]["error"]["code"], super::NOT_INDEXED);
+        assert_eq!(responses[0]["id"], 1);
+        assert_eq!(responses[1]["error"]["code"], super::NOT_INDEXED);
+        assert_eq!(responses[2]["error"]["code"], super::METHOD_NOT_FOUND);
+        assert_eq!(responses[3]["error"]["code"], super::INVALID_PARAMS);
+
+        let not_a_request = rpc(Arc::new(MockDatabase::new()), json!("getSlot")).await;
+        assert_eq!(not_a_request["error"]["code"], super::INVALID_REQUEST);
+
+        let oversized: Vec<Value> = (0..=super::MAX_BATCH_SIZE)
+            .map(|id| json!({ "jsonrpc": "2.0", "id": id, "method": "getSlot" }))
+            .collect();
+        let rejected = rpc(Arc::new(MockDatabase::new()), Value::Array(oversized)).await;
+        assert_eq!(rejected["error"]["code"], super::INVALID_REQUEST);
+    }
+}
@@ -0,0 +1,300 @@
+//! Live streams of indexer events: SSE under `/stream/...` and a WebSocket at `/stream/ws`.
+//!
+//! Both are fed by the cache's event fan-out ([`MultiCache::subscribe`]), so they only carry
+//! data when the indexer runs in the same process (`start` with `API_PORT`).
+//!
+//! [`MultiCache::subscribe`]: crate::storage::cache::multi_cache::MultiCache::subscribe
+
+use super::ApiState;
+use crate::core::events::IndexerEvent;
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        Path, Query, State,
+    },
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+    routing::get,
+    Router,
+};
+use futures::{Stream, StreamExt};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use tokio::sync::broadcast::error::RecvError;
+
+/// Events one subscription receives.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "channel", rename_all = "snake_case")]
+enum Topic {
+    Slots,
+    /// Transactions touching `account` (any account or program id), or all when unset.
+    Transactions { account: Option<String> },
+    /// Changes to the account at `address`.
+    Account { address: String },
+}
+
+impl Topic {
+    fn matches(&self, event: &IndexerEvent) -> bool {
+        match (self, event) {
+            (Topic::Slots, IndexerEvent::Slot(_)) => true,
+            (Topic::Transactions { account }, IndexerEvent::Transaction(tx)) => account
+                .as_ref()
+                .is_none_or(|a| tx.accounts.contains(a) || tx.program.as_ref() == Some(a)),
+            (Topic::Account { address }, IndexerEvent::Account(change)) => {
+                &change.current.address == address
+            }
+            _ => false,
+        }
+    }
+}
+
+enum StreamItem {
+    Event(Box<IndexerEvent>),
+    /// The subscriber fell behind and this many events were dropped.
+    Lagged(u64),
+}
+
+#[derive(Deserialize)]
+struct TransactionStreamParams {
+    account: Option<String>,
+}
+
+pub(super) fn routes() -> Router<ApiState> {
+    Router::new()
+        .route("/stream/slots", get(stream_slots))
+        .route("/stream/transactions", get(stream_transactions))
+        .route("/stream/accounts/:address", get(stream_account))
+        .route("/stream/ws", get(stream_ws))
+}
+
+/// Events matching `topic` until the client disconnects or the server shuts down.
+fn events(state: &ApiState, topic: Topic) -> impl Stream<Item = StreamItem> + Send + 'static {
+    let receivers = (state.cache.subscribe(), state.stream_shutdown.subscribe());
+    futures::stream::unfold(receivers, move |(mut events, mut stop)| {
+        let topic = topic.clone();
+        async move {
+            loop {
+                let item = tokio::select! {
+                    Ok(()) = stop.recv() => return None,
+                    event = events.recv() => match event {
+                        Ok(event) if topic.matches(&event) => StreamItem::Event(Box::new(event)),
+                        Ok(_) => continue,
+                        Err(RecvError::Lagged(skipped)) => StreamItem::Lagged(skipped),
+                        Err(RecvError::Closed) => return None,
+                    },
+                };
+                return Some((item, (events, stop)));
+            }
+        }
+    })
+}
+
+fn sse(
+    state: &ApiState,
+    topic: Topic,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>> + Send + 'static> {
+    let stream = events(state, topic).map(|item| {
+        let event = match item {
+            StreamItem::Event(event) => {
+                let mut body = serde_json::to_value(&*event).unwrap_or_default();
+                Event::default()
+                    .event(event.kind())
+                    .json_data(body["data"].take())
+            }
+            StreamItem::Lagged(skipped) => Event::default()
+                .event("lagged")
+                .json_data(serde_json::json!({ "skipped": skipped })),
+        };
+        Ok(event.unwrap_or_else(|e| Event::default().event("error").data(e.to_string())))
+    });
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+/// Every stored slot (once per commitment level reached).
+async fn stream_slots(State(state): State<ApiState>) -> Response {
+    sse(&state, Topic::Slots).into_response()
+}
+
+/// Stored transactions, optionally only those touching `account`.
+async fn stream_transactions(
+    State(state): State<ApiState>,
+    Query(params): Query<TransactionStreamParams>,
+) -> Response {
+    sse(
+        &state,
+        Topic::Transactions {
+            account: params.account,
+        },
+    )
+    .into_response()
+}
+
+/// Changes to one account: `{"previous", "current"}` per event.
+async fn stream_account(State(state): State<ApiState>, Path(address): Path<String>) -> Response {
+    sse(&state, Topic::Account { address }).into_response()
+}
+
+/// WebSocket with `subscribe` / `unsubscribe` messages (see `docs/COMMANDS.md`).
+async fn stream_ws(State(state): State<ApiState>, ws: WebSocketUpgrade) -> Response {
+    ws.on_upgrade(move |socket| handle_socket(socket, state))
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum Command {
+    Subscribe {
+        #[serde(flatten)]
+        topic: Topic,
+    },
+    Unsubscribe {
+        subscription: u64,
+    },
+}
+
+#[derive(Default)]
+struct Subscriptions {
+    topics: BTreeMap<u64, Topic>,
+    next_id: u64,
+}
+
+impl Subscriptions {
+    /// Applies a client message and returns the reply.
+    fn handle(&mut self, text: &str) -> serde_json::Value {
+        match serde_json::from_str::<Command>(text) {
+            Ok(Command::Subscribe { topic }) => {
+                self.next_id += 1;
+                self.topics.insert(self.next_id, topic);
+                serde_json::json!({ "type": "subscribed", "subscription": self.next_id })
+            }
+            Ok(Command::Unsubscribe { subscription }) => {
+                if self.topics.remove(&subscription).is_some() {
+                    serde_json::json!({ "type": "unsubscribed", "subscription": subscription })
+                } else {
+                    serde_json::json!({
+                        "type": "error",
+                        "message": format!("unknown subscription {subscription}"),
+                    })
+                }
+            }
+            Err(e) => serde_json::json!({ "type": "error", "message": e.to_string() }),
+        }
+    }
+
+    /// One message per subscription that matches `event`.
+    fn messages(&self, event: &IndexerEvent) -> Vec<serde_json::Value> {
+        let matching: Vec<u64> = self
+            .topics
+            .iter()
+            .filter(|(_, topic)| topic.matches(event))
+            .map(|(&id, _)| id)
+            .collect();
+        if matching.is_empty() {
+            return Vec::new();
+        }
+        let body = serde_json::to_value(event).unwrap_or_default();
+        matching
+            .into_iter()
+            .map(|id| {
+                let mut message = body.clone();
+                message["subscription"] = id.into();
+                message
+            })
+            .collect()
+    }
+}
+
+async fn handle_socket(mut socket: WebSocket, state: ApiState) {
+    let mut events = state.cache.subscribe();
+    let mut stop = state.stream_shutdown.subscribe();
+    let mut subscriptions = Subscriptions::default();
+
+    loop {
+        let outgoing = tokio::select! {
+            Ok(()) = stop.recv() => {
+                let _ = socket.send(Message::Close(None)).await;
+                return;
+            }
+            message = socket.recv() => match message {
+                Some(Ok(Message::Text(text))) => vec![subscriptions.handle(&text)],
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                Some(Ok(_)) => continue,
+            },
+            event = events.recv() => match event {
+                Ok(event) => subscriptions.messages(&event),
+                Err(RecvError::Lagged(skipped)) => {
+                    vec![serde_json::json!({ "type": "lagged", "skipped": skipped })]
+                }
+                Err(RecvError::Closed) => return,
+            },
+        };
+
+        for message in outgoing {
+            if socket.send(Message::Text(message.to_string())).await.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::cache::multi_cache::MultiCache;
+    use crate::testing::fixtures::sample_slot;
+    use crate::testing::mock_db::MockDatabase;
+    use crate::utils::metrics::IndexerMetrics;
+    use axum::{body::Body, http::Request};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn sse_streams_stored_slots() {
+        let db = Arc::new(MockDatabase::new());
+        let cache = Arc::new(MultiCache::new(10, 10, 10, db, IndexerMetrics::new()));
+        let app = crate::api::router(cache.clone(), None, None);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/stream/slots")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.headers()["content-type"], "text/event-stream");
+
+        cache.store_slot(sample_slot(42)).await.unwrap();
+        let mut body = response.into_body().into_data_stream();
+        let frame = body.next().await.unwrap().unwrap();
+        let frame = String::from_utf8(frame.to_vec()).unwrap();
+        assert!(frame.starts_with("event: slot\n"), "{frame}");
+        assert!(frame.contains("\"slot\":42"), "{frame}");
+    }
+
+    #[test]
+    fn websocket_subscriptions_route_matching_events() {
+        let mut subscriptions = Subscriptions::default();
+        let reply = subscriptions.handle(r#"{"op":"subscribe","channel":"slots"}"#);
+        assert_eq!(reply["subscription"], 1);
+        subscriptions.handle(r#"{"op":"subscribe","channel":"account","address":"wallet"}"#);
+
+        let messages = subscriptions.messages(&IndexerEvent::Slot(sample_slot(7)));
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0]["type"], "slot");
+        assert_eq!(messages[0]["subscription"], 1);
+        assert_eq!(messages[0]["data"]["slot"], 7);
+
+        let reply = subscriptions.handle(r#"{"op":"unsubscribe","subscription":1}"#);
+        assert_eq!(reply["type"], "unsubscribed");
+        assert!(subscriptions
+            .messages(&IndexerEvent::Slot(sample_slot(8)))
+            .is_empty());
+
+        let reply = subscriptions.handle(r#"{"op":"subscribe","channel":"account"}"#);
+        assert_eq!(reply["type"], "error");
+    }
+}
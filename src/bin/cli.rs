@@ -1,4 +1,5 @@
 use clap::{Parser, Subcommand};
+use solana_stream_indexer::core::backfill::BackfillOptions;
 use solana_stream_indexer::core::commands;
 use solana_stream_indexer::utils::errors::Result;
 use solana_stream_indexer::utils::logger;
@@ -31,6 +32,31 @@ enum Cmd {
         #[arg(short, long)]
         port: Option<u16>,
     },
+    /// Index full historical blocks (slots + transactions) for a slot range; resumable
+    Backfill {
+        #[arg(long)]
+        from: u64,
+        #[arg(long)]
+        to: u64,
+        /// Concurrent getBlock requests
+        #[arg(short, long, default_value_t = 4)]
+        workers: usize,
+        /// Max getBlock requests per second across all workers
+        #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+        rps: u32,
+        /// Slots written per batch (progress is saved after each)
+        #[arg(long, default_value_t = 100)]
+        batch_size: u64,
+    },
+    /// Report slot coverage (stored / skipped by leader / missed) for a finalized range
+    Gaps {
+        /// Defaults to 5000 slots below `--to`
+        #[arg(long)]
+        from: Option<u64>,
+        /// Defaults to the checkpoint minus a finality margin
+        #[arg(long)]
+        to: Option<u64>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -62,6 +88,15 @@ enum Query {
     Tx { signature: String },
     /// Account state by address
     Account { address: String },
+    /// Transactions touching an account, newest first
+    AccountTxs {
+        address: String,
+        #[arg(short, long, default_value_t = 20)]
+        limit: u32,
+        /// `next_cursor` from a previous page
+        #[arg(short, long)]
+        cursor: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -108,7 +143,27 @@ async fn main() -> Result<()> {
             Query::Slot { number } => commands::query_slot(number).await,
             Query::Tx { signature } => commands::query_tx(signature).await,
             Query::Account { address } => commands::query_account(address).await,
+            Query::AccountTxs {
+                address,
+                limit,
+                cursor,
+            } => commands::query_account_txs(address, limit, cursor).await,
         },
         Cmd::Serve { port } => commands::serve(port).await,
+        Cmd::Backfill {
+            from,
+            to,
+            workers,
+            rps,
+            batch_size,
+        } => {
+            let options = BackfillOptions {
+                workers,
+                requests_per_second: rps,
+                batch_size,
+            };
+            commands::backfill(from, to, options).await
+        }
+        Cmd::Gaps { from, to } => commands::gaps(from, to).await,
     }
 }
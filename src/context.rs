@@ -1,4 +1,6 @@
-use crate::data_sources::solana_rpc::SolanaRpc;
+use crate::core::decoders::anchor;
+use crate::data_sources::failover::{RpcPool, YellowstonePool};
+use crate::data_sources::merged::MergedYellowstone;
 use crate::data_sources::yellowstone_grpc::YellowstoneGrpc;
 use crate::data_sources::{AccountSource, SlotSource, YellowstoneSource};
 use crate::storage::cache::multi_cache::MultiCache;
@@ -6,7 +8,7 @@ use crate::storage::factory::create_storage;
 use crate::utils::config::Config;
 use crate::utils::errors::Result;
 use crate::utils::metrics::IndexerMetrics;
-use crate::utils::redact::redact_database_url;
+use crate::utils::redact::{endpoint_host, redact_database_url};
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use std::time::Duration;
@@ -17,7 +19,7 @@ pub struct AppContext {
     pub cache: Arc<MultiCache>,
     pub metrics: Arc<IndexerMetrics>,
     pub yellowstone_connected: Arc<AtomicBool>,
-    rpc: Arc<SolanaRpc>,
+    rpc: Arc<RpcPool>,
     enrich_rpc: Arc<dyn SlotSource>,
     yellowstone: Option<Arc<dyn YellowstoneSource>>,
 }
@@ -37,24 +39,62 @@ impl AppContext {
             db,
             metrics.clone(),
         ));
-        let rpc = Arc::new(SolanaRpc::new(&config.rpc.solana_rpc_url, metrics.clone()));
-
-        let enrich_rpc: Arc<dyn SlotSource> =
-            match config.rpc.enrichment_rpc_url.as_deref() {
-                Some(url) if url != config.rpc.solana_rpc_url => {
-                    tracing::info!("Using dedicated enrichment RPC");
-                    Arc::new(SolanaRpc::new(url, metrics.clone()))
-                }
-                _ => Arc::clone(&rpc) as Arc<dyn SlotSource>,
-            };
-
-        let yellowstone = config.rpc.yellowstone_grpc_url.as_ref().map(|url| {
-            tracing::info!("Yellowstone gRPC configured at {url}");
-            Arc::new(YellowstoneGrpc::new(
-                url,
-                config.rpc.yellowstone_grpc_token.clone(),
-                config.rpc.yellowstone_tx_accounts.clone(),
-            )) as Arc<dyn YellowstoneSource>
+        if let Some(dir) = &config.anchor_idl_dir {
+            for idl in anchor::load_dir(dir)? {
+                tracing::info!(program = %idl.address, name = %idl.name, "Loaded Anchor IDL");
+                cache.decoders().register_anchor(idl);
+            }
+        }
+        let rpc = Arc::new(RpcPool::from_urls(
+            &config.rpc.solana_rpc_urls,
+            config.failover,
+            metrics.clone(),
+        ));
+        if config.rpc.solana_rpc_urls.len() > 1 {
+            tracing::info!(
+                endpoints = config.rpc.solana_rpc_urls.len(),
+                "RPC failover pool configured"
+            );
+        }
+
+        let enrich_rpc: Arc<dyn SlotSource> = if config.rpc.enrichment_rpc_urls.is_empty()
+            || config.rpc.enrichment_rpc_urls == config.rpc.solana_rpc_urls
+        {
+            Arc::clone(&rpc) as Arc<dyn SlotSource>
+        } else {
+            tracing::info!("Using dedicated enrichment RPC");
+            Arc::new(RpcPool::from_urls(
+                &config.rpc.enrichment_rpc_urls,
+                config.failover,
+                metrics.clone(),
+            ))
+        };
+
+        let yellowstone = (!config.rpc.yellowstone_grpc_urls.is_empty()).then(|| {
+            let endpoints = config
+                .rpc
+                .yellowstone_grpc_urls
+                .iter()
+                .enumerate()
+                .map(|(i, url)| {
+                    tracing::info!("Yellowstone gRPC configured at {}", endpoint_host(url));
+                    let client = YellowstoneGrpc::new(
+                        url,
+                        config.rpc.yellowstone_grpc_token(i),
+                        config.rpc.yellowstone_tx_accounts.clone(),
+                    )
+                    .with_programs(config.index_programs.clone());
+                    (endpoint_host(url), Arc::new(client) as Arc<dyn YellowstoneSource>)
+                })
+                .collect();
+            if config.rpc.yellowstone_merge_streams && config.rpc.yellowstone_grpc_urls.len() > 1 {
+                tracing::info!("Merging redundant Yellowstone streams");
+                Arc::new(MergedYellowstone::new(endpoints, metrics.clone()))
+                    as Arc<dyn YellowstoneSource>
+            } else {
+                Arc::new(YellowstonePool::new(endpoints, config.failover, metrics.clone()))
+                    as Arc<dyn YellowstoneSource>
+            }
         });
 
         let db_label = config
@@ -63,13 +103,16 @@ impl AppContext {
             .as_ref()
             .map(|url| format!("PostgreSQL ({})", redact_database_url(url)))
             .unwrap_or_else(|| format!("SQLite ({:?})", config.storage.sqlite_path));
-        let tx_mode = if !config.rpc.yellowstone_tx_accounts.is_empty() {
+        let tx_mode = if yellowstone.is_some()
+            && !(config.rpc.yellowstone_tx_accounts.is_empty() && config.index_programs.is_empty())
+        {
             format!(
-                "Yellowstone filtered txs ({} accounts)",
-                config.rpc.yellowstone_tx_accounts.len()
+                "Yellowstone filtered txs ({} accounts, {} programs)",
+                config.rpc.yellowstone_tx_accounts.len(),
+                config.index_programs.len()
             )
         } else if yellowstone.is_some() {
-            "Yellowstone slots only (set YELLOWSTONE_TX_ACCOUNTS for txs)".into()
+            "Yellowstone slots only (set YELLOWSTONE_TX_ACCOUNTS or INDEX_PROGRAMS for txs)".into()
         } else {
             "RPC polling (slots + optional block txs)".into()
         };
@@ -82,6 +125,7 @@ impl AppContext {
             },
             transactions = %tx_mode,
             watch_accounts = config.watch_accounts.len(),
+            index_programs = config.index_programs.len(),
             api_port = ?config.api_port,
             "Indexer startup"
         );
@@ -114,7 +158,11 @@ impl AppContext {
         rpc_url: &str,
         metrics: Arc<IndexerMetrics>,
     ) -> Self {
-        let rpc = Arc::new(SolanaRpc::new(rpc_url, metrics.clone()));
+        let rpc = Arc::new(RpcPool::from_urls(
+            &[rpc_url.to_string()],
+            config.failover,
+            metrics.clone(),
+        ));
         let enrich_rpc = Arc::clone(&rpc) as Arc<dyn SlotSource>;
         Self {
             config,
@@ -127,10 +175,6 @@ impl AppContext {
         }
     }
 
-    pub fn rpc_client(&self) -> Arc<SolanaRpc> {
-        Arc::clone(&self.rpc)
-    }
-
     pub fn backfill_max_slots(&self) -> u64 {
         self.config.backfill_max_slots
     }
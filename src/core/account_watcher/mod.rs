@@ -13,11 +13,19 @@ const POLL_INTERVAL_SECS: u64 = 5;
 #[cfg(test)]
 const POLL_INTERVAL_SECS: u64 = 1;
 
+/// `getProgramAccounts` is expensive; rescan program-owned accounts less often.
+#[cfg(not(test))]
+const PROGRAM_POLL_INTERVAL_SECS: u64 = 60;
+
+#[cfg(test)]
+const PROGRAM_POLL_INTERVAL_SECS: u64 = 1;
+
 pub struct AccountWatcher {
     accounts_source: Arc<dyn AccountSource>,
     yellowstone: Option<Arc<dyn YellowstoneSource>>,
     cache: Arc<MultiCache>,
     accounts_to_watch: Vec<String>,
+    programs: Vec<String>,
 }
 
 impl AccountWatcher {
@@ -32,9 +40,17 @@ impl AccountWatcher {
             yellowstone,
             cache,
             accounts_to_watch: accounts,
+            programs: vec![],
         }
     }
 
+    /// Also watch every account owned by `programs` (Geyser owner filter, or
+    /// periodic `getProgramAccounts` when polling).
+    pub fn with_programs(mut self, programs: Vec<String>) -> Self {
+        self.programs = programs;
+        self
+    }
+
     pub async fn fetch_account(&self, address: &str) -> Result<AccountState> {
         let account = self.accounts_source.get_account(address).await?;
         self.cache.store_account(account.clone()).await?;
@@ -47,9 +63,33 @@ impl AccountWatcher {
                 tracing::warn!("Failed to seed account {address}: {e}");
             }
         }
+        for program in &self.programs {
+            match self.accounts_source.get_program_accounts(program).await {
+                Ok(accounts) => {
+                    tracing::info!(program = %program, count = accounts.len(), "Seeded program accounts");
+                    for account in accounts {
+                        self.cache.store_account(account).await?;
+                    }
+                }
+                Err(e) => tracing::warn!("Failed to seed accounts of program {program}: {e}"),
+            }
+        }
         Ok(())
     }
 
+    /// Stores `current`, reporting a change against the cached state (if any).
+    async fn apply_update<F>(&self, current: AccountState, on_change: &mut F) -> Result<()>
+    where
+        F: FnMut(&str, &AccountState, &AccountState),
+    {
+        if let Some(previous) = self.cache.get_account(&current.address).await? {
+            if previous.lamports != current.lamports || previous.data != current.data {
+                on_change(&current.address, &previous, &current);
+            }
+        }
+        self.cache.store_account(current).await
+    }
+
     pub async fn run<F>(&self, on_change: F) -> Result<()>
     where
         F: FnMut(&str, &AccountState, &AccountState),
@@ -71,14 +111,15 @@ impl AccountWatcher {
         F: FnMut(&str, &AccountState, &AccountState),
     {
         if let Some(yellowstone) = &self.yellowstone {
-            if !self.accounts_to_watch.is_empty() {
+            if !self.accounts_to_watch.is_empty() || !self.programs.is_empty() {
                 match yellowstone
-                    .subscribe_accounts(&self.accounts_to_watch)
+                    .subscribe_accounts(&self.accounts_to_watch, &self.programs)
                     .await
                 {
                     Ok(mut stream) => {
                         tracing::info!(
                             count = self.accounts_to_watch.len(),
+                            programs = self.programs.len(),
                             "Account watcher using Yellowstone gRPC filters"
                         );
                         return self
@@ -119,13 +160,7 @@ impl AccountWatcher {
                         return self.run_poll_until(on_change, shutdown).await;
                     };
 
-                    let address = current.address.clone();
-                    if let Some(previous) = self.cache.get_account(&address).await? {
-                        if previous.lamports != current.lamports || previous.data != current.data {
-                            on_change(&address, &previous, &current);
-                        }
-                    }
-                    self.cache.store_account(current).await?;
+                    self.apply_update(current, on_change).await?;
                 }
             }
         }
@@ -141,6 +176,9 @@ impl AccountWatcher {
     {
         tracing::debug!("Account watcher using RPC polling ({}s interval)", POLL_INTERVAL_SECS);
         let mut ticker = interval(Duration::from_secs(POLL_INTERVAL_SECS));
+        let mut program_ticker = interval(Duration::from_secs(PROGRAM_POLL_INTERVAL_SECS));
+        // Seeding already loaded program accounts; skip the immediate first tick.
+        program_ticker.reset();
         let accounts = self.accounts_to_watch.clone();
 
         loop {
@@ -153,18 +191,23 @@ impl AccountWatcher {
                 _ = ticker.tick() => {
                     for address in &accounts {
                         match self.accounts_source.get_account(address).await {
+                            Ok(current) => self.apply_update(current, &mut on_change).await?,
+                            Err(e) => {
+                                tracing::warn!("Failed to fetch account {address}: {e}");
+                            }
+                        }
+                    }
+                }
+                _ = program_ticker.tick(), if !self.programs.is_empty() => {
+                    for program in &self.programs {
+                        match self.accounts_source.get_program_accounts(program).await {
                             Ok(current) => {
-                                if let Some(previous) = self.cache.get_account(address).await? {
-                                    if previous.lamports != current.lamports
-                                        || previous.data != current.data
-                                    {
-                                        on_change(address, &previous, &current);
-                                    }
+                                for account in current {
+                                    self.apply_update(account, &mut on_change).await?;
                                 }
-                                self.cache.store_account(current).await?;
                             }
                             Err(e) => {
-                                tracing::warn!("Failed to fetch account {address}: {e}");
+                                tracing::warn!("Failed to fetch accounts of program {program}: {e}");
                             }
                         }
                     }
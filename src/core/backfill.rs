@@ -0,0 +1,289 @@
+//! Historical backfill (`backfill --from --to`): fetches full blocks over RPC with parallel
+//! workers under a shared request rate limit, writes them in batches, and records progress in
+//! `backfill_progress` so an interrupted run resumes where it stopped.
+
+use crate::core::types::Block;
+use crate::data_sources::SlotSource;
+use crate::storage::cache::multi_cache::MultiCache;
+use crate::utils::errors::{IndexerError, Result};
+use futures::{StreamExt, TryStreamExt};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, Mutex};
+use tokio::time::Instant;
+
+/// Attempts per block before the run stops (progress up to the failing batch is kept).
+const FETCH_ATTEMPTS: u32 = 3;
+
+/// Delay before the first retry of a failed block fetch; doubles per attempt.
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Copy)]
+pub struct BackfillOptions {
+    /// Concurrent `getBlock` requests.
+    pub workers: usize,
+    /// Max `getBlock` requests per second across all workers.
+    pub requests_per_second: u32,
+    /// Slots fetched and written per batch; the cursor advances after each batch.
+    pub batch_size: u64,
+}
+
+impl Default for BackfillOptions {
+    fn default() -> Self {
+        Self {
+            workers: 4,
+            requests_per_second: 10,
+            batch_size: 100,
+        }
+    }
+}
+
+/// Totals for one run (slots already done in an earlier run are not counted).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct BackfillSummary {
+    pub blocks: u64,
+    pub skipped_slots: u64,
+    pub transactions: u64,
+    /// `false` when the run was interrupted before reaching the end of the range.
+    pub complete: bool,
+}
+
+/// Spaces requests evenly so all workers together stay under the rate.
+struct RateLimiter {
+    interval: Duration,
+    next: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    fn new(per_second: u32) -> Self {
+        Self {
+            interval: Duration::from_secs(1) / per_second.max(1),
+            next: Mutex::new(Instant::now()),
+        }
+    }
+
+    async fn acquire(&self) {
+        let at = {
+            let mut next = self.next.lock().await;
+            let at = (*next).max(Instant::now());
+            *next = at + self.interval;
+            at
+        };
+        tokio::time::sleep_until(at).await;
+    }
+}
+
+pub struct HistoricalBackfill {
+    source: Arc<dyn SlotSource>,
+    cache: Arc<MultiCache>,
+    options: BackfillOptions,
+    limiter: RateLimiter,
+}
+
+impl HistoricalBackfill {
+    pub fn new(
+        source: Arc<dyn SlotSource>,
+        cache: Arc<MultiCache>,
+        options: BackfillOptions,
+    ) -> Self {
+        Self {
+            source,
+            cache,
+            limiter: RateLimiter::new(options.requests_per_second),
+            options,
+        }
+    }
+
+    /// Backfills `from..=to`, resuming from the stored cursor of that range. `on_batch` gets the
+    /// next slot to fetch after each written batch.
+    pub async fn run_until(
+        &self,
+        from: u64,
+        to: u64,
+        mut shutdown: broadcast::Receiver<()>,
+        on_batch: impl Fn(u64),
+    ) -> Result<BackfillSummary> {
+        if from > to {
+            return Err(IndexerError::ConfigError(format!(
+                "backfill range is empty: --from {from} is after --to {to}"
+            )));
+        }
+
+        let mut next = self
+            .cache
+            .get_backfill_cursor(from, to)
+            .await?
+            .unwrap_or(from);
+        if next > from {
+            tracing::info!(from, to, resume_at = next, "Resuming backfill");
+        }
+
+        let mut summary = BackfillSummary::default();
+        let batch_size = self.options.batch_size.max(1);
+        while next <= to {
+            let end = next.saturating_add(batch_size - 1).min(to);
+            let blocks = tokio::select! {
+                Ok(()) = shutdown.recv() => return Ok(summary),
+                blocks = self.fetch(next..=end) => blocks?,
+            };
+
+            summary.skipped_slots += end - next + 1 - blocks.len() as u64;
+            summary.blocks += blocks.len() as u64;
+            summary.transactions += blocks
+                .iter()
+                .map(|b| b.transactions.len() as u64)
+                .sum::<u64>();
+            self.cache.store_backfilled_blocks(blocks).await?;
+
+            next = end + 1;
+            self.cache.set_backfill_cursor(from, to, next).await?;
+            on_batch(next);
+        }
+
+        summary.complete = true;
+        Ok(summary)
+    }
+
+    /// Fetches and stores the given slots (e.g. gaps found by the gap detector) in batches,
+    /// without touching any backfill cursor.
+    pub async fn fetch_slots(&self, slots: &[u64]) -> Result<BackfillSummary> {
+        let mut summary = BackfillSummary::default();
+        for batch in slots.chunks(self.options.batch_size.max(1) as usize) {
+            let blocks = self.fetch(batch.iter().copied()).await?;
+            summary.skipped_slots += (batch.len() - blocks.len()) as u64;
+            summary.blocks += blocks.len() as u64;
+            summary.transactions += blocks
+                .iter()
+                .map(|b| b.transactions.len() as u64)
+                .sum::<u64>();
+            self.cache.store_backfilled_blocks(blocks).await?;
+        }
+        summary.complete = true;
+        Ok(summary)
+    }
+
+    /// Blocks produced in `slots` (skipped slots omitted), fetched by the worker pool.
+    async fn fetch(&self, slots: impl Iterator<Item = u64>) -> Result<Vec<Block>> {
+        let blocks: Vec<Option<Block>> = futures::stream::iter(slots)
+            .map(|slot| self.fetch_block(slot))
+            .buffer_unordered(self.options.workers.max(1))
+            .try_collect()
+            .await?;
+        Ok(blocks.into_iter().flatten().collect())
+    }
+
+    async fn fetch_block(&self, slot: u64) -> Result<Option<Block>> {
+        let mut attempt = 1;
+        loop {
+            self.limiter.acquire().await;
+            match self.source.get_block(slot).await {
+                Ok(block) => return Ok(block),
+                Err(e) if attempt < FETCH_ATTEMPTS => {
+                    tracing::debug!(slot, attempt, "Backfill block fetch failed, retrying: {e}");
+                    tokio::time::sleep(RETRY_DELAY * 2u32.pow(attempt - 1)).await;
+                    attempt += 1;
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        slot,
+                        "Backfill block fetch failed after {FETCH_ATTEMPTS} attempts"
+                    );
+                    return Err(e);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::TransactionInfo;
+    use crate::storage::database::DatabaseStorage;
+    use crate::testing::fixtures::sample_slot;
+    use crate::testing::mock_db::MockDatabase;
+    use crate::testing::mock_sources::MockSlotSource;
+    use crate::utils::metrics::IndexerMetrics;
+
+    fn block(slot: u64, signatures: &[&str]) -> Block {
+        Block {
+            slot: sample_slot(slot),
+            transactions: signatures
+                .iter()
+                .map(|sig| TransactionInfo {
+                    signature: sig.to_string(),
+                    slot,
+                    success: true,
+                    fee: 5000,
+                    program: "11111111111111111111111111111111".into(),
+                    instructions: 1,
+                    compute_units: 150,
+                    accounts: vec!["payer".into()],
+                    timestamp: 0,
+                    block_time: Some(1_700_000_000),
+                    instruction_details: vec![],
+                    token_balance_changes: vec![],
+                    sol_balance_changes: vec![],
+                })
+                .collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn backfills_range_in_batches_and_resumes_from_cursor() {
+        let db = Arc::new(MockDatabase::new());
+        let cache = Arc::new(MultiCache::new(
+            10,
+            10,
+            10,
+            db.clone(),
+            IndexerMetrics::new(),
+        ));
+        // Slot 12 was skipped.
+        let source = Arc::new(MockSlotSource::with_blocks(vec![
+            block(10, &["a"]),
+            block(11, &["b", "c"]),
+            block(13, &[]),
+            block(14, &["d"]),
+        ]));
+        let backfill = HistoricalBackfill::new(
+            source,
+            cache.clone(),
+            BackfillOptions {
+                workers: 2,
+                requests_per_second: 1000,
+                batch_size: 2,
+            },
+        );
+
+        let batches = std::sync::Mutex::new(Vec::new());
+        let summary = backfill
+            .run_until(10, 14, broadcast::channel(1).1, |next| {
+                batches.lock().unwrap().push(next)
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(
+            summary,
+            BackfillSummary {
+                blocks: 4,
+                skipped_slots: 1,
+                transactions: 4,
+                complete: true,
+            }
+        );
+        assert_eq!(*batches.lock().unwrap(), vec![12, 14, 15]);
+        assert!(db.get_slot(12).await.unwrap().is_none());
+        assert_eq!(db.get_transaction("c").await.unwrap().unwrap().slot, 11);
+        // The live checkpoint is untouched.
+        assert_eq!(db.get_checkpoint().await.unwrap(), None);
+
+        let rerun = backfill
+            .run_until(10, 14, broadcast::channel(1).1, |_| {})
+            .await
+            .unwrap();
+        assert_eq!(rerun.blocks, 0);
+        assert!(rerun.complete);
+    }
+}
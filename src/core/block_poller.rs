@@ -0,0 +1,193 @@
+//! RPC indexing mode: enumerates every produced block with `getBlocks` and fetches each one in
+//! slot order, instead of sampling the chain head.
+
+use crate::core::channels;
+use crate::core::types::Block;
+use crate::data_sources::SlotSource;
+use crate::utils::errors::Result;
+use futures::StreamExt;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Widest `getBlocks` range requested per poll; a node that fell behind catches up over
+/// several polls.
+const MAX_SLOTS_PER_POLL: u64 = 500;
+
+/// Attempts per block before it is left to the gap repairer.
+const FETCH_ATTEMPTS: u32 = 3;
+
+const RETRY_DELAY: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Copy)]
+pub struct BlockPollerOptions {
+    /// Delay between polls once the poller has caught up with the head.
+    pub interval: Duration,
+    /// Slots to stay behind the RPC head, so nodes behind a load balancer all have the block.
+    pub lag_slots: u64,
+    /// Concurrent `getBlock` requests.
+    pub concurrency: usize,
+    /// When resuming, slots further than this behind the head are not walked (see `backfill`).
+    pub max_catch_up: u64,
+}
+
+impl Default for BlockPollerOptions {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_millis(400),
+            lag_slots: 2,
+            concurrency: 4,
+            max_catch_up: 100,
+        }
+    }
+}
+
+pub struct BlockPoller {
+    source: Arc<dyn SlotSource>,
+    options: BlockPollerOptions,
+}
+
+impl BlockPoller {
+    pub fn new(source: Arc<dyn SlotSource>, options: BlockPollerOptions) -> Self {
+        Self { source, options }
+    }
+
+    /// Streams produced blocks in slot order, starting after `resume_after` (or at the lagged
+    /// head). The stream ends when the receiver is dropped.
+    pub fn spawn(self, resume_after: Option<u64>) -> mpsc::Receiver<Block> {
+        let (tx, rx) = channels::block_channel();
+        tokio::spawn(async move { self.run(resume_after, tx).await });
+        rx
+    }
+
+    async fn run(&self, mut last: Option<u64>, tx: mpsc::Sender<Block>) {
+        loop {
+            let caught_up = match self.poll(&mut last, &tx).await {
+                Ok(caught_up) => caught_up,
+                Err(e) => {
+                    tracing::warn!("RPC block poll failed: {e}");
+                    true
+                }
+            };
+            if tx.is_closed() {
+                return;
+            }
+            if caught_up {
+                tokio::time::sleep(self.options.interval).await;
+            }
+        }
+    }
+
+    /// Sends the blocks after `last` up to the lagged head. Returns whether it reached the head.
+    async fn poll(&self, last: &mut Option<u64>, tx: &mpsc::Sender<Block>) -> Result<bool> {
+        let head = self.source.current_slot().await?;
+        let target = head.saturating_sub(self.options.lag_slots);
+        let oldest = target.saturating_sub(self.options.max_catch_up);
+        let start = match *last {
+            Some(l) if l >= target => return Ok(true),
+            Some(l) if l + 1 >= oldest => l + 1,
+            Some(l) => {
+                tracing::warn!(
+                    resume_after = l,
+                    start = oldest,
+                    "RPC poller is too far behind the head; skipping ahead (use `backfill`)"
+                );
+                oldest
+            }
+            None => target,
+        };
+        let end = target.min(start.saturating_add(MAX_SLOTS_PER_POLL - 1));
+
+        let produced = self.source.get_blocks(start, end).await?;
+        let mut blocks = futures::stream::iter(produced)
+            .map(|slot| self.fetch_block(slot))
+            .buffered(self.options.concurrency.max(1));
+        while let Some(block) = blocks.next().await {
+            if let Some(block) = block {
+                if tx.send(block).await.is_err() {
+                    return Ok(true);
+                }
+            }
+        }
+
+        *last = Some(end);
+        Ok(end == target)
+    }
+
+    /// `None` for a block that still failed after retries; the gap repairer picks it up later.
+    async fn fetch_block(&self, slot: u64) -> Option<Block> {
+        for attempt in 1..=FETCH_ATTEMPTS {
+            match self.source.get_block(slot).await {
+                Ok(block) => return block,
+                Err(e) if attempt < FETCH_ATTEMPTS => {
+                    tracing::debug!(slot, attempt, "Block fetch failed, retrying: {e}");
+                    tokio::time::sleep(RETRY_DELAY).await;
+                }
+                Err(e) => tracing::warn!(slot, "Block fetch failed, leaving a gap: {e}"),
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::fixtures::sample_slot;
+    use crate::testing::mock_sources::MockSlotSource;
+
+    fn block(slot: u64, parent: u64) -> Block {
+        let mut slot = sample_slot(slot);
+        slot.parent = Some(parent);
+        Block {
+            slot,
+            transactions: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn walks_every_produced_block_in_order_behind_the_head() {
+        // 13 was skipped, so 14's parent is 12; the head is 20 and the lag keeps 19-20 back.
+        let mut blocks = vec![block(11, 10), block(12, 11), block(14, 12)];
+        blocks.extend((15..=20).map(|s| block(s, s - 1)));
+        let source = Arc::new(MockSlotSource::with_blocks(blocks));
+        let poller = BlockPoller::new(
+            source,
+            BlockPollerOptions {
+                interval: Duration::from_millis(10),
+                lag_slots: 2,
+                concurrency: 3,
+                max_catch_up: 100,
+            },
+        );
+
+        let mut rx = poller.spawn(Some(10));
+        let mut received = Vec::new();
+        while received.len() < 7 {
+            let block = tokio::time::timeout(Duration::from_secs(2), rx.recv())
+                .await
+                .expect("poller should emit blocks")
+                .unwrap();
+            received.push((block.slot.slot, block.slot.parent));
+        }
+
+        assert_eq!(
+            received,
+            vec![
+                (11, Some(10)),
+                (12, Some(11)),
+                (14, Some(12)),
+                (15, Some(14)),
+                (16, Some(15)),
+                (17, Some(16)),
+                (18, Some(17)),
+            ]
+        );
+        assert!(
+            tokio::time::timeout(Duration::from_millis(50), rx.recv())
+                .await
+                .is_err(),
+            "slots within the lag should not be emitted"
+        );
+    }
+}
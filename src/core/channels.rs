@@ -1,4 +1,4 @@
-use crate::core::types::{Slot, TransactionInfo};
+use crate::core::types::{Block, Slot, TransactionInfo};
 use tokio::sync::mpsc;
 
 /// Slot stream capacity (architecture: 1000).
@@ -7,6 +7,9 @@ pub const SLOT_CHANNEL_CAPACITY: usize = 1000;
 /// Transaction stream capacity (architecture: 10000).
 pub const TX_CHANNEL_CAPACITY: usize = 10000;
 
+/// Full blocks from the RPC poller (each carries its transactions, so kept small).
+pub const BLOCK_CHANNEL_CAPACITY: usize = 100;
+
 pub fn slot_channel() -> (mpsc::Sender<Slot>, mpsc::Receiver<Slot>) {
     mpsc::channel(SLOT_CHANNEL_CAPACITY)
 }
@@ -14,3 +17,7 @@ pub fn slot_channel() -> (mpsc::Sender<Slot>, mpsc::Receiver<Slot>) {
 pub fn transaction_channel() -> (mpsc::Sender<TransactionInfo>, mpsc::Receiver<TransactionInfo>) {
     mpsc::channel(TX_CHANNEL_CAPACITY)
 }
+
+pub fn block_channel() -> (mpsc::Sender<Block>, mpsc::Receiver<Block>) {
+    mpsc::channel(BLOCK_CHANNEL_CAPACITY)
+}
@@ -0,0 +1,46 @@
+use crate::context::AppContext;
+use crate::core::backfill::{BackfillOptions, HistoricalBackfill};
+use crate::utils::cli_animations::Cli;
+use crate::utils::errors::Result;
+use crate::utils::shutdown;
+
+pub async fn backfill(from: u64, to: u64, options: BackfillOptions) -> Result<()> {
+    Cli::banner();
+    let ctx = AppContext::new().await?;
+
+    Cli::success(&format!(
+        "Backfilling slots {from}..={to} ({} workers, {} req/s)",
+        options.workers, options.requests_per_second
+    ));
+    Cli::info("Progress is saved after every batch; re-run the same range to resume");
+    Cli::info("Ctrl+C to stop");
+
+    let shutdown_tx = shutdown::channel();
+    shutdown::spawn_on_ctrl_c(
+        shutdown_tx.clone(),
+        "Shutdown signal received, stopping backfill...",
+    );
+
+    let backfill = HistoricalBackfill::new(ctx.slot_source(), ctx.cache.clone(), options);
+    let summary = backfill
+        .run_until(from, to, shutdown_tx.subscribe(), |next| {
+            let done = next - from;
+            tracing::info!(
+                next_slot = next,
+                progress = %format!("{done}/{}", to - from + 1),
+                "Backfill batch stored"
+            );
+        })
+        .await?;
+
+    let totals = format!(
+        "{} blocks, {} transactions, {} skipped slots",
+        summary.blocks, summary.transactions, summary.skipped_slots
+    );
+    if summary.complete {
+        Cli::success(&format!("Backfill complete: {totals}"));
+    } else {
+        Cli::warning(&format!("Backfill stopped: {totals}; re-run to resume"));
+    }
+    Ok(())
+}
@@ -1,13 +1,12 @@
 use crate::core::account_watcher::AccountWatcher;
-use crate::core::types::{AccountState, Slot, TransactionInfo};
+use crate::core::runtime::AccountChangeHandler;
+use crate::core::slot_pipeline::{SlotHandler, TxHandler};
+use crate::core::types::{Slot, TransactionInfo};
 use crate::utils::cli_animations::Cli;
 use crate::utils::errors::Result;
 use std::sync::Arc;
 
-pub fn slot_and_tx_handlers() -> (
-    Arc<dyn Fn(Slot, Option<String>) + Send + Sync>,
-    Arc<dyn Fn(TransactionInfo) + Send + Sync>,
-) {
+pub fn slot_and_tx_handlers() -> (SlotHandler, TxHandler) {
     let on_slot = Arc::new(|slot: Slot, leader: Option<String>| {
         Cli::slot(&slot, leader.as_deref())
     });
@@ -25,7 +24,7 @@ pub fn slot_and_tx_handlers() -> (
     (on_slot, on_tx)
 }
 
-pub fn account_change_handler() -> Arc<dyn Fn(&str, &AccountState, &AccountState) + Send + Sync> {
+pub fn account_change_handler() -> AccountChangeHandler {
     Arc::new(|addr, prev, curr| {
         Cli::account_change(addr, prev.lamports, curr.lamports, curr.slot);
     })
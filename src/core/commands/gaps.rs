@@ -0,0 +1,47 @@
+use crate::context::AppContext;
+use crate::core::gaps::{GapDetector, DEFAULT_REPORT_WINDOW};
+use crate::utils::cli_animations::Cli;
+use crate::utils::errors::Result;
+
+/// Missed ranges printed before the list is cut short.
+const MAX_PRINTED_RANGES: usize = 20;
+
+pub async fn gaps(from: Option<u64>, to: Option<u64>) -> Result<()> {
+    Cli::banner();
+    let ctx = AppContext::new().await?;
+
+    let detector = GapDetector::new(ctx.slot_source(), ctx.cache.clone());
+    let Some((from, to)) = detector.range(from, to, DEFAULT_REPORT_WINDOW).await? else {
+        Cli::warning("No slots indexed yet. Run `indexer start` first or pass --to.");
+        return Ok(());
+    };
+    let report = detector.scan(from, to).await?;
+
+    Cli::success(&format!(
+        "Slots {from}..={to}: {:.2}% of produced blocks stored",
+        report.coverage * 100.0
+    ));
+    Cli::info(&format!(
+        "{} stored, {} skipped by leader, {} missed by indexer",
+        report.stored, report.skipped, report.missed
+    ));
+    for range in report.missed_ranges.iter().take(MAX_PRINTED_RANGES) {
+        if range.start == range.end {
+            println!("    missed {}", range.start);
+        } else {
+            println!("    missed {}..={}", range.start, range.end);
+        }
+    }
+    if report.missed_ranges.len() > MAX_PRINTED_RANGES {
+        Cli::info(&format!(
+            "... and {} more ranges (GET /admin/gaps lists up to 1000)",
+            report.missed_ranges.len() - MAX_PRINTED_RANGES
+        ));
+    }
+    if report.missed > 0 {
+        Cli::info(
+            "`indexer start` repairs missed slots in the background (GAP_SCAN_INTERVAL_SECS)",
+        );
+    }
+    Ok(())
+}
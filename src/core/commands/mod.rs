@@ -1,11 +1,15 @@
+pub mod backfill;
 pub mod display;
+pub mod gaps;
 pub mod query;
 pub mod serve;
 pub mod start;
 pub mod wallets;
 pub mod watch;
 
-pub use query::{query_account, query_latest, query_slot, query_tx};
+pub use backfill::backfill;
+pub use gaps::gaps;
+pub use query::{query_account, query_account_txs, query_latest, query_slot, query_tx};
 pub use serve::serve;
 pub use start::{start, track_slots};
 pub use wallets::{wallet_add, wallet_list, wallet_remove};
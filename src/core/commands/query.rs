@@ -1,5 +1,6 @@
 use colored::*;
 use crate::context::AppContext;
+use crate::storage::database::TxCursor;
 use crate::utils::cli_animations::Cli;
 use crate::utils::errors::Result;
 
@@ -57,6 +58,21 @@ pub async fn query_tx(signature: String) -> Result<()> {
                     "no".bright_red()
                 }
             );
+            if let Some(program) = &tx.program {
+                println!("    {} {}", "Program:".bright_white(), program);
+            }
+            if let (Some(ix), Some(cu)) = (tx.instruction_count, tx.compute_units) {
+                println!(
+                    "    {} {}  {} {}",
+                    "Instructions:".bright_white(),
+                    ix,
+                    "CU:".bright_white(),
+                    cu
+                );
+            }
+            if let Some(block_time) = tx.block_time {
+                println!("    {} {}", "Block time:".bright_white(), block_time);
+            }
             println!(
                 "    {} {}",
                 "Accounts:".bright_white(),
@@ -81,3 +97,47 @@ pub async fn query_account(address: String) -> Result<()> {
     }
     Ok(())
 }
+
+pub async fn query_account_txs(address: String, limit: u32, cursor: Option<String>) -> Result<()> {
+    Cli::banner();
+    let cursor = match cursor.as_deref().map(str::parse::<TxCursor>).transpose() {
+        Ok(cursor) => cursor,
+        Err(e) => {
+            Cli::error("Cursor", &e);
+            return Ok(());
+        }
+    };
+
+    let ctx = AppContext::new().await?;
+    let page = ctx
+        .cache
+        .get_transactions_for_account(&address, limit, cursor)
+        .await?;
+
+    if page.items.is_empty() {
+        Cli::warning("No indexed transactions for this account");
+        return Ok(());
+    }
+
+    Cli::success(&format!("{} transaction(s) for {}", page.items.len(), address));
+    println!();
+    for tx in &page.items {
+        println!(
+            "    {} {}  {}  {} {}",
+            "Slot:".bright_white(),
+            tx.slot.to_string().bright_yellow(),
+            if tx.success {
+                "ok  ".bright_green()
+            } else {
+                "fail".bright_red()
+            },
+            tx.signature.bright_cyan(),
+            format!("(fee {})", tx.fee).bright_black()
+        );
+    }
+    println!();
+    if let Some(next) = page.next_cursor {
+        Cli::info(&format!("More results: --cursor {next}"));
+    }
+    Ok(())
+}
@@ -9,7 +9,7 @@ pub async fn serve(port: Option<u16>) -> Result<()> {
     let port = port.or(ctx.config.api_port).unwrap_or(8080);
 
     Cli::success(&format!("Starting HTTP query API on port {port}"));
-    Cli::info("Endpoints: /health, /ready, /slots/latest, /slots/{{n}}, /transactions/{{sig}}, /accounts/{{addr}}");
+    Cli::info("Endpoints: /health, /ready, /slots, /slots/latest, /slots/{{n}}, /slots/{{n}}/transactions, /transactions/{{sig}}, /accounts/{{addr}}, /accounts/{{addr}}/transactions");
     if ctx.config.api_key.is_some() {
         Cli::info("Auth: set X-API-Key or Authorization: Bearer <API_KEY>");
     } else {
@@ -22,9 +22,10 @@ pub async fn serve(port: Option<u16>) -> Result<()> {
 
     let readiness = ReadinessDeps {
         cache: ctx.cache.clone(),
-        rpc: ctx.rpc_client(),
+        rpc: ctx.slot_source(),
         yellowstone: ctx.yellowstone_source(),
         yellowstone_connected: None,
+        limits: ctx.config.readiness,
     };
 
     api::serve(ApiServeConfig {
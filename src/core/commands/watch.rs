@@ -8,7 +8,7 @@ pub async fn watch_account(address: String) -> Result<()> {
     Cli::banner();
 
     let ctx = AppContext::new().await?;
-    Cli::connecting(&ctx.config.rpc.solana_rpc_url);
+    Cli::connecting(&ctx.config.rpc.solana_rpc_urls.join(", "));
 
     let watcher = AccountWatcher::with_accounts(
         ctx.account_source(),
@@ -0,0 +1,569 @@
+//! Borsh decoding driven by Anchor IDL files (the 0.30+ spec and legacy pre-0.30 IDLs).
+//!
+//! Accounts and instructions are matched on their 8-byte discriminator. Legacy IDLs do
+//! not list discriminators, so they are derived the way Anchor does:
+//! `sha256("account:<Name>")` and `sha256("global:<snake_name>")`.
+
+use crate::core::decoders::{AccountDecoder, DecodeContext, InstructionDecoder};
+use crate::core::types::{AccountState, DecodedAccount};
+use crate::utils::errors::{IndexerError, Result};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::path::Path;
+
+const DISCRIMINATOR_LEN: usize = 8;
+
+/// Guards against self-referencing type aliases in malformed IDLs.
+const MAX_TYPE_DEPTH: usize = 64;
+
+type Discriminator = [u8; DISCRIMINATOR_LEN];
+
+struct AccountDef {
+    name: String,
+    discriminator: Discriminator,
+}
+
+struct InstructionDef {
+    name: String,
+    discriminator: Discriminator,
+    args: Vec<(String, Value)>,
+    /// Account names in instruction order; composite account groups are flattened as `group.name`.
+    accounts: Vec<String>,
+}
+
+/// A parsed Anchor IDL; decodes both the program's accounts and its instructions.
+pub struct AnchorIdl {
+    pub address: String,
+    pub name: String,
+    accounts: Vec<AccountDef>,
+    instructions: Vec<InstructionDef>,
+    /// Type name → IDL type definition (`{ "name", "type": { "kind", .. } }`).
+    types: HashMap<String, Value>,
+}
+
+impl AnchorIdl {
+    pub fn from_json(idl: &Value) -> Result<Self> {
+        let address = idl
+            .get("address")
+            .or_else(|| idl.pointer("/metadata/address"))
+            .and_then(Value::as_str)
+            .ok_or_else(|| invalid_idl("missing program address"))?
+            .to_string();
+        let name = idl
+            .pointer("/metadata/name")
+            .or_else(|| idl.get("name"))
+            .and_then(Value::as_str)
+            .unwrap_or(&address)
+            .to_string();
+
+        let mut types: HashMap<String, Value> = entries(idl, "types")
+            .filter_map(|t| Some((t.get("name")?.as_str()?.to_string(), t.clone())))
+            .collect();
+
+        let mut accounts = Vec::new();
+        for account in entries(idl, "accounts") {
+            let account_name = entry_name(account)?;
+            // Legacy IDLs define the account layout inline instead of under `types`.
+            if let Some(ty) = account.get("type") {
+                types.insert(account_name.clone(), json!({ "name": account_name, "type": ty }));
+            }
+            let discriminator = match account.get("discriminator") {
+                Some(d) => parse_discriminator(d)?,
+                None => sighash("account", &account_name),
+            };
+            accounts.push(AccountDef {
+                name: account_name,
+                discriminator,
+            });
+        }
+
+        let mut instructions = Vec::new();
+        for ix in entries(idl, "instructions") {
+            let ix_name = entry_name(ix)?;
+            let discriminator = match ix.get("discriminator") {
+                Some(d) => parse_discriminator(d)?,
+                None => sighash("global", &snake_case(&ix_name)),
+            };
+            let args = entries(ix, "args")
+                .map(|arg| {
+                    let ty = arg
+                        .get("type")
+                        .cloned()
+                        .ok_or_else(|| invalid_idl("instruction arg without type"))?;
+                    Ok((entry_name(arg)?, ty))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let mut ix_accounts = Vec::new();
+            flatten_accounts(ix, "", &mut ix_accounts);
+            instructions.push(InstructionDef {
+                name: ix_name,
+                discriminator,
+                args,
+                accounts: ix_accounts,
+            });
+        }
+
+        Ok(Self {
+            address,
+            name,
+            accounts,
+            instructions,
+            types,
+        })
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let raw = std::fs::read_to_string(path)
+            .map_err(|e| IndexerError::ConfigError(format!("cannot read {}: {e}", path.display())))?;
+        Self::from_json(&serde_json::from_str(&raw)?)
+    }
+
+    fn decode_defined(&self, name: &str, reader: &mut Reader, depth: usize) -> Result<Value> {
+        let def = self
+            .types
+            .get(name)
+            .ok_or_else(|| decode_error(format!("undefined type {name}")))?;
+        if let Some(serialization) = def.get("serialization").and_then(Value::as_str) {
+            if serialization != "borsh" {
+                return Err(decode_error(format!(
+                    "{name} uses unsupported {serialization} serialization"
+                )));
+            }
+        }
+        let ty = def.get("type").unwrap_or(&Value::Null);
+        match ty.get("kind").and_then(Value::as_str) {
+            Some("struct") => self.decode_fields(ty.get("fields"), reader, depth),
+            Some("enum") => {
+                let index = reader.u8()? as usize;
+                let variant = ty
+                    .get("variants")
+                    .and_then(Value::as_array)
+                    .and_then(|v| v.get(index))
+                    .ok_or_else(|| decode_error(format!("{name} has no variant {index}")))?;
+                let variant_name = entry_name(variant)?;
+                match variant.get("fields") {
+                    None => Ok(Value::String(variant_name)),
+                    fields => {
+                        let value = self.decode_fields(fields, reader, depth)?;
+                        Ok(json!({ variant_name: value }))
+                    }
+                }
+            }
+            Some("type") => {
+                let alias = ty
+                    .get("alias")
+                    .ok_or_else(|| decode_error(format!("{name} alias without target")))?;
+                self.decode_type(alias, reader, depth + 1)
+            }
+            other => Err(decode_error(format!("{name} has unsupported kind {other:?}"))),
+        }
+    }
+
+    /// Named fields decode to an object, tuple fields to an array.
+    fn decode_fields(&self, fields: Option<&Value>, reader: &mut Reader, depth: usize) -> Result<Value> {
+        let fields = fields.and_then(Value::as_array).map(Vec::as_slice).unwrap_or_default();
+        let named = fields
+            .iter()
+            .all(|f| f.get("name").is_some() && f.get("type").is_some());
+        if named {
+            let mut out = Map::new();
+            for field in fields {
+                let value = self.decode_type(&field["type"], reader, depth + 1)?;
+                out.insert(entry_name(field)?, value);
+            }
+            Ok(Value::Object(out))
+        } else {
+            fields
+                .iter()
+                .map(|ty| self.decode_type(ty, reader, depth + 1))
+                .collect::<Result<Vec<_>>>()
+                .map(Value::Array)
+        }
+    }
+
+    fn decode_type(&self, ty: &Value, reader: &mut Reader, depth: usize) -> Result<Value> {
+        if depth > MAX_TYPE_DEPTH {
+            return Err(decode_error("type nesting too deep".into()));
+        }
+        if let Some(primitive) = ty.as_str() {
+            return reader.primitive(primitive);
+        }
+        if let Some(inner) = ty.get("option") {
+            return match reader.u8()? {
+                0 => Ok(Value::Null),
+                _ => self.decode_type(inner, reader, depth + 1),
+            };
+        }
+        if let Some(inner) = ty.get("coption") {
+            return match reader.u32()? {
+                0 => Ok(Value::Null),
+                _ => self.decode_type(inner, reader, depth + 1),
+            };
+        }
+        if let Some(inner) = ty.get("vec") {
+            let len = reader.u32()? as usize;
+            // Every element takes at least one byte; reject lengths the data cannot hold.
+            if len > reader.remaining() {
+                return Err(decode_error(format!("vec length {len} exceeds data")));
+            }
+            return (0..len)
+                .map(|_| self.decode_type(inner, reader, depth + 1))
+                .collect::<Result<Vec<_>>>()
+                .map(Value::Array);
+        }
+        if let Some([inner, len]) = ty.get("array").and_then(Value::as_array).map(Vec::as_slice) {
+            let len = len
+                .as_u64()
+                .ok_or_else(|| decode_error("generic array lengths are not supported".into()))?;
+            return (0..len)
+                .map(|_| self.decode_type(inner, reader, depth + 1))
+                .collect::<Result<Vec<_>>>()
+                .map(Value::Array);
+        }
+        if let Some(defined) = ty.get("defined") {
+            // Legacy: `{"defined": "Name"}`; 0.30+: `{"defined": {"name": "Name"}}`.
+            let name = defined
+                .as_str()
+                .or_else(|| defined.get("name").and_then(Value::as_str))
+                .ok_or_else(|| decode_error(format!("invalid defined type {defined}")))?;
+            return self.decode_defined(name, reader, depth + 1);
+        }
+        Err(decode_error(format!("unsupported IDL type {ty}")))
+    }
+}
+
+impl AccountDecoder for AnchorIdl {
+    fn decode(&self, account: &AccountState, _ctx: &DecodeContext) -> Result<DecodedAccount> {
+        let (discriminator, data) = split_discriminator(&account.data)?;
+        let def = self
+            .accounts
+            .iter()
+            .find(|a| a.discriminator == discriminator)
+            .ok_or_else(|| decode_error("unknown account discriminator".into()))?;
+        let info = self.decode_defined(&def.name, &mut Reader::new(data), 0)?;
+        Ok(DecodedAccount {
+            program: self.name.clone(),
+            parsed: json!({ "type": def.name, "info": info }),
+        })
+    }
+}
+
+impl InstructionDecoder for AnchorIdl {
+    fn decode(&self, data: &[u8], accounts: &[String]) -> Result<Value> {
+        let (discriminator, data) = split_discriminator(data)?;
+        let def = self
+            .instructions
+            .iter()
+            .find(|ix| ix.discriminator == discriminator)
+            .ok_or_else(|| decode_error("unknown instruction discriminator".into()))?;
+
+        let mut reader = Reader::new(data);
+        let mut info = Map::new();
+        for (name, ty) in &def.args {
+            info.insert(name.clone(), self.decode_type(ty, &mut reader, 0)?);
+        }
+        // Accounts past the IDL list are `remaining_accounts` and stay unnamed.
+        let named: Map<String, Value> = def
+            .accounts
+            .iter()
+            .zip(accounts)
+            .map(|(name, address)| (name.clone(), Value::String(address.clone())))
+            .collect();
+        Ok(json!({ "type": def.name, "info": info, "accounts": named }))
+    }
+}
+
+/// Parses every `*.json` file in `dir`; files that are not valid IDLs are skipped with a warning.
+pub fn load_dir(dir: &Path) -> Result<Vec<AnchorIdl>> {
+    let entries = std::fs::read_dir(dir).map_err(|e| {
+        IndexerError::ConfigError(format!("cannot read ANCHOR_IDL_DIR {}: {e}", dir.display()))
+    })?;
+    let mut paths: Vec<_> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+
+    let mut idls = Vec::new();
+    for path in paths {
+        match AnchorIdl::from_file(&path) {
+            Ok(idl) => idls.push(idl),
+            Err(e) => tracing::warn!("Skipping Anchor IDL {}: {e}", path.display()),
+        }
+    }
+    Ok(idls)
+}
+
+fn entries<'a>(value: &'a Value, key: &str) -> impl Iterator<Item = &'a Value> {
+    value.get(key).and_then(Value::as_array).into_iter().flatten()
+}
+
+fn entry_name(entry: &Value) -> Result<String> {
+    entry
+        .get("name")
+        .and_then(Value::as_str)
+        .map(String::from)
+        .ok_or_else(|| invalid_idl("entry without name"))
+}
+
+fn flatten_accounts(group: &Value, prefix: &str, out: &mut Vec<String>) {
+    for account in entries(group, "accounts") {
+        let name = account.get("name").and_then(Value::as_str).unwrap_or_default();
+        let path = if prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{prefix}.{name}")
+        };
+        if account.get("accounts").is_some() {
+            flatten_accounts(account, &path, out);
+        } else {
+            out.push(path);
+        }
+    }
+}
+
+fn parse_discriminator(value: &Value) -> Result<Discriminator> {
+    let bytes: Vec<u8> = serde_json::from_value(value.clone())
+        .map_err(|e| invalid_idl(&format!("invalid discriminator: {e}")))?;
+    bytes
+        .try_into()
+        .map_err(|_| invalid_idl("discriminator must be 8 bytes"))
+}
+
+fn sighash(namespace: &str, name: &str) -> Discriminator {
+    let hash = solana_sdk::hash::hashv(&[namespace.as_bytes(), b":", name.as_bytes()]);
+    let mut out = [0u8; DISCRIMINATOR_LEN];
+    out.copy_from_slice(&hash.as_ref()[..DISCRIMINATOR_LEN]);
+    out
+}
+
+/// `camelCase` legacy instruction names → the `snake_case` Rust name Anchor hashes.
+fn snake_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len() + 4);
+    let mut prev_lower = false;
+    for c in name.chars() {
+        if c.is_uppercase() {
+            if prev_lower {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+            prev_lower = false;
+        } else {
+            out.push(c);
+            prev_lower = c.is_lowercase() || c.is_ascii_digit();
+        }
+    }
+    out
+}
+
+fn split_discriminator(data: &[u8]) -> Result<(Discriminator, &[u8])> {
+    if data.len() < DISCRIMINATOR_LEN {
+        return Err(decode_error("data shorter than discriminator".into()));
+    }
+    let (head, rest) = data.split_at(DISCRIMINATOR_LEN);
+    Ok((head.try_into().expect("length checked"), rest))
+}
+
+fn invalid_idl(message: &str) -> IndexerError {
+    IndexerError::ConfigError(format!("invalid Anchor IDL: {message}"))
+}
+
+fn decode_error(message: String) -> IndexerError {
+    IndexerError::DecodeError(message)
+}
+
+/// Little-endian Borsh reader over a byte slice.
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    fn remaining(&self) -> usize {
+        self.data.len()
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8]> {
+        if self.data.len() < n {
+            return Err(decode_error("unexpected end of data".into()));
+        }
+        let (head, rest) = self.data.split_at(n);
+        self.data = rest;
+        Ok(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        Ok(self.take(N)?.try_into().expect("length checked"))
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    /// 128-bit integers are rendered as strings; JSON numbers cannot hold them.
+    fn primitive(&mut self, name: &str) -> Result<Value> {
+        Ok(match name {
+            "bool" => Value::Bool(self.u8()? != 0),
+            "u8" => self.u8()?.into(),
+            "i8" => i8::from_le_bytes(self.array()?).into(),
+            "u16" => u16::from_le_bytes(self.array()?).into(),
+            "i16" => i16::from_le_bytes(self.array()?).into(),
+            "u32" => self.u32()?.into(),
+            "i32" => i32::from_le_bytes(self.array()?).into(),
+            "u64" => u64::from_le_bytes(self.array()?).into(),
+            "i64" => i64::from_le_bytes(self.array()?).into(),
+            "u128" => u128::from_le_bytes(self.array()?).to_string().into(),
+            "i128" => i128::from_le_bytes(self.array()?).to_string().into(),
+            "f32" => f32::from_le_bytes(self.array()?).into(),
+            "f64" => f64::from_le_bytes(self.array()?).into(),
+            "pubkey" | "publicKey" => bs58::encode(self.take(32)?).into_string().into(),
+            "string" => {
+                let len = self.u32()? as usize;
+                String::from_utf8(self.take(len)?.to_vec())
+                    .map_err(|_| decode_error("string is not valid UTF-8".into()))?
+                    .into()
+            }
+            "bytes" => {
+                let len = self.u32()? as usize;
+                self.take(len)?.to_vec().into()
+            }
+            other => return Err(decode_error(format!("unsupported IDL type {other}"))),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::mock_sources::sample_account;
+
+    const PROGRAM: &str = "Counter111111111111111111111111111111111111";
+
+    fn modern_idl() -> AnchorIdl {
+        AnchorIdl::from_json(&json!({
+            "address": PROGRAM,
+            "metadata": { "name": "counter", "version": "0.1.0", "spec": "0.1.0" },
+            "instructions": [{
+                "name": "increment",
+                "discriminator": [11, 18, 104, 9, 104, 174, 59, 33],
+                "accounts": [
+                    { "name": "counter", "writable": true },
+                    { "name": "auth", "accounts": [{ "name": "authority", "signer": true }] }
+                ],
+                "args": [
+                    { "name": "by", "type": "u64" },
+                    { "name": "memo", "type": { "option": "string" } }
+                ]
+            }],
+            "accounts": [{ "name": "Counter", "discriminator": [255, 176, 4, 245, 188, 253, 124, 25] }],
+            "types": [
+                {
+                    "name": "Counter",
+                    "type": { "kind": "struct", "fields": [
+                        { "name": "authority", "type": "pubkey" },
+                        { "name": "count", "type": "u64" },
+                        { "name": "mode", "type": { "defined": { "name": "Mode" } } },
+                        { "name": "history", "type": { "vec": "u8" } }
+                    ] }
+                },
+                {
+                    "name": "Mode",
+                    "type": { "kind": "enum", "variants": [
+                        { "name": "Off" },
+                        { "name": "Step", "fields": [{ "name": "size", "type": "u16" }] }
+                    ] }
+                }
+            ]
+        }))
+        .expect("valid IDL")
+    }
+
+    #[test]
+    fn decodes_account_by_discriminator() {
+        let idl = modern_idl();
+        let authority = [7u8; 32];
+        let mut data = vec![255, 176, 4, 245, 188, 253, 124, 25];
+        data.extend_from_slice(&authority);
+        data.extend_from_slice(&42u64.to_le_bytes());
+        data.extend_from_slice(&[1, 5, 0]);
+        data.extend_from_slice(&2u32.to_le_bytes());
+        data.extend_from_slice(&[3, 4]);
+
+        let mut account = sample_account("counter1", 1);
+        account.owner = PROGRAM.into();
+        account.data = data;
+        let decoded = AccountDecoder::decode(&idl, &account, &DecodeContext::default())
+            .expect("decodes");
+        assert_eq!(decoded.program, "counter");
+        assert_eq!(decoded.parsed["type"], "Counter");
+        let info = &decoded.parsed["info"];
+        assert_eq!(info["authority"], bs58::encode(authority).into_string());
+        assert_eq!(info["count"], 42);
+        assert_eq!(info["mode"], json!({ "Step": { "size": 5 } }));
+        assert_eq!(info["history"], json!([3, 4]));
+
+        account.data[0] = 0;
+        assert!(AccountDecoder::decode(&idl, &account, &DecodeContext::default()).is_err());
+    }
+
+    #[test]
+    fn decodes_instruction_args_and_named_accounts() {
+        let idl = modern_idl();
+        let mut data = vec![11, 18, 104, 9, 104, 174, 59, 33];
+        data.extend_from_slice(&3u64.to_le_bytes());
+        data.push(1);
+        data.extend_from_slice(&2u32.to_le_bytes());
+        data.extend_from_slice(b"hi");
+
+        let accounts = ["counter1".to_string(), "signer1".into(), "extra".into()];
+        let decoded = InstructionDecoder::decode(&idl, &data, &accounts).expect("decodes");
+        assert_eq!(decoded["type"], "increment");
+        assert_eq!(decoded["info"], json!({ "by": 3, "memo": "hi" }));
+        assert_eq!(
+            decoded["accounts"],
+            json!({ "counter": "counter1", "auth.authority": "signer1" })
+        );
+    }
+
+    #[test]
+    fn legacy_idl_derives_discriminators() {
+        let idl = AnchorIdl::from_json(&json!({
+            "version": "0.1.0",
+            "name": "legacy",
+            "metadata": { "address": PROGRAM },
+            "instructions": [{
+                "name": "setValue",
+                "accounts": [{ "name": "state", "isMut": true, "isSigner": false }],
+                "args": [{ "name": "value", "type": "i32" }]
+            }],
+            "accounts": [{
+                "name": "State",
+                "type": { "kind": "struct", "fields": [{ "name": "owner", "type": "publicKey" }] }
+            }]
+        }))
+        .expect("valid legacy IDL");
+        assert_eq!(idl.address, PROGRAM);
+
+        let mut data = sighash("global", "set_value").to_vec();
+        data.extend_from_slice(&(-5i32).to_le_bytes());
+        let decoded = InstructionDecoder::decode(&idl, &data, &["state1".into()]).expect("decodes");
+        assert_eq!(decoded["info"]["value"], -5);
+        assert_eq!(decoded["accounts"]["state"], "state1");
+
+        let mut account = sample_account("state1", 1);
+        account.data = sighash("account", "State").to_vec();
+        account.data.extend_from_slice(&[1u8; 32]);
+        let decoded = AccountDecoder::decode(&idl, &account, &DecodeContext::default())
+            .expect("decodes");
+        assert_eq!(decoded.parsed["type"], "State");
+        assert_eq!(decoded.parsed["info"]["owner"], bs58::encode([1u8; 32]).into_string());
+    }
+}
@@ -0,0 +1,94 @@
+use crate::core::decoders::{AccountDecoder, DecodeContext};
+use crate::core::types::{AccountState, DecodedAccount};
+use crate::utils::errors::{IndexerError, Result};
+use solana_account_decoder::parse_account_data::{
+    parse_account_data_v3, AccountAdditionalDataV3, SplTokenAdditionalDataV2,
+};
+use solana_sdk::pubkey::Pubkey;
+
+pub const SYSTEM_PROGRAM: &str = "11111111111111111111111111111111";
+pub const STAKE_PROGRAM: &str = "Stake11111111111111111111111111111111111111";
+pub const SPL_TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+pub const SPL_TOKEN_2022_PROGRAM: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+
+pub const BUILTIN_PROGRAMS: [&str; 4] = [
+    SYSTEM_PROGRAM,
+    STAKE_PROGRAM,
+    SPL_TOKEN_PROGRAM,
+    SPL_TOKEN_2022_PROGRAM,
+];
+
+/// Base SPL mint / token account sizes; Token-2022 appends an account-type byte and extensions.
+pub const MINT_LEN: usize = 82;
+pub const TOKEN_ACCOUNT_LEN: usize = 165;
+const ACCOUNT_TYPE_MINT: u8 = 1;
+const ACCOUNT_TYPE_ACCOUNT: u8 = 2;
+const MINT_DECIMALS_OFFSET: usize = 44;
+
+/// Nonce, stake, SPL Token and Token-2022 (with extensions) layouts, via `solana-account-decoder`.
+pub struct BuiltinDecoder;
+
+impl AccountDecoder for BuiltinDecoder {
+    fn decode(&self, account: &AccountState, ctx: &DecodeContext) -> Result<DecodedAccount> {
+        let pubkey: Pubkey = account
+            .address
+            .parse()
+            .map_err(|e| IndexerError::DecodeError(format!("invalid address: {e}")))?;
+        let owner: Pubkey = account
+            .owner
+            .parse()
+            .map_err(|e| IndexerError::DecodeError(format!("invalid owner: {e}")))?;
+
+        // Token accounts need the mint's decimals; without them, decode with 0 and drop UI amounts.
+        let decimals_known = ctx.mint_decimals.is_some();
+        let additional = AccountAdditionalDataV3 {
+            spl_token_additional_data: Some(SplTokenAdditionalDataV2 {
+                decimals: ctx.mint_decimals.unwrap_or(0),
+                ..Default::default()
+            }),
+        };
+        let parsed = parse_account_data_v3(&pubkey, &owner, &account.data, Some(additional))
+            .map_err(|e| IndexerError::DecodeError(e.to_string()))?;
+
+        let mut value = parsed.parsed;
+        if !decimals_known && token_account_mint(account).is_some() {
+            if let Some(amount) = value
+                .pointer_mut("/info/tokenAmount")
+                .and_then(|a| a.as_object_mut())
+            {
+                amount.retain(|k, _| k == "amount");
+            }
+        }
+
+        Ok(DecodedAccount {
+            program: parsed.program,
+            parsed: value,
+        })
+    }
+}
+
+fn is_token_program(owner: &str) -> bool {
+    owner == SPL_TOKEN_PROGRAM || owner == SPL_TOKEN_2022_PROGRAM
+}
+
+/// Mint address of an SPL token account (`None` for mints and other accounts).
+pub fn token_account_mint(account: &AccountState) -> Option<String> {
+    if !is_token_program(&account.owner) {
+        return None;
+    }
+    let data = &account.data;
+    let is_account = data.len() == TOKEN_ACCOUNT_LEN
+        || (data.len() > TOKEN_ACCOUNT_LEN && data[TOKEN_ACCOUNT_LEN] == ACCOUNT_TYPE_ACCOUNT);
+    is_account.then(|| bs58::encode(&data[..32]).into_string())
+}
+
+/// Decimals of an SPL mint account.
+pub fn mint_decimals(account: &AccountState) -> Option<u8> {
+    if !is_token_program(&account.owner) {
+        return None;
+    }
+    let data = &account.data;
+    let is_mint = data.len() == MINT_LEN
+        || (data.len() > TOKEN_ACCOUNT_LEN && data[TOKEN_ACCOUNT_LEN] == ACCOUNT_TYPE_MINT);
+    is_mint.then(|| data[MINT_DECIMALS_OFFSET])
+}
@@ -0,0 +1,314 @@
+//! Account and instruction data decoders keyed by program.
+//!
+//! The registry ships with account decoders for the System (nonce), Stake, SPL Token and
+//! Token-2022 programs; register your own with [`DecoderRegistry::register`] and
+//! [`DecoderRegistry::register_instructions`], or load Anchor IDLs with
+//! [`DecoderRegistry::register_anchor`].
+
+pub mod anchor;
+pub mod builtin;
+
+use crate::core::types::{AccountState, DecodedAccount, Instruction};
+use crate::utils::errors::Result;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Extra data some layouts need that is not stored in the account itself.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DecodeContext {
+    /// Decimals of the mint, for SPL token accounts (UI amounts are omitted without it).
+    pub mint_decimals: Option<u8>,
+}
+
+/// Turns the raw data of accounts owned by one program into structured JSON.
+pub trait AccountDecoder: Send + Sync {
+    fn decode(&self, account: &AccountState, ctx: &DecodeContext) -> Result<DecodedAccount>;
+}
+
+/// Turns the raw data of instructions addressed to one program into structured JSON.
+pub trait InstructionDecoder: Send + Sync {
+    /// `accounts` are the instruction's account addresses, in instruction order.
+    fn decode(&self, data: &[u8], accounts: &[String]) -> Result<serde_json::Value>;
+}
+
+/// Program id → account decoder (by owner) and instruction decoder (by invoked program).
+#[derive(Default)]
+pub struct DecoderRegistry {
+    decoders: RwLock<HashMap<String, Arc<dyn AccountDecoder>>>,
+    instruction_decoders: RwLock<HashMap<String, Arc<dyn InstructionDecoder>>>,
+}
+
+impl DecoderRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registry with the built-in System, Stake, SPL Token and Token-2022 decoders.
+    pub fn with_builtin() -> Self {
+        let registry = Self::new();
+        let builtin = Arc::new(builtin::BuiltinDecoder);
+        for program in builtin::BUILTIN_PROGRAMS {
+            registry.register(program, builtin.clone());
+        }
+        registry
+    }
+
+    /// Registers (or replaces) the decoder for accounts owned by `program`.
+    pub fn register(&self, program: impl Into<String>, decoder: Arc<dyn AccountDecoder>) {
+        self.decoders
+            .write()
+            .expect("decoder registry lock")
+            .insert(program.into(), decoder);
+    }
+
+    /// Registers (or replaces) the decoder for instructions invoking `program`.
+    pub fn register_instructions(
+        &self,
+        program: impl Into<String>,
+        decoder: Arc<dyn InstructionDecoder>,
+    ) {
+        self.instruction_decoders
+            .write()
+            .expect("decoder registry lock")
+            .insert(program.into(), decoder);
+    }
+
+    /// Decodes the accounts and instructions of the program an Anchor IDL describes.
+    pub fn register_anchor(&self, idl: anchor::AnchorIdl) {
+        let idl = Arc::new(idl);
+        self.register(idl.address.clone(), idl.clone());
+        self.register_instructions(idl.address.clone(), idl);
+    }
+
+    /// Decodes `account` with its owner's decoder. Accounts without data, without a
+    /// registered decoder, or whose data does not parse yield `None`.
+    pub fn decode(&self, account: &AccountState, ctx: &DecodeContext) -> Option<DecodedAccount> {
+        if account.data.is_empty() {
+            return None;
+        }
+        let decoder = self
+            .decoders
+            .read()
+            .expect("decoder registry lock")
+            .get(&account.owner)
+            .cloned()?;
+        match decoder.decode(account, ctx) {
+            Ok(decoded) => Some(decoded),
+            Err(e) => {
+                tracing::debug!(address = %account.address, owner = %account.owner, "decode failed: {e}");
+                None
+            }
+        }
+    }
+
+    /// Decodes the raw (base58) data of `ix`, resolving its account indices against the
+    /// transaction's `tx_accounts`. Same `None` cases as [`Self::decode`].
+    pub fn decode_instruction(
+        &self,
+        ix: &Instruction,
+        tx_accounts: &[String],
+    ) -> Option<serde_json::Value> {
+        let data = bs58::decode(ix.data.as_deref()?).into_vec().ok()?;
+        if data.is_empty() {
+            return None;
+        }
+        let decoder = self
+            .instruction_decoders
+            .read()
+            .expect("decoder registry lock")
+            .get(&ix.program_id)
+            .cloned()?;
+        let accounts: Vec<String> = ix
+            .accounts
+            .iter()
+            .filter_map(|&i| tx_accounts.get(i as usize).cloned())
+            .collect();
+        match decoder.decode(&data, &accounts) {
+            Ok(decoded) => Some(decoded),
+            Err(e) => {
+                tracing::debug!(signature = %ix.signature, program = %ix.program_id, "instruction decode failed: {e}");
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::mock_sources::sample_account;
+    use crate::utils::errors::IndexerError;
+    use solana_sdk::pubkey::Pubkey;
+
+    struct Counter;
+
+    impl AccountDecoder for Counter {
+        fn decode(&self, account: &AccountState, _ctx: &DecodeContext) -> Result<DecodedAccount> {
+            let count = account
+                .data
+                .first()
+                .ok_or_else(|| IndexerError::DecodeError("empty".into()))?;
+            Ok(DecodedAccount {
+                program: "counter".into(),
+                parsed: serde_json::json!({ "count": count }),
+            })
+        }
+    }
+
+    #[test]
+    fn custom_decoder_is_used_for_its_owner() {
+        let registry = DecoderRegistry::new();
+        registry.register("CounterProgram", Arc::new(Counter));
+
+        let mut account = sample_account("counter1", 1);
+        account.owner = "CounterProgram".into();
+        account.data = vec![7];
+        let decoded = registry
+            .decode(&account, &DecodeContext::default())
+            .expect("decoded");
+        assert_eq!(decoded.parsed["count"], 7);
+
+        account.owner = "Other".into();
+        assert!(registry.decode(&account, &DecodeContext::default()).is_none());
+    }
+
+    #[test]
+    fn builtin_decodes_spl_token_mint() {
+        let mut data = vec![0u8; builtin::MINT_LEN];
+        data[36..44].copy_from_slice(&1_000u64.to_le_bytes());
+        data[44] = 6;
+        data[45] = 1;
+        let mut mint = sample_account("So11111111111111111111111111111111111111112", 1);
+        mint.owner = builtin::SPL_TOKEN_PROGRAM.into();
+        mint.data = data;
+
+        assert_eq!(builtin::mint_decimals(&mint), Some(6));
+        let decoded = DecoderRegistry::with_builtin()
+            .decode(&mint, &DecodeContext::default())
+            .expect("mint decodes");
+        assert_eq!(decoded.program, "spl-token");
+        assert_eq!(decoded.parsed["type"], "mint");
+        assert_eq!(decoded.parsed["info"]["decimals"], 6);
+    }
+
+    fn key(byte: u8) -> Pubkey {
+        Pubkey::new_from_array([byte; 32])
+    }
+
+    /// Base layout padded to 165 bytes, then the account-type byte and one TLV extension.
+    fn token_2022_layout(base: &[u8], account_type: u8, extension: u16, value: &[u8]) -> Vec<u8> {
+        let mut data = base.to_vec();
+        data.resize(builtin::TOKEN_ACCOUNT_LEN, 0);
+        data.push(account_type);
+        data.extend_from_slice(&extension.to_le_bytes());
+        data.extend_from_slice(&(value.len() as u16).to_le_bytes());
+        data.extend_from_slice(value);
+        data
+    }
+
+    #[test]
+    fn builtin_decodes_token_2022_mint_and_account_with_extensions() {
+        let mut base = vec![0u8; builtin::MINT_LEN];
+        base[36..44].copy_from_slice(&1_000u64.to_le_bytes());
+        base[44] = 9;
+        base[45] = 1;
+        // MintCloseAuthority (extension type 3).
+        let mut mint = sample_account(&key(1).to_string(), 1);
+        mint.owner = builtin::SPL_TOKEN_2022_PROGRAM.into();
+        mint.data = token_2022_layout(&base, 1, 3, key(2).as_ref());
+        assert!(mint.data.len() > builtin::TOKEN_ACCOUNT_LEN);
+
+        assert_eq!(builtin::mint_decimals(&mint), Some(9));
+        assert_eq!(builtin::token_account_mint(&mint), None);
+        let decoded = DecoderRegistry::with_builtin()
+            .decode(&mint, &DecodeContext::default())
+            .expect("mint decodes");
+        assert_eq!(decoded.program, "spl-token-2022");
+        assert_eq!(decoded.parsed["type"], "mint");
+        assert_eq!(decoded.parsed["info"]["decimals"], 9);
+        let extension = &decoded.parsed["info"]["extensions"][0];
+        assert_eq!(extension["extension"], "mintCloseAuthority");
+        assert_eq!(extension["state"]["closeAuthority"], key(2).to_string());
+
+        let mut base = vec![0u8; builtin::TOKEN_ACCOUNT_LEN];
+        base[..32].copy_from_slice(key(1).as_ref());
+        base[32..64].copy_from_slice(key(3).as_ref());
+        base[64..72].copy_from_slice(&2_500u64.to_le_bytes());
+        base[108] = 1;
+        // ImmutableOwner (extension type 7) has no data.
+        let mut account = sample_account(&key(4).to_string(), 1);
+        account.owner = builtin::SPL_TOKEN_2022_PROGRAM.into();
+        account.data = token_2022_layout(&base, 2, 7, &[]);
+
+        assert_eq!(builtin::token_account_mint(&account), Some(key(1).to_string()));
+        assert_eq!(builtin::mint_decimals(&account), None);
+        let ctx = DecodeContext {
+            mint_decimals: Some(9),
+        };
+        let decoded = DecoderRegistry::with_builtin()
+            .decode(&account, &ctx)
+            .expect("token account decodes");
+        assert_eq!(decoded.parsed["type"], "account");
+        assert_eq!(decoded.parsed["info"]["tokenAmount"]["amount"], "2500");
+        assert_eq!(decoded.parsed["info"]["tokenAmount"]["decimals"], 9);
+        assert_eq!(
+            decoded.parsed["info"]["extensions"][0]["extension"],
+            "immutableOwner"
+        );
+    }
+
+    #[test]
+    fn builtin_decodes_initialized_stake_account() {
+        // StakeStateV2::Initialized(Meta), zero-padded to the 200-byte stake account size.
+        let mut data = 1u32.to_le_bytes().to_vec();
+        data.extend_from_slice(&2_282_880u64.to_le_bytes());
+        data.extend_from_slice(key(5).as_ref());
+        data.extend_from_slice(key(6).as_ref());
+        data.extend_from_slice(&0i64.to_le_bytes());
+        data.extend_from_slice(&0u64.to_le_bytes());
+        data.extend_from_slice(key(7).as_ref());
+        data.resize(200, 0);
+        let mut stake = sample_account(&key(8).to_string(), 1_000_000_000);
+        stake.owner = builtin::STAKE_PROGRAM.into();
+        stake.data = data;
+
+        let decoded = DecoderRegistry::with_builtin()
+            .decode(&stake, &DecodeContext::default())
+            .expect("stake account decodes");
+        assert_eq!(decoded.program, "stake");
+        assert_eq!(decoded.parsed["type"], "initialized");
+        let meta = &decoded.parsed["info"]["meta"];
+        assert_eq!(meta["rentExemptReserve"], "2282880");
+        assert_eq!(meta["authorized"]["staker"], key(5).to_string());
+        assert_eq!(meta["authorized"]["withdrawer"], key(6).to_string());
+        assert_eq!(meta["lockup"]["custodian"], key(7).to_string());
+    }
+
+    #[test]
+    fn builtin_decodes_nonce_account() {
+        // nonce::Versions::Current(State::Initialized(Data)): authority, durable nonce, fee.
+        let mut data = 1u32.to_le_bytes().to_vec();
+        data.extend_from_slice(&1u32.to_le_bytes());
+        data.extend_from_slice(key(9).as_ref());
+        data.extend_from_slice(&[10; 32]);
+        data.extend_from_slice(&5_000u64.to_le_bytes());
+        let mut nonce = sample_account(&key(11).to_string(), 1_447_680);
+        nonce.owner = builtin::SYSTEM_PROGRAM.into();
+        nonce.data = data;
+
+        let decoded = DecoderRegistry::with_builtin()
+            .decode(&nonce, &DecodeContext::default())
+            .expect("nonce account decodes");
+        assert_eq!(decoded.program, "nonce");
+        assert_eq!(decoded.parsed["type"], "initialized");
+        assert_eq!(decoded.parsed["info"]["authority"], key(9).to_string());
+        assert_eq!(
+            decoded.parsed["info"]["blockhash"],
+            bs58::encode([10; 32]).into_string()
+        );
+        assert_eq!(
+            decoded.parsed["info"]["feeCalculator"]["lamportsPerSignature"],
+            "5000"
+        );
+    }
+}
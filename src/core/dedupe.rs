@@ -0,0 +1,112 @@
+//! Drops stream updates that were already delivered, e.g. those replayed by a Yellowstone
+//! reconnect with `from_slot` or delivered again by a second merged stream.
+
+use crate::core::types::{Slot, SlotStatus};
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::time::Instant;
+
+/// Slot status updates remembered (several per slot).
+const SLOT_UPDATE_CAPACITY: usize = 20_000;
+
+/// Transaction signatures remembered.
+const SIGNATURE_CAPACITY: usize = 200_000;
+
+/// Keys seen recently, with when each was first seen; forgets the oldest beyond `capacity`.
+struct RecentSet<K> {
+    order: VecDeque<K>,
+    first_seen: HashMap<K, Instant>,
+    capacity: usize,
+}
+
+impl<K: Hash + Eq + Clone> RecentSet<K> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            order: VecDeque::new(),
+            first_seen: HashMap::new(),
+            capacity,
+        }
+    }
+
+    /// `None` when `key` was not seen recently (it is remembered now); otherwise when it first was.
+    fn insert(&mut self, key: K) -> Option<Instant> {
+        if let Some(&seen) = self.first_seen.get(&key) {
+            return Some(seen);
+        }
+        self.first_seen.insert(key.clone(), Instant::now());
+        self.order.push_back(key);
+        if self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.first_seen.remove(&oldest);
+            }
+        }
+        None
+    }
+}
+
+/// Remembers recent slot status transitions and transaction signatures.
+pub struct UpdateDeduper {
+    slots: RecentSet<(u64, SlotStatus)>,
+    signatures: RecentSet<String>,
+}
+
+impl Default for UpdateDeduper {
+    fn default() -> Self {
+        Self {
+            slots: RecentSet::new(SLOT_UPDATE_CAPACITY),
+            signatures: RecentSet::new(SIGNATURE_CAPACITY),
+        }
+    }
+}
+
+impl UpdateDeduper {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `true` the first time this slot reaches this status.
+    pub fn first_slot_update(&mut self, slot: &Slot) -> bool {
+        self.slot_update_seen(slot).is_none()
+    }
+
+    /// `true` the first time this signature is seen.
+    pub fn first_transaction(&mut self, signature: &str) -> bool {
+        self.transaction_seen(signature).is_none()
+    }
+
+    /// When this slot status was first seen; `None` (and remembered) if this is the first time.
+    pub fn slot_update_seen(&mut self, slot: &Slot) -> Option<Instant> {
+        self.slots.insert((slot.slot, slot.status.clone()))
+    }
+
+    /// When this signature was first seen; `None` (and remembered) if this is the first time.
+    pub fn transaction_seen(&mut self, signature: &str) -> Option<Instant> {
+        self.signatures.insert(signature.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::fixtures::sample_slot;
+
+    #[test]
+    fn drops_repeats_and_forgets_oldest_beyond_capacity() {
+        let mut deduper = UpdateDeduper::new();
+        let mut slot = sample_slot(5);
+        assert!(deduper.first_slot_update(&slot));
+        assert!(!deduper.first_slot_update(&slot));
+        slot.status = SlotStatus::Finalized;
+        assert!(deduper.first_slot_update(&slot));
+
+        assert!(deduper.first_transaction("sig"));
+        assert!(!deduper.first_transaction("sig"));
+
+        let mut recent = RecentSet::new(2);
+        assert!(recent.insert(1).is_none());
+        assert!(recent.insert(2).is_none());
+        assert!(recent.insert(3).is_none());
+        assert!(recent.insert(1).is_none(), "1 was evicted by 3");
+        assert!(recent.insert(3).is_some());
+    }
+}
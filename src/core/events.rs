@@ -0,0 +1,37 @@
+//! Events published by [`MultiCache`](crate::storage::cache::multi_cache::MultiCache) after
+//! each successful write. Subscribe with `MultiCache::subscribe`.
+
+use crate::core::types::{AccountState, Slot, Transaction};
+use serde::Serialize;
+
+/// Buffered events per subscriber before slow receivers start lagging (and drop the oldest).
+pub const EVENT_CHANNEL_CAPACITY: usize = 4096;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum IndexerEvent {
+    /// A slot was stored (once per commitment level reached, and on metadata backfill).
+    Slot(Slot),
+    /// A transaction was stored.
+    Transaction(Transaction),
+    /// An account was stored with different contents than its previous version.
+    Account(AccountChange),
+}
+
+impl IndexerEvent {
+    /// Event type name, as serialized in `type`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            IndexerEvent::Slot(_) => "slot",
+            IndexerEvent::Transaction(_) => "transaction",
+            IndexerEvent::Account(_) => "account",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AccountChange {
+    /// `None` the first time the indexer sees the account.
+    pub previous: Option<AccountState>,
+    pub current: AccountState,
+}
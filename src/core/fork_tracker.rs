@@ -0,0 +1,148 @@
+use crate::core::types::{Slot, SlotStatus};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Mutex;
+
+/// Upper bound on unfinalized slots kept in memory (RPC polling never finalizes).
+const MAX_PENDING_SLOTS: usize = 4096;
+
+#[derive(Default)]
+struct ForkState {
+    /// Unfinalized slot → parent (when known).
+    pending: BTreeMap<u64, Option<u64>>,
+    last_finalized: Option<u64>,
+}
+
+/// Follows the parent chain of unfinalized slots and reports the ones orphaned
+/// once a competing fork is finalized.
+#[derive(Default)]
+pub struct ForkTracker {
+    state: Mutex<ForkState>,
+}
+
+impl ForkTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a slot update. Returns the slots orphaned by it, which is only
+    /// non-empty when `slot` is finalized on a different fork than earlier updates.
+    pub fn observe(&self, slot: &Slot) -> Vec<u64> {
+        let mut state = self.state.lock().expect("fork tracker lock");
+
+        if state.last_finalized.is_some_and(|f| slot.slot <= f) {
+            return Vec::new();
+        }
+
+        match slot.status {
+            SlotStatus::Finalized => Self::finalize(&mut state, slot),
+            SlotStatus::Dead => {
+                state.pending.remove(&slot.slot);
+                Vec::new()
+            }
+            _ => {
+                let entry = state.pending.entry(slot.slot).or_insert(None);
+                if slot.parent.is_some() {
+                    *entry = slot.parent;
+                }
+                while state.pending.len() > MAX_PENDING_SLOTS {
+                    state.pending.pop_first();
+                }
+                Vec::new()
+            }
+        }
+    }
+
+    fn finalize(state: &mut ForkState, slot: &Slot) -> Vec<u64> {
+        let floor = state.last_finalized.unwrap_or(0);
+
+        // Ancestors of the finalized slot that we still hold as pending.
+        let mut chain = HashSet::from([slot.slot]);
+        let mut cursor = slot
+            .parent
+            .or_else(|| state.pending.get(&slot.slot).copied().flatten());
+        if cursor.is_none() {
+            // Without a parent link we cannot tell forks apart; just advance the root.
+            state.pending.retain(|n, _| *n > slot.slot);
+            state.last_finalized = Some(slot.slot);
+            return Vec::new();
+        }
+        // Lowest height below which the canonical chain is unknown.
+        let mut known_from = slot.slot;
+        while let Some(parent) = cursor {
+            if parent <= floor {
+                known_from = floor;
+                break;
+            }
+            if !chain.insert(parent) {
+                break;
+            }
+            known_from = parent;
+            cursor = state.pending.get(&parent).copied().flatten();
+        }
+
+        let mut orphaned: Vec<u64> = state
+            .pending
+            .range(known_from + 1..slot.slot)
+            .map(|(&n, _)| n)
+            .filter(|n| !chain.contains(n))
+            .collect();
+
+        // Descendants of orphaned slots (ascending, so parents are classified first).
+        let mut dead: HashSet<u64> = orphaned.iter().copied().collect();
+        for (&n, parent) in state.pending.range(slot.slot + 1..) {
+            if parent.is_some_and(|p| dead.contains(&p)) {
+                dead.insert(n);
+                orphaned.push(n);
+            }
+        }
+
+        state.pending.retain(|n, _| *n > slot.slot && !dead.contains(n));
+        state.last_finalized = Some(slot.slot);
+        orphaned
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::fixtures::sample_slot;
+
+    fn slot(n: u64, parent: u64, status: SlotStatus) -> Slot {
+        Slot {
+            parent: Some(parent),
+            status,
+            ..sample_slot(n)
+        }
+    }
+
+    #[test]
+    fn finalizing_competing_fork_orphans_sibling_and_descendants() {
+        let forks = ForkTracker::new();
+        assert!(forks.observe(&slot(10, 9, SlotStatus::Processed)).is_empty());
+        assert!(forks.observe(&slot(11, 9, SlotStatus::Processed)).is_empty());
+        assert!(forks.observe(&slot(12, 10, SlotStatus::Confirmed)).is_empty());
+        assert!(forks.observe(&slot(13, 11, SlotStatus::Processed)).is_empty());
+
+        let orphaned = forks.observe(&slot(11, 9, SlotStatus::Finalized));
+        assert_eq!(orphaned, vec![10, 12]);
+    }
+
+    #[test]
+    fn finalizing_same_fork_orphans_nothing() {
+        let forks = ForkTracker::new();
+        forks.observe(&slot(20, 19, SlotStatus::Processed));
+        forks.observe(&slot(21, 20, SlotStatus::Confirmed));
+
+        assert!(forks.observe(&slot(20, 19, SlotStatus::Finalized)).is_empty());
+        assert!(forks.observe(&slot(21, 20, SlotStatus::Finalized)).is_empty());
+    }
+
+    #[test]
+    fn unknown_ancestry_is_not_orphaned() {
+        let forks = ForkTracker::new();
+        forks.observe(&slot(30, 29, SlotStatus::Processed));
+
+        // Parent 31 was never observed, so slot 30 may still be an ancestor.
+        assert!(forks.observe(&slot(32, 31, SlotStatus::Finalized)).is_empty());
+    }
+}
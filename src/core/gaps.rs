@@ -0,0 +1,300 @@
+//! Gap detection and repair: finds slot numbers missing from `slots`, classifies each as
+//! skipped by its leader (no block produced) or missed by the indexer (`getBlocks` lists it),
+//! and fetches missed blocks in the background through the backfill worker pool.
+
+use crate::core::backfill::{BackfillOptions, HistoricalBackfill};
+use crate::data_sources::SlotSource;
+use crate::storage::cache::multi_cache::MultiCache;
+use crate::utils::config::GapConfig;
+use crate::utils::errors::{IndexerError, Result};
+use serde::Serialize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+/// Slots just below the checkpoint left out of default scans: `getBlocks` only lists finalized
+/// blocks, so newer produced slots would be misread as skipped.
+pub const FINALITY_MARGIN: u64 = 64;
+
+/// Widest range one scan classifies (`getBlocks` itself allows 500,000).
+pub const MAX_SCAN_RANGE: u64 = 100_000;
+
+/// Slots covered by a report when `from` is not given (`gaps`, `GET /admin/gaps`).
+pub const DEFAULT_REPORT_WINDOW: u64 = 5000;
+
+/// Missed ranges listed in a report; the counts stay exact beyond it.
+const MAX_REPORTED_RANGES: usize = 1000;
+
+/// Inclusive run of slot numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct SlotGap {
+    pub start: u64,
+    pub end: u64,
+}
+
+/// Coverage of `from..=to`: every slot number is stored, skipped by its leader, or missed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GapReport {
+    pub from: u64,
+    pub to: u64,
+    pub stored: u64,
+    /// Lowest stored slot in the range; slots below it predate indexing rather than being gaps.
+    pub first_stored: Option<u64>,
+    /// Missing slots that produced no block.
+    pub skipped: u64,
+    /// Missing slots that produced a block the indexer never stored.
+    pub missed: u64,
+    /// Share of produced blocks that are stored (1.0 when none were produced).
+    pub coverage: f64,
+    /// Runs of missed slots, lowest first, capped at 1000 entries.
+    pub missed_ranges: Vec<SlotGap>,
+    #[serde(skip)]
+    pub missed_slots: Vec<u64>,
+}
+
+pub struct GapDetector {
+    source: Arc<dyn SlotSource>,
+    cache: Arc<MultiCache>,
+}
+
+impl GapDetector {
+    pub fn new(source: Arc<dyn SlotSource>, cache: Arc<MultiCache>) -> Self {
+        Self { source, cache }
+    }
+
+    /// Fills in an open-ended range: `to` defaults to `FINALITY_MARGIN` below the checkpoint and
+    /// `from` to `window` slots before `to`. `None` when `to` is unset and nothing is stored yet.
+    pub async fn range(
+        &self,
+        from: Option<u64>,
+        to: Option<u64>,
+        window: u64,
+    ) -> Result<Option<(u64, u64)>> {
+        let to = match to {
+            Some(to) => to,
+            None => match self.cache.get_checkpoint().await? {
+                Some(checkpoint) => checkpoint.saturating_sub(FINALITY_MARGIN),
+                None => return Ok(None),
+            },
+        };
+        let from = from.unwrap_or_else(|| to.saturating_sub(window.max(1) - 1));
+        Ok(Some((from, to)))
+    }
+
+    pub async fn scan(&self, from: u64, to: u64) -> Result<GapReport> {
+        if from > to {
+            return Err(IndexerError::ConfigError(format!(
+                "gap scan range is empty: from {from} is after to {to}"
+            )));
+        }
+        if to - from >= MAX_SCAN_RANGE {
+            return Err(IndexerError::ConfigError(format!(
+                "gap scan range spans more than {MAX_SCAN_RANGE} slots"
+            )));
+        }
+
+        let gaps = self.cache.get_slot_gaps(from, to).await?;
+        let produced = self.source.get_blocks(from, to).await?;
+        let missing: u64 = gaps.iter().map(|(start, end)| end - start + 1).sum();
+
+        // Both lists are ascending, so one pass pairs each produced slot with its gap.
+        let mut missed_slots = Vec::new();
+        let mut gap_iter = gaps.iter().peekable();
+        for &slot in &produced {
+            while gap_iter.next_if(|(_, end)| *end < slot).is_some() {}
+            if gap_iter.peek().is_some_and(|(start, _)| *start <= slot) {
+                missed_slots.push(slot);
+            }
+        }
+
+        let mut missed_ranges: Vec<SlotGap> = Vec::new();
+        for &slot in &missed_slots {
+            if let Some(range) = missed_ranges.last_mut().filter(|r| r.end + 1 == slot) {
+                range.end = slot;
+            } else if missed_ranges.len() == MAX_REPORTED_RANGES {
+                break;
+            } else {
+                missed_ranges.push(SlotGap {
+                    start: slot,
+                    end: slot,
+                });
+            }
+        }
+
+        let first_stored = match gaps.first() {
+            Some(&(start, end)) if start == from => Some(end + 1).filter(|&s| s <= to),
+            _ => Some(from),
+        };
+        let missed = missed_slots.len() as u64;
+        Ok(GapReport {
+            from,
+            to,
+            stored: to - from + 1 - missing,
+            first_stored,
+            skipped: missing - missed,
+            missed,
+            coverage: if produced.is_empty() {
+                1.0
+            } else {
+                (produced.len() as u64 - missed) as f64 / produced.len() as f64
+            },
+            missed_ranges,
+            missed_slots,
+        })
+    }
+}
+
+/// Periodically scans the finalized window below the checkpoint and fetches missed blocks.
+pub struct GapRepairer {
+    detector: GapDetector,
+    backfill: HistoricalBackfill,
+    cache: Arc<MultiCache>,
+    config: GapConfig,
+}
+
+impl GapRepairer {
+    pub fn new(source: Arc<dyn SlotSource>, cache: Arc<MultiCache>, config: GapConfig) -> Self {
+        let options = BackfillOptions {
+            workers: 2,
+            requests_per_second: config.repair_rps,
+            ..BackfillOptions::default()
+        };
+        Self {
+            detector: GapDetector::new(source.clone(), cache.clone()),
+            backfill: HistoricalBackfill::new(source, cache.clone(), options),
+            cache,
+            config,
+        }
+    }
+
+    pub async fn run_until(&self, mut shutdown: broadcast::Receiver<()>) {
+        let mut interval =
+            tokio::time::interval(Duration::from_secs(self.config.scan_interval_secs.max(1)));
+        loop {
+            tokio::select! {
+                Ok(()) = shutdown.recv() => break,
+                _ = interval.tick() => {}
+            }
+            tokio::select! {
+                Ok(()) = shutdown.recv() => break,
+                result = self.repair_once() => {
+                    if let Err(e) = result {
+                        tracing::warn!("Gap repair failed: {e}");
+                    }
+                }
+            }
+        }
+    }
+
+    /// One scan of the default window; returns the report taken before repairing. Slots below
+    /// the first stored one are left to `backfill`.
+    pub async fn repair_once(&self) -> Result<Option<GapReport>> {
+        let Some((from, to)) = self.detector.range(None, None, self.config.window).await? else {
+            return Ok(None);
+        };
+        let report = self.detector.scan(from, to).await?;
+        let Some(first_stored) = report.first_stored else {
+            return Ok(Some(report));
+        };
+        let repairs: Vec<u64> = report
+            .missed_slots
+            .iter()
+            .copied()
+            .filter(|&slot| slot > first_stored)
+            .collect();
+        if repairs.is_empty() {
+            return Ok(Some(report));
+        }
+
+        tracing::info!(
+            from,
+            to,
+            missed = repairs.len(),
+            skipped = report.skipped,
+            "Repairing missed slots"
+        );
+        let repaired = self.backfill.fetch_slots(&repairs).await?;
+        self.cache
+            .metrics()
+            .slots_repaired
+            .fetch_add(repaired.blocks, Ordering::Relaxed);
+        Ok(Some(report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::Block;
+    use crate::testing::fixtures::sample_slot;
+    use crate::testing::mock_db::MockDatabase;
+    use crate::testing::mock_sources::MockSlotSource;
+    use crate::utils::metrics::IndexerMetrics;
+
+    fn block(slot: u64) -> Block {
+        Block {
+            slot: sample_slot(slot),
+            transactions: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn classifies_gaps_and_repairs_missed_slots() {
+        let db = Arc::new(MockDatabase::new());
+        let cache = Arc::new(MultiCache::new(10, 10, 10, db, IndexerMetrics::new()));
+        // Produced: 98..=110 except 103 and 107 (skipped by their leaders).
+        let produced: Vec<u64> = (98..=110).filter(|s| *s != 103 && *s != 107).collect();
+        let source = Arc::new(MockSlotSource::with_blocks(
+            produced.iter().map(|&s| block(s)).collect(),
+        ));
+        // The indexer stored 100, 104 and 110; the checkpoint lands on 110 + FINALITY_MARGIN.
+        for slot in [100, 104, 110, 110 + FINALITY_MARGIN] {
+            cache.store_slot(sample_slot(slot)).await.unwrap();
+        }
+
+        let detector = GapDetector::new(source.clone(), cache.clone());
+        let report = detector.scan(100, 110).await.unwrap();
+        assert_eq!(report.stored, 3);
+        assert_eq!(report.first_stored, Some(100));
+        assert_eq!(report.skipped, 2);
+        assert_eq!(report.missed, 6);
+        assert_eq!(report.missed_slots, vec![101, 102, 105, 106, 108, 109]);
+        assert_eq!(
+            report.missed_ranges,
+            vec![
+                SlotGap {
+                    start: 101,
+                    end: 102
+                },
+                SlotGap {
+                    start: 105,
+                    end: 106
+                },
+                SlotGap {
+                    start: 108,
+                    end: 109
+                },
+            ]
+        );
+        assert!((report.coverage - 3.0 / 9.0).abs() < 1e-9);
+
+        let repairer = GapRepairer::new(
+            source,
+            cache.clone(),
+            GapConfig {
+                scan_interval_secs: 60,
+                window: 13,
+                repair_rps: 1000,
+            },
+        );
+        let before = repairer.repair_once().await.unwrap().unwrap();
+        assert_eq!((before.from, before.to, before.missed), (98, 110, 8));
+        // 98 and 99 predate the first stored slot, so only the six interior slots are fetched.
+        assert_eq!(cache.metrics().slots_repaired.load(Ordering::Relaxed), 6);
+
+        let after = detector.scan(100, 110).await.unwrap();
+        assert_eq!((after.missed, after.skipped), (0, 2));
+        assert_eq!(after.coverage, 1.0);
+    }
+}
@@ -5,6 +5,7 @@ use std::sync::Arc;
 const MAX_ENTRIES: usize = 128;
 
 /// Caches slot → leader lookups to avoid one RPC call per streamed slot.
+#[derive(Default)]
 pub struct LeaderCache {
     entries: HashMap<u64, String>,
     order: Vec<u64>,
@@ -12,10 +13,7 @@ pub struct LeaderCache {
 
 impl LeaderCache {
     pub fn new() -> Self {
-        Self {
-            entries: HashMap::new(),
-            order: Vec::new(),
-        }
+        Self::default()
     }
 
     pub async fn leader_for_slot(
@@ -1,9 +1,17 @@
 pub mod account_watcher;
+pub mod backfill;
+pub mod block_poller;
 pub mod channels;
 pub mod commands;
+pub mod decoders;
+pub mod dedupe;
 pub mod enrichment_limiter;
+pub mod events;
+pub mod fork_tracker;
+pub mod gaps;
 pub mod leader_cache;
 pub mod runtime;
 pub mod slot_pipeline;
 pub mod slot_tracker;
-pub mod types;
\ No newline at end of file
+pub mod types;
+pub mod webhooks;
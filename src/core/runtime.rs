@@ -1,8 +1,10 @@
 use crate::api::{self, ApiServeConfig, ReadinessDeps};
 use crate::context::AppContext;
 use crate::core::account_watcher::AccountWatcher;
-use crate::core::slot_pipeline::{self, SlotPipelineOptions};
-use crate::core::types::{AccountState, Slot, TransactionInfo};
+use crate::core::gaps::GapRepairer;
+use crate::core::slot_pipeline::{self, SlotHandler, SlotPipelineOptions, TxHandler};
+use crate::core::types::AccountState;
+use crate::core::webhooks::WebhookNotifier;
 use crate::utils::errors::Result;
 use crate::utils::shutdown;
 use std::collections::HashSet;
@@ -11,6 +13,9 @@ use std::time::Duration;
 use tokio::sync::broadcast;
 use tokio::task::JoinHandle;
 
+/// Callback for watched account changes: `(address, previous, current)`.
+pub type AccountChangeHandler = Arc<dyn Fn(&str, &AccountState, &AccountState) + Send + Sync>;
+
 #[derive(Clone, Copy)]
 pub struct IndexerOptions {
     pub pipeline: SlotPipelineOptions,
@@ -68,9 +73,9 @@ pub async fn collect_watch_accounts(ctx: &AppContext) -> Result<Vec<String>> {
 pub async fn run(
     ctx: AppContext,
     options: IndexerOptions,
-    on_slot: Arc<dyn Fn(Slot, Option<String>) + Send + Sync>,
-    on_tx: Arc<dyn Fn(TransactionInfo) + Send + Sync>,
-    on_account_change: Arc<dyn Fn(&str, &AccountState, &AccountState) + Send + Sync>,
+    on_slot: SlotHandler,
+    on_tx: TxHandler,
+    on_account_change: AccountChangeHandler,
 ) -> Result<()> {
     let (shutdown_tx, _) = broadcast::channel(1);
 
@@ -94,6 +99,8 @@ pub async fn run(
 
     let api_port = options.api_port.or(ctx.config.api_port);
     let mut api_handle = spawn_api_server(&ctx, api_port, &shutdown_tx);
+    let webhook_handle = spawn_webhook_notifier(&ctx, &shutdown_tx).await?;
+    let gap_handle = spawn_gap_repairer(&ctx, &shutdown_tx);
 
     wait_for_shutdown(
         shutdown_tx,
@@ -111,6 +118,12 @@ pub async fn run(
     if let Some(h) = api_handle {
         handles.push(h);
     }
+    if let Some(h) = webhook_handle {
+        handles.push(h);
+    }
+    if let Some(h) = gap_handle {
+        handles.push(h);
+    }
     shutdown::shutdown_handles(handles).await;
 
     ctx.metrics.log_snapshot();
@@ -142,9 +155,10 @@ fn spawn_api_server(
         bind_localhost: ctx.config.api_bind_localhost,
         readiness: Some(ReadinessDeps {
             cache: ctx.cache.clone(),
-            rpc: ctx.rpc_client(),
+            rpc: ctx.slot_source(),
             yellowstone: ctx.yellowstone_source(),
             yellowstone_connected: Some(ctx.yellowstone_connected.clone()),
+            limits: ctx.config.readiness,
         }),
     };
     let shutdown_rx = shutdown_tx.subscribe();
@@ -156,29 +170,83 @@ fn spawn_api_server(
     }))
 }
 
+async fn spawn_webhook_notifier(
+    ctx: &AppContext,
+    shutdown_tx: &broadcast::Sender<()>,
+) -> Result<Option<JoinHandle<()>>> {
+    let config = &ctx.config.webhooks;
+    if config.urls.is_empty() {
+        return Ok(None);
+    }
+
+    let mut match_accounts = collect_watch_accounts(ctx).await?;
+    match_accounts.extend(ctx.config.index_programs.iter().cloned());
+    match_accounts.extend(ctx.config.rpc.yellowstone_tx_accounts.iter().cloned());
+    tracing::info!(
+        urls = config.urls.len(),
+        events = %config.events.join(","),
+        "Webhook notifier enabled"
+    );
+    let notifier =
+        WebhookNotifier::new(ctx.cache.clone(), config).with_match_accounts(match_accounts);
+
+    let events = ctx.cache.subscribe();
+    let shutdown_rx = shutdown_tx.subscribe();
+    Ok(Some(tokio::spawn(async move {
+        notifier.run_until(events, shutdown_rx).await;
+    })))
+}
+
+fn spawn_gap_repairer(
+    ctx: &AppContext,
+    shutdown_tx: &broadcast::Sender<()>,
+) -> Option<JoinHandle<()>> {
+    let config = ctx.config.gaps;
+    if config.scan_interval_secs == 0 {
+        return None;
+    }
+
+    tracing::info!(
+        interval_secs = config.scan_interval_secs,
+        window = config.window,
+        "Gap repairer enabled"
+    );
+    let repairer = GapRepairer::new(ctx.slot_source(), ctx.cache.clone(), config);
+    let shutdown_rx = shutdown_tx.subscribe();
+    Some(tokio::spawn(async move {
+        repairer.run_until(shutdown_rx).await;
+    }))
+}
+
 async fn spawn_account_watcher(
     ctx: &AppContext,
     watch_accounts: bool,
     shutdown_tx: &broadcast::Sender<()>,
-    on_account_change: Arc<dyn Fn(&str, &AccountState, &AccountState) + Send + Sync>,
+    on_account_change: AccountChangeHandler,
 ) -> Result<Option<JoinHandle<()>>> {
     if !watch_accounts {
         return Ok(None);
     }
 
     let accounts = collect_watch_accounts(ctx).await?;
-    if accounts.is_empty() {
-        tracing::info!("No wallets or WATCH_ACCOUNTS configured; account watcher idle");
+    let programs = ctx.config.index_programs.clone();
+    if accounts.is_empty() && programs.is_empty() {
+        tracing::info!("No wallets, WATCH_ACCOUNTS or INDEX_PROGRAMS configured; account watcher idle");
         return Ok(None);
     }
 
-    tracing::info!("Watching {} account(s) in parallel", accounts.len());
+    tracing::info!(
+        "Watching {} account(s) and accounts of {} program(s) in parallel",
+        accounts.len(),
+        programs.len()
+    );
     let watcher = AccountWatcher::with_accounts(
         ctx.account_source(),
         ctx.yellowstone_source(),
         ctx.cache.clone(),
         accounts,
-    );
+    )
+    .with_programs(programs);
     watcher.seed_accounts().await?;
 
     let shutdown_rx = shutdown_tx.subscribe();
@@ -1,4 +1,5 @@
 use crate::context::AppContext;
+use crate::core::block_poller::BlockPollerOptions;
 use crate::core::channels;
 use crate::core::leader_cache::LeaderCache;
 use crate::core::slot_tracker::SlotTracker;
@@ -7,6 +8,12 @@ use std::sync::Arc;
 use tokio::sync::broadcast;
 use tokio::task::JoinHandle;
 
+/// Display callback for each streamed slot (with its leader when requested).
+pub type SlotHandler = Arc<dyn Fn(Slot, Option<String>) + Send + Sync>;
+
+/// Display callback for each streamed transaction.
+pub type TxHandler = Arc<dyn Fn(TransactionInfo) + Send + Sync>;
+
 #[derive(Clone, Copy)]
 pub struct SlotPipelineOptions {
     pub show_leaders: bool,
@@ -26,8 +33,8 @@ impl Default for SlotPipelineOptions {
 pub fn spawn(
     ctx: AppContext,
     options: SlotPipelineOptions,
-    on_slot: Arc<dyn Fn(Slot, Option<String>) + Send + Sync>,
-    on_tx: Arc<dyn Fn(TransactionInfo) + Send + Sync>,
+    on_slot: SlotHandler,
+    on_tx: TxHandler,
     shutdown: broadcast::Sender<()>,
 ) -> (JoinHandle<()>, JoinHandle<()>) {
     let (slot_tx, slot_rx) = channels::slot_channel();
@@ -44,7 +51,12 @@ pub fn spawn(
         Some(ctx.yellowstone_connected.clone()),
         slot_tx,
         tx_tx,
-    );
+    )
+    .with_rpc_poller(BlockPollerOptions {
+        lag_slots: ctx.config.rpc.poll_lag_slots,
+        concurrency: ctx.config.rpc.poll_concurrency,
+        ..BlockPollerOptions::default()
+    });
 
     let shutdown_rx_tracker = shutdown.subscribe();
 
@@ -1,17 +1,27 @@
+use crate::core::block_poller::{BlockPoller, BlockPollerOptions};
+use crate::core::dedupe::UpdateDeduper;
 use crate::core::enrichment_limiter::EnrichmentLimiter;
-use crate::core::types::{Slot, SlotStatus, TransactionInfo};
+use crate::core::fork_tracker::ForkTracker;
+use crate::core::types::{Slot, SlotLifecycle, SlotStatus, TransactionInfo};
 use crate::data_sources::{SlotSource, YellowstoneSource};
 use crate::storage::cache::multi_cache::MultiCache;
 use crate::utils::errors::{IndexerError, Result};
 use crate::utils::metrics::IndexerMetrics;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::sync::{broadcast, mpsc};
 
+mod reconnect;
+
+use reconnect::ReconnectBackoff;
+
 /// Delay before background metadata backfill (lets blocks become available on RPC).
 const METADATA_BACKFILL_DELAY: Duration = Duration::from_secs(2);
 
+/// A Yellowstone stream that stayed up this long resets the reconnect backoff.
+const HEALTHY_STREAM: Duration = Duration::from_secs(30);
+
 pub struct SlotTracker {
     yellowstone: Option<Arc<dyn YellowstoneSource>>,
     rpc: Arc<dyn SlotSource>,
@@ -19,13 +29,17 @@ pub struct SlotTracker {
     cache: Arc<MultiCache>,
     metrics: Arc<IndexerMetrics>,
     enrich_limiter: EnrichmentLimiter,
+    forks: ForkTracker,
     backfill_max_slots: u64,
+    poller: BlockPollerOptions,
+    reconnect: ReconnectBackoff,
     yellowstone_connected: Option<Arc<AtomicBool>>,
     slot_tx: mpsc::Sender<Slot>,
     tx_tx: mpsc::Sender<TransactionInfo>,
 }
 
 impl SlotTracker {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         yellowstone: Option<Arc<dyn YellowstoneSource>>,
         rpc: Arc<dyn SlotSource>,
@@ -45,48 +59,103 @@ impl SlotTracker {
             cache,
             metrics,
             enrich_limiter: EnrichmentLimiter::new(enrich_min_interval),
+            forks: ForkTracker::new(),
             backfill_max_slots,
+            poller: BlockPollerOptions::default(),
+            reconnect: ReconnectBackoff::default(),
             yellowstone_connected,
             slot_tx,
             tx_tx,
         }
     }
 
+    /// Interval, head lag and concurrency of the RPC block poller (fallback mode).
+    pub fn with_rpc_poller(mut self, options: BlockPollerOptions) -> Self {
+        self.poller = options;
+        self
+    }
+
+    /// Streams from Yellowstone when configured, indexing from RPC while it is down and
+    /// reconnecting with backoff; otherwise polls RPC. Returns on shutdown.
     pub async fn start_until(&self, mut shutdown: broadcast::Receiver<()>) -> Result<()> {
+        let Some(yellowstone) = &self.yellowstone else {
+            tracing::info!("No Yellowstone configured, using RPC polling");
+            self.poll_from_rpc(&mut shutdown, None).await?;
+            return Ok(());
+        };
         self.backfill_from_checkpoint().await?;
 
-        if let Some(yellowstone) = &self.yellowstone {
-            match yellowstone.subscribe_with_transactions().await {
+        let mut backoff = self.reconnect;
+        let mut deduper = UpdateDeduper::new();
+        let mut first_attempt = true;
+        let mut replay_rejected = false;
+        loop {
+            // The first connection streams live (the restart gap was backfilled above); later
+            // ones replay from the last stored slot, and the deduper drops what was seen.
+            let from_slot = if first_attempt || replay_rejected {
+                None
+            } else {
+                self.cache.get_checkpoint().await?
+            };
+            first_attempt = false;
+
+            match yellowstone.subscribe_with_transactions(from_slot).await {
                 Ok((slot_stream, tx_stream)) => {
-                    tracing::info!("Using Yellowstone gRPC (real-time streaming)");
+                    tracing::info!(?from_slot, "Using Yellowstone gRPC (real-time streaming)");
                     if let Some(flag) = &self.yellowstone_connected {
                         flag.store(true, Ordering::Relaxed);
                     }
 
+                    let connected = Instant::now();
+                    let mut received = 0;
                     let result = self
-                        .stream_from_yellowstone(slot_stream, tx_stream, &mut shutdown)
+                        .stream_from_yellowstone(
+                            slot_stream,
+                            tx_stream,
+                            &mut deduper,
+                            &mut received,
+                            &mut shutdown,
+                        )
                         .await;
 
                     if let Some(flag) = &self.yellowstone_connected {
                         flag.store(false, Ordering::Relaxed);
                     }
 
-                    match result {
-                        Ok(_) => return Ok(()),
-                        Err(e) => {
-                            tracing::warn!("Yellowstone stream failed: {}, falling back to RPC", e);
-                        }
+                    let Err(e) = result else {
+                        return Ok(());
+                    };
+                    tracing::warn!("Yellowstone stream failed: {e}");
+                    if connected.elapsed() >= HEALTHY_STREAM {
+                        backoff.reset();
+                        replay_rejected = false;
+                    } else if from_slot.is_some() && received == 0 {
+                        // The server may no longer retain `from_slot`; reconnect live instead.
+                        tracing::warn!(
+                            ?from_slot,
+                            "Yellowstone replay failed; next reconnect streams live"
+                        );
+                        replay_rejected = true;
                     }
                 }
-                Err(e) => {
-                    tracing::warn!("Yellowstone connection failed: {}, using RPC polling", e);
-                }
+                Err(e) => tracing::warn!("Yellowstone connection failed: {e}"),
             }
-        } else {
-            tracing::info!("No Yellowstone configured, using RPC polling");
+
+            let retry_in = backoff.next_delay();
+            tracing::info!(
+                retry_in_ms = retry_in.as_millis() as u64,
+                "Indexing from RPC until Yellowstone reconnects"
+            );
+            if self
+                .poll_from_rpc(&mut shutdown, Some(Instant::now() + retry_in))
+                .await?
+            {
+                return Ok(());
+            }
+            self.metrics
+                .yellowstone_reconnects
+                .fetch_add(1, Ordering::Relaxed);
         }
-        tracing::info!("Using RPC polling (fallback mode)");
-        self.poll_from_rpc(&mut shutdown).await
     }
 
     async fn maybe_enrich_slot(&self, slot: &mut Slot) {
@@ -99,10 +168,12 @@ impl SlotTracker {
             return;
         }
 
-        if self.enrich_rpc.enrich_slot_block_metadata(slot).await.is_ok() {
-            if slot.block_hash.is_some() || slot.block_height.is_some() {
-                self.metrics.enrich_success.fetch_add(1, Ordering::Relaxed);
-            }
+        let started = Instant::now();
+        let enriched = self.enrich_rpc.enrich_slot_block_metadata(slot).await;
+        self.metrics.enrich_latency.observe(started.elapsed());
+        if enriched.is_ok() && (slot.block_hash.is_some() || slot.block_height.is_some())
+        {
+            self.metrics.enrich_success.fetch_add(1, Ordering::Relaxed);
         }
     }
 
@@ -118,10 +189,10 @@ impl SlotTracker {
         tokio::spawn(async move {
             tokio::time::sleep(METADATA_BACKFILL_DELAY).await;
             let mut updated = slot;
-            if enrich_rpc
-                .enrich_slot_block_metadata(&mut updated)
-                .await
-                .is_ok()
+            let started = Instant::now();
+            let enriched = enrich_rpc.enrich_slot_block_metadata(&mut updated).await;
+            metrics.enrich_latency.observe(started.elapsed());
+            if enriched.is_ok()
                 && (updated.block_hash.is_some() || updated.block_height.is_some())
             {
                 metrics.enrich_success.fetch_add(1, Ordering::Relaxed);
@@ -133,23 +204,50 @@ impl SlotTracker {
     }
 
     async fn forward_slot(&self, mut slot: Slot) -> bool {
+        let received = Instant::now();
         self.maybe_enrich_slot(&mut slot).await;
 
         if self.slot_tx.send(slot.clone()).await.is_err() {
             tracing::debug!("Pipeline channel closed, stopping slot tracker");
             return true;
         }
+        self.metrics
+            .slot_channel_depth
+            .store(queued(&self.slot_tx), Ordering::Relaxed);
 
         if let Err(e) = self.cache.store_slot(slot.clone()).await {
             tracing::error!("Failed to cache slot: {}", e);
         } else {
+            self.metrics.slot_store_lag.observe(received.elapsed());
             self.metrics.maybe_log_periodic(100);
-            self.spawn_metadata_backfill(slot);
+            if !matches!(slot.status, SlotStatus::Dead) {
+                self.spawn_metadata_backfill(slot.clone());
+            }
         }
 
+        self.rollback_orphans(&slot).await;
         false
     }
 
+    /// Marks slots abandoned by the finalized fork (or reported dead) and drops their transactions.
+    async fn rollback_orphans(&self, slot: &Slot) {
+        let mut orphaned = self.forks.observe(slot);
+        if matches!(slot.status, SlotStatus::Dead) {
+            orphaned.push(slot.slot);
+        }
+
+        for n in orphaned {
+            match self.cache.rollback_slot(n).await {
+                Ok(removed) => tracing::info!(
+                    slot = n,
+                    transactions_removed = removed,
+                    "Rolled back orphaned slot"
+                ),
+                Err(e) => tracing::error!("Failed to roll back slot {n}: {e}"),
+            }
+        }
+    }
+
     async fn backfill_from_checkpoint(&self) -> Result<()> {
         let Some(last) = self.cache.get_checkpoint().await? else {
             return Ok(());
@@ -184,6 +282,7 @@ impl SlotTracker {
                 timestamp: chrono::Utc::now().timestamp(),
                 block_hash: None,
                 block_height: None,
+                lifecycle: SlotLifecycle::default(),
             };
             let _ = self.enrich_rpc.enrich_slot_block_metadata(&mut slot).await;
             if let Err(e) = self.cache.store_slot(slot).await {
@@ -195,14 +294,40 @@ impl SlotTracker {
         Ok(())
     }
 
-    async fn forward_tx(&self, tx: TransactionInfo) -> bool {
+    async fn forward_tx(&self, mut tx: TransactionInfo) -> bool {
         if self.tx_tx.send(tx.clone()).await.is_err() {
             tracing::debug!("Pipeline channel closed, stopping slot tracker");
             return true;
         }
-
+        self.metrics
+            .tx_channel_depth
+            .store(queued(&self.tx_tx), Ordering::Relaxed);
+
+        let signature = tx.signature.clone();
+        let mut instructions = std::mem::take(&mut tx.instruction_details);
+        let token_changes = std::mem::take(&mut tx.token_balance_changes);
+        let sol_changes = std::mem::take(&mut tx.sol_balance_changes);
+        self.cache.decode_instructions(&mut instructions, &tx.accounts);
         if let Err(e) = self.cache.store_transaction(tx.into()).await {
             tracing::error!("Failed to cache transaction: {}", e);
+            return false;
+        }
+        if let Err(e) = self.cache.store_instructions(&signature, &instructions).await {
+            tracing::error!("Failed to store instructions for {}: {}", signature, e);
+        }
+        if let Err(e) = self
+            .cache
+            .store_token_balance_changes(&signature, &token_changes)
+            .await
+        {
+            tracing::error!("Failed to store token balance changes for {}: {}", signature, e);
+        }
+        if let Err(e) = self
+            .cache
+            .store_sol_balance_changes(&signature, &sol_changes)
+            .await
+        {
+            tracing::error!("Failed to store SOL balance changes for {}: {}", signature, e);
         }
 
         false
@@ -212,6 +337,8 @@ impl SlotTracker {
         &self,
         mut slot_stream: mpsc::Receiver<Slot>,
         mut tx_stream: mpsc::Receiver<TransactionInfo>,
+        deduper: &mut UpdateDeduper,
+        received: &mut u64,
         shutdown: &mut broadcast::Receiver<()>,
     ) -> Result<()> {
         loop {
@@ -228,6 +355,10 @@ impl SlotTracker {
                         ));
                     };
 
+                    *received += 1;
+                    if !deduper.first_slot_update(&slot) {
+                        continue;
+                    }
                     tracing::debug!(slot = slot.slot, source = "yellowstone", "ingest_slot");
 
                     if self.forward_slot(slot).await {
@@ -241,6 +372,10 @@ impl SlotTracker {
                         ));
                     };
 
+                    *received += 1;
+                    if !deduper.first_transaction(&tx.signature) {
+                        continue;
+                    }
                     tracing::debug!(signature = %tx.signature, source = "yellowstone", "ingest_tx");
 
                     if self.forward_tx(tx).await {
@@ -251,33 +386,51 @@ impl SlotTracker {
         }
     }
 
-    async fn poll_from_rpc(&self, shutdown: &mut broadcast::Receiver<()>) -> Result<()> {
-        let mut slot_stream = self.rpc.subscribe_slots().await?;
+    /// Indexes every produced block from RPC, resuming after the checkpoint (at most
+    /// `backfill_max_slots` behind the head), until `deadline` if set. Returns `true` when
+    /// stopped by shutdown or a closed pipeline rather than the deadline.
+    async fn poll_from_rpc(
+        &self,
+        shutdown: &mut broadcast::Receiver<()>,
+        deadline: Option<Instant>,
+    ) -> Result<bool> {
+        let options = BlockPollerOptions {
+            max_catch_up: self.backfill_max_slots,
+            ..self.poller
+        };
+        let resume_after = self.cache.get_checkpoint().await?;
+        let mut blocks = BlockPoller::new(self.rpc.clone(), options).spawn(resume_after);
+        let until = async {
+            match deadline {
+                Some(at) => tokio::time::sleep_until(at.into()).await,
+                None => std::future::pending().await,
+            }
+        };
+        tokio::pin!(until);
 
         loop {
             tokio::select! {
                 biased;
                 _ = shutdown.recv() => {
                     tracing::debug!("Slot tracker stopping (shutdown signal)");
-                    return Ok(());
+                    return Ok(true);
                 }
-                slot = slot_stream.recv() => {
-                    let Some(slot) = slot else {
-                        return Ok(());
+                _ = &mut until => return Ok(false),
+                block = blocks.recv() => {
+                    let Some(block) = block else {
+                        return Ok(true);
                     };
 
-                    tracing::debug!(slot = slot.slot, source = "rpc", "ingest_slot");
+                    tracing::debug!(slot = block.slot.slot, source = "rpc", "ingest_slot");
 
-                    if let Ok(transactions) = self.rpc.get_block_with_transactions(slot.slot).await {
-                        for tx in transactions {
-                            if self.forward_tx(tx).await {
-                                return Ok(());
-                            }
+                    for tx in block.transactions {
+                        if self.forward_tx(tx).await {
+                            return Ok(true);
                         }
                     }
 
-                    if self.forward_slot(slot).await {
-                        return Ok(());
+                    if self.forward_slot(block.slot).await {
+                        return Ok(true);
                     }
                 }
             }
@@ -285,24 +438,31 @@ impl SlotTracker {
     }
 }
 
+/// Messages waiting in `sender`'s channel.
+fn queued<T>(sender: &mpsc::Sender<T>) -> u64 {
+    (sender.max_capacity() - sender.capacity()) as u64
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::core::channels;
+    use crate::core::types::Block;
     use crate::storage::cache::multi_cache::MultiCache;
     use crate::testing::fixtures::sample_slot;
     use crate::testing::mock_db::MockDatabase;
-    use crate::testing::mock_sources::MockSlotSource;
+    use crate::testing::mock_sources::{MockSlotSource, MockYellowstoneSource};
     use crate::utils::metrics::IndexerMetrics;
     use tokio::sync::broadcast;
     use tokio::time::{timeout, Duration};
 
     #[tokio::test]
     async fn rpc_poll_forwards_slot_to_channel_and_cache() {
-        let slot = sample_slot(42);
         let metrics = IndexerMetrics::new();
-        let rpc: Arc<dyn SlotSource> =
-            Arc::new(MockSlotSource::with_slots("mock-leader", vec![slot.clone()]));
+        let rpc: Arc<dyn SlotSource> = Arc::new(MockSlotSource::with_blocks(vec![Block {
+            slot: sample_slot(42),
+            transactions: vec![],
+        }]));
         let db = Arc::new(MockDatabase::new());
         let cache = Arc::new(MultiCache::new(10, 10, 10, db, metrics.clone()));
         let (slot_tx, mut slot_rx) = channels::slot_channel();
@@ -319,7 +479,11 @@ mod tests {
             None,
             slot_tx,
             tx_tx,
-        );
+        )
+        .with_rpc_poller(BlockPollerOptions {
+            lag_slots: 0,
+            ..BlockPollerOptions::default()
+        });
         let (_shutdown_tx, shutdown_rx) = broadcast::channel(1);
         let tracker_task = tokio::spawn(async move {
             tracker.start_until(shutdown_rx).await
@@ -341,6 +505,131 @@ mod tests {
         tracker_task.abort();
     }
 
+    #[tokio::test]
+    async fn yellowstone_reconnects_with_replay_and_drops_duplicates() {
+        let metrics = IndexerMetrics::new();
+        let rpc: Arc<dyn SlotSource> = Arc::new(MockSlotSource::new("leader"));
+        let yellowstone = Arc::new(MockYellowstoneSource::with_connections(vec![
+            Some(vec![sample_slot(10), sample_slot(11)]),
+            None,
+            // The replay from slot 11 repeats it before the new slot.
+            Some(vec![sample_slot(11), sample_slot(12)]),
+        ]));
+        let connected = Arc::new(AtomicBool::new(false));
+        let cache = Arc::new(MultiCache::new(
+            10,
+            10,
+            10,
+            Arc::new(MockDatabase::new()),
+            metrics.clone(),
+        ));
+        let (slot_tx, mut slot_rx) = channels::slot_channel();
+        let (tx_tx, _tx_rx) = channels::transaction_channel();
+
+        let mut tracker = SlotTracker::new(
+            Some(yellowstone.clone()),
+            rpc.clone(),
+            rpc,
+            cache,
+            metrics.clone(),
+            Duration::from_millis(0),
+            100,
+            Some(connected.clone()),
+            slot_tx,
+            tx_tx,
+        );
+        tracker.reconnect =
+            ReconnectBackoff::new(Duration::from_millis(1), Duration::from_millis(5));
+        let (_shutdown_tx, shutdown_rx) = broadcast::channel(1);
+        let tracker_task = tokio::spawn(async move { tracker.start_until(shutdown_rx).await });
+
+        let mut received = Vec::new();
+        while received.len() < 3 {
+            let slot = timeout(Duration::from_secs(2), slot_rx.recv())
+                .await
+                .expect("tracker should keep forwarding slots across reconnects")
+                .unwrap();
+            received.push(slot.slot);
+        }
+        assert_eq!(received, vec![10, 11, 12]);
+
+        // Back on the (now idle) gRPC stream.
+        timeout(Duration::from_secs(2), async {
+            while yellowstone.requests.lock().unwrap().len() < 4 {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .unwrap();
+        assert!(timeout(Duration::from_millis(50), slot_rx.recv()).await.is_err());
+        assert_eq!(
+            *yellowstone.requests.lock().unwrap(),
+            vec![None, Some(11), Some(11), Some(12)]
+        );
+        assert!(connected.load(Ordering::Relaxed));
+        assert_eq!(metrics.yellowstone_reconnects.load(Ordering::Relaxed), 3);
+
+        tracker_task.abort();
+    }
+
+    #[tokio::test]
+    async fn dead_slot_from_yellowstone_is_rolled_back() {
+        let metrics = IndexerMetrics::new();
+        let rpc: Arc<dyn SlotSource> = Arc::new(MockSlotSource::new("leader"));
+        let dead = Slot {
+            status: SlotStatus::Dead,
+            ..sample_slot(20)
+        };
+        let yellowstone = Arc::new(MockYellowstoneSource::with_connections(vec![Some(vec![
+            sample_slot(20),
+            dead,
+        ])]));
+        let cache = Arc::new(MultiCache::new(
+            10,
+            10,
+            10,
+            Arc::new(MockDatabase::new()),
+            metrics.clone(),
+        ));
+        let (slot_tx, mut slot_rx) = channels::slot_channel();
+        let (tx_tx, _tx_rx) = channels::transaction_channel();
+
+        let tracker = SlotTracker::new(
+            Some(yellowstone),
+            rpc.clone(),
+            rpc,
+            cache.clone(),
+            metrics.clone(),
+            Duration::from_millis(0),
+            100,
+            None,
+            slot_tx,
+            tx_tx,
+        );
+        let (_shutdown_tx, shutdown_rx) = broadcast::channel(1);
+        let tracker_task = tokio::spawn(async move { tracker.start_until(shutdown_rx).await });
+
+        for _ in 0..2 {
+            timeout(Duration::from_secs(2), slot_rx.recv())
+                .await
+                .expect("tracker should forward both updates")
+                .unwrap();
+        }
+        timeout(Duration::from_secs(2), async {
+            while metrics.slots_orphaned.load(Ordering::Relaxed) == 0 {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .expect("dead slot should be rolled back");
+
+        let stored = cache.get_slot(20).await.unwrap().expect("slot is kept");
+        assert_eq!(stored.status, SlotStatus::Dead);
+        assert_eq!(metrics.slots_orphaned.load(Ordering::Relaxed), 1);
+
+        tracker_task.abort();
+    }
+
     #[tokio::test]
     async fn yellowstone_path_enriches_block_metadata_via_rpc() {
         let mut slot = sample_slot(99);
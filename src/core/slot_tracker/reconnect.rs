@@ -0,0 +1,62 @@
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::time::Duration;
+
+/// Jittered exponential backoff between Yellowstone reconnect attempts.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct ReconnectBackoff {
+    base: Duration,
+    max: Duration,
+    attempts: u32,
+}
+
+impl Default for ReconnectBackoff {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(1), Duration::from_secs(60))
+    }
+}
+
+impl ReconnectBackoff {
+    pub(super) fn new(base: Duration, max: Duration) -> Self {
+        Self {
+            base,
+            max,
+            attempts: 0,
+        }
+    }
+
+    /// Next delay: `base * 2^attempts` capped at `max`, then a random 50–100% of that so
+    /// several indexers do not reconnect in lockstep.
+    pub(super) fn next_delay(&mut self) -> Duration {
+        let factor = 1u32.checked_shl(self.attempts).unwrap_or(u32::MAX);
+        self.attempts = self.attempts.saturating_add(1);
+        let ceiling = self.base.saturating_mul(factor).min(self.max);
+        let jitter = RandomState::new().hash_one(self.attempts) % 1000;
+        ceiling / 2 + ceiling.mul_f64(jitter as f64 / 2000.0)
+    }
+
+    pub(super) fn reset(&mut self) {
+        self.attempts = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delays_double_with_jitter_up_to_cap() {
+        let mut backoff = ReconnectBackoff::new(Duration::from_secs(1), Duration::from_secs(8));
+        let delays: Vec<Duration> = (0..6).map(|_| backoff.next_delay()).collect();
+        for (delay, ceiling) in delays.iter().zip([1, 2, 4, 8, 8, 8]) {
+            let ceiling = Duration::from_secs(ceiling);
+            assert!(
+                *delay >= ceiling / 2 && *delay <= ceiling,
+                "{delay:?} vs {ceiling:?}"
+            );
+        }
+
+        backoff.reset();
+        assert!(backoff.next_delay() <= Duration::from_secs(1));
+    }
+}
@@ -10,15 +10,47 @@ pub struct Slot {
     pub timestamp: i64,
     pub block_hash: Option<String>,
     pub block_height: Option<u64>,
+    #[serde(default)]
+    pub lifecycle: SlotLifecycle,
+}
+
+/// First-seen unix timestamps for each commitment level a slot reached.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlotLifecycle {
+    pub processed_at: Option<i64>,
+    pub confirmed_at: Option<i64>,
+    pub finalized_at: Option<i64>,
+}
+
+impl SlotLifecycle {
+    /// Records `at` for `status` unless an earlier time is already known.
+    pub fn record(&mut self, status: &SlotStatus, at: i64) {
+        let field = match status {
+            SlotStatus::Processed => &mut self.processed_at,
+            SlotStatus::Confirmed => &mut self.confirmed_at,
+            SlotStatus::Finalized => &mut self.finalized_at,
+            SlotStatus::Dead => return,
+        };
+        field.get_or_insert(at);
+    }
+
+    /// Keeps the earlier first-seen times from `previous` (e.g. the cached copy of the slot).
+    pub fn merge_earlier(&mut self, previous: &SlotLifecycle) {
+        self.processed_at = previous.processed_at.or(self.processed_at);
+        self.confirmed_at = previous.confirmed_at.or(self.confirmed_at);
+        self.finalized_at = previous.finalized_at.or(self.finalized_at);
+    }
 }
 
 // Slot confirmation status.
 #[non_exhaustive]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum SlotStatus {
     Processed,
     Confirmed,
     Finalized,
+    /// Orphaned by a different finalized fork (or reported dead by the validator).
+    Dead,
 }
 
 impl FromStr for SlotStatus {
@@ -28,6 +60,7 @@ impl FromStr for SlotStatus {
         Ok(match s {
             "Finalized" => SlotStatus::Finalized,
             "Confirmed" => SlotStatus::Confirmed,
+            "Dead" => SlotStatus::Dead,
             _ => SlotStatus::Processed,
         })
     }
@@ -39,11 +72,13 @@ impl SlotStatus {
             SlotStatus::Processed => "Processed",
             SlotStatus::Confirmed => "Confirmed",
             SlotStatus::Finalized => "Finalized",
+            SlotStatus::Dead => "Dead",
         }
     }
 }
 
-/// Persisted transaction shape (DB + L2 cache). Omits display-only fields.
+/// Persisted transaction shape (DB + L2 cache).
+/// Detail fields are `None` for rows indexed before they were persisted.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Transaction {
     pub signature: String,
@@ -52,6 +87,12 @@ pub struct Transaction {
     pub fee: u64,
     pub success: bool,
     pub accounts: Vec<String>,
+    #[serde(default)]
+    pub program: Option<String>,
+    #[serde(default)]
+    pub instruction_count: Option<u32>,
+    #[serde(default)]
+    pub compute_units: Option<u64>,
 }
 
 /// Rich transaction from RPC/Yellowstone streams (program, CU, etc.) before storage.
@@ -66,7 +107,151 @@ pub struct TransactionInfo {
     pub instructions: usize,
     pub compute_units: u64,
     pub accounts: Vec<String>,
+    /// Local ingest time.
     pub timestamp: i64,
+    /// On-chain block time when the source reports it (RPC blocks; not Yellowstone tx updates).
+    #[serde(default)]
+    pub block_time: Option<i64>,
+    /// Top-level and inner instructions, in execution order.
+    #[serde(default)]
+    pub instruction_details: Vec<Instruction>,
+    /// SPL token accounts whose balance this transaction changed.
+    #[serde(default)]
+    pub token_balance_changes: Vec<TokenBalanceChange>,
+    /// Accounts whose lamport balance this transaction changed (fee payer included).
+    #[serde(default)]
+    pub sol_balance_changes: Vec<SolBalanceChange>,
+}
+
+/// One executed instruction. Inner (CPI) instructions carry the index of the
+/// top-level instruction that invoked them plus their position in that group.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Instruction {
+    pub signature: String,
+    pub slot: u64,
+    /// Index of the top-level instruction.
+    pub index: u32,
+    /// Position among the inner instructions of `index`; `None` for the top-level one.
+    pub inner_index: Option<u32>,
+    pub stack_height: Option<u32>,
+    pub program_id: String,
+    /// Indices into the transaction's `accounts`.
+    pub accounts: Vec<u8>,
+    /// Base58 instruction data; `None` for rows indexed before raw data was captured.
+    pub data: Option<String>,
+    /// JsonParsed-style payload for builtin programs, or the output of a registered instruction
+    /// decoder (e.g. an Anchor IDL: `{type, info, accounts}`).
+    pub parsed: Option<serde_json::Value>,
+}
+
+/// One entry of `meta.pre_token_balances` / `meta.post_token_balances`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenBalance {
+    /// Index into the transaction's `accounts`.
+    pub account_index: u32,
+    pub mint: String,
+    pub owner: Option<String>,
+    /// Raw amount in base units.
+    pub amount: u64,
+    pub decimals: u8,
+}
+
+/// Balance of one SPL token account before and after a transaction (raw base units).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenBalanceChange {
+    pub signature: String,
+    pub slot: u64,
+    /// Token account address.
+    pub account: String,
+    /// Wallet owning the token account (absent on very old blocks).
+    pub owner: Option<String>,
+    pub mint: String,
+    pub pre: u64,
+    pub post: u64,
+    pub decimals: u8,
+}
+
+impl TokenBalanceChange {
+    /// Pairs pre/post balances by account index and keeps the accounts whose amount changed.
+    /// Accounts created (no pre entry) or closed (no post entry) count from / to zero.
+    pub fn from_balances(
+        signature: &str,
+        slot: u64,
+        accounts: &[String],
+        pre: &[TokenBalance],
+        post: &[TokenBalance],
+    ) -> Vec<Self> {
+        let mut indices: Vec<u32> = pre.iter().chain(post).map(|b| b.account_index).collect();
+        indices.sort_unstable();
+        indices.dedup();
+
+        indices
+            .into_iter()
+            .filter_map(|index| {
+                let before = pre.iter().find(|b| b.account_index == index);
+                let after = post.iter().find(|b| b.account_index == index);
+                let latest = after.or(before)?;
+                let change = Self {
+                    signature: signature.to_string(),
+                    slot,
+                    account: accounts.get(index as usize)?.clone(),
+                    owner: latest
+                        .owner
+                        .clone()
+                        .or_else(|| before.and_then(|b| b.owner.clone())),
+                    mint: latest.mint.clone(),
+                    pre: before.map_or(0, |b| b.amount),
+                    post: after.map_or(0, |b| b.amount),
+                    decimals: latest.decimals,
+                };
+                (change.pre != change.post).then_some(change)
+            })
+            .collect()
+    }
+}
+
+/// Lamport balance of one account before and after a transaction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SolBalanceChange {
+    pub signature: String,
+    pub slot: u64,
+    pub account: String,
+    pub pre: u64,
+    pub post: u64,
+    /// `post - pre` (negative for debits, including the fee).
+    pub delta: i64,
+}
+
+impl SolBalanceChange {
+    pub fn new(signature: String, slot: u64, account: String, pre: u64, post: u64) -> Self {
+        Self {
+            signature,
+            slot,
+            account,
+            pre,
+            post,
+            delta: post as i64 - pre as i64,
+        }
+    }
+
+    /// Zips `meta.pre_balances` / `meta.post_balances` with the transaction's `accounts`
+    /// and keeps the accounts whose balance changed, in account order.
+    pub fn from_balances(
+        signature: &str,
+        slot: u64,
+        accounts: &[String],
+        pre: &[u64],
+        post: &[u64],
+    ) -> Vec<Self> {
+        accounts
+            .iter()
+            .zip(pre.iter().zip(post))
+            .filter(|(_, (pre, post))| pre != post)
+            .map(|(account, (&pre, &post))| {
+                Self::new(signature.to_string(), slot, account.clone(), pre, post)
+            })
+            .collect()
+    }
 }
 
 impl From<TransactionInfo> for Transaction {
@@ -74,10 +259,13 @@ impl From<TransactionInfo> for Transaction {
         Self {
             signature: info.signature,
             slot: info.slot,
-            block_time: Some(info.timestamp),
+            block_time: info.block_time,
             fee: info.fee,
             success: info.success,
             accounts: info.accounts,
+            program: Some(info.program),
+            instruction_count: Some(info.instructions as u32),
+            compute_units: Some(info.compute_units),
         }
     }
 }
@@ -92,6 +280,55 @@ pub struct AccountState {
     pub executable: bool,
     pub data: Vec<u8>,
     pub rent_epoch: u64,
+    /// Geyser write version, ordering updates within `slot` (0 for RPC snapshots).
+    #[serde(default)]
+    pub write_version: u64,
+    /// Structured `data`, filled in by the owner program's decoder when one is registered.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decoded: Option<DecodedAccount>,
+}
+
+impl AccountState {
+    /// Same on-chain contents (ignores slot, write version and the decoded view).
+    pub fn same_state(&self, other: &AccountState) -> bool {
+        self.lamports == other.lamports
+            && self.owner == other.owner
+            && self.executable == other.executable
+            && self.rent_epoch == other.rent_epoch
+            && self.data == other.data
+    }
+}
+
+/// A produced block from `getBlock`: the slot with its metadata plus every transaction.
+#[derive(Debug, Clone)]
+pub struct Block {
+    pub slot: Slot,
+    pub transactions: Vec<TransactionInfo>,
+}
+
+/// Account data decoded by a [`crate::core::decoders::AccountDecoder`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecodedAccount {
+    /// Decoder label, e.g. `spl-token` or an Anchor program name.
+    pub program: String,
+    pub parsed: serde_json::Value,
+}
+
+/// One queued webhook POST and its retry state (see [`crate::core::webhooks`]).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    pub id: String,
+    pub url: String,
+    /// Event type (`slot`, `transaction` or `account`).
+    pub event: String,
+    /// JSON request body.
+    pub payload: String,
+    /// Failed attempts so far.
+    pub attempts: u32,
+    /// Unix time of the next attempt (for dead letters: when delivery was abandoned).
+    pub next_attempt_at: i64,
+    pub last_error: Option<String>,
+    pub created_at: i64,
 }
 
 #[cfg(test)]
@@ -109,9 +346,53 @@ mod tests {
             SlotStatus::from_str("Confirmed").unwrap(),
             SlotStatus::Confirmed
         ));
+        assert!(matches!(
+            SlotStatus::from_str("Dead").unwrap(),
+            SlotStatus::Dead
+        ));
         assert!(matches!(
             SlotStatus::from_str("unknown").unwrap(),
             SlotStatus::Processed
         ));
     }
+
+    #[test]
+    fn lifecycle_keeps_first_seen_times() {
+        let mut earlier = SlotLifecycle::default();
+        earlier.record(&SlotStatus::Processed, 10);
+        earlier.record(&SlotStatus::Processed, 11);
+        assert_eq!(earlier.processed_at, Some(10));
+
+        let mut later = SlotLifecycle::default();
+        later.record(&SlotStatus::Confirmed, 12);
+        later.merge_earlier(&earlier);
+        assert_eq!(later.processed_at, Some(10));
+        assert_eq!(later.confirmed_at, Some(12));
+        assert_eq!(later.finalized_at, None);
+    }
+
+    #[test]
+    fn token_balance_changes_pair_pre_and_post() {
+        let balance = |account_index, amount| TokenBalance {
+            account_index,
+            mint: "Mint".into(),
+            owner: Some("wallet".into()),
+            amount,
+            decimals: 6,
+        };
+        let accounts: Vec<String> = ["payer", "ata_a", "ata_b", "ata_new"]
+            .map(String::from)
+            .to_vec();
+        let pre = [balance(1, 500), balance(2, 10)];
+        let post = [balance(1, 300), balance(2, 10), balance(3, 200)];
+
+        let changes = TokenBalanceChange::from_balances("sig", 5, &accounts, &pre, &post);
+        let summary: Vec<_> = changes
+            .iter()
+            .map(|c| (c.account.as_str(), c.pre, c.post))
+            .collect();
+        // Unchanged ata_b is skipped; the account created in the tx starts from zero.
+        assert_eq!(summary, vec![("ata_a", 500, 300), ("ata_new", 0, 200)]);
+        assert!(changes.iter().all(|c| c.signature == "sig" && c.slot == 5));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,407 @@
+//! Webhook notifier: POSTs account changes, matched transactions and slots to configured URLs.
+//!
+//! Events are first written to a delivery queue in the database (one row per URL), so pending
+//! deliveries survive restarts. The delivery loop POSTs due rows, retries failures with
+//! exponential backoff and moves deliveries that exhaust `WEBHOOK_MAX_ATTEMPTS` to the
+//! `webhook_dead_letters` table.
+//!
+//! Request body: `{"id", "type", "timestamp", "data"}` (`id` is shared by all URLs of one event;
+//! use it to drop duplicates). With `WEBHOOK_SECRET` set, requests carry
+//! `X-Webhook-Signature: t=<unix>,v1=<hex>`, where `v1` is the HMAC-SHA256 of `"<t>.<body>"`.
+
+use crate::core::events::IndexerEvent;
+use crate::core::types::WebhookDelivery;
+use crate::storage::cache::multi_cache::MultiCache;
+use crate::utils::config::WebhookConfig;
+use crate::utils::errors::Result;
+use futures::StreamExt;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+pub const SIGNATURE_HEADER: &str = "X-Webhook-Signature";
+pub const EVENT_HEADER: &str = "X-Webhook-Event";
+
+const EVENT_TYPES: [&str; 3] = ["account", "transaction", "slot"];
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+const BATCH_SIZE: u32 = 64;
+const MAX_CONCURRENT_DELIVERIES: usize = 8;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const BASE_RETRY_DELAY: Duration = Duration::from_secs(5);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(3600);
+
+pub struct WebhookNotifier {
+    cache: Arc<MultiCache>,
+    client: reqwest::Client,
+    urls: Vec<String>,
+    secret: Option<String>,
+    events: HashSet<String>,
+    match_accounts: HashSet<String>,
+    max_attempts: u32,
+    seq: AtomicU64,
+}
+
+impl WebhookNotifier {
+    pub fn new(cache: Arc<MultiCache>, config: &WebhookConfig) -> Self {
+        let events = config
+            .events
+            .iter()
+            .filter(|e| {
+                let known = EVENT_TYPES.contains(&e.as_str());
+                if !known {
+                    tracing::warn!("Ignoring unknown WEBHOOK_EVENTS entry {e:?}");
+                }
+                known
+            })
+            .cloned()
+            .collect();
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            cache,
+            client,
+            urls: config.urls.clone(),
+            secret: config.secret.clone(),
+            events,
+            match_accounts: HashSet::new(),
+            max_attempts: config.max_attempts.max(1),
+            seq: AtomicU64::new(0),
+        }
+    }
+
+    /// Transactions are only sent when they touch one of `accounts` (watched accounts,
+    /// wallets and indexed programs); without any, no transaction webhooks are sent.
+    pub fn with_match_accounts(mut self, accounts: impl IntoIterator<Item = String>) -> Self {
+        self.match_accounts.extend(accounts);
+        self
+    }
+
+    fn wants(&self, event: &IndexerEvent) -> bool {
+        if !self.events.contains(event.kind()) {
+            return false;
+        }
+        match event {
+            IndexerEvent::Transaction(tx) => {
+                tx.accounts.iter().any(|a| self.match_accounts.contains(a))
+                    || tx
+                        .program
+                        .as_ref()
+                        .is_some_and(|p| self.match_accounts.contains(p))
+            }
+            _ => true,
+        }
+    }
+
+    /// Queues matching `events` and delivers the queue until `shutdown`.
+    pub async fn run_until(
+        &self,
+        events: broadcast::Receiver<IndexerEvent>,
+        shutdown: broadcast::Receiver<()>,
+    ) {
+        let deliver_shutdown = shutdown.resubscribe();
+        tokio::join!(
+            self.enqueue_loop(events, shutdown),
+            self.deliver_loop(deliver_shutdown)
+        );
+    }
+
+    async fn enqueue_loop(
+        &self,
+        mut events: broadcast::Receiver<IndexerEvent>,
+        mut shutdown: broadcast::Receiver<()>,
+    ) {
+        loop {
+            tokio::select! {
+                _ = shutdown.recv() => break,
+                event = events.recv() => match event {
+                    Ok(event) if self.wants(&event) => {
+                        if let Err(e) = self.enqueue(&event).await {
+                            tracing::error!("Failed to queue {} webhook: {e}", event.kind());
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        self.cache
+                            .metrics()
+                            .webhook_events_lagged
+                            .fetch_add(skipped, Ordering::Relaxed);
+                        tracing::warn!(skipped, "Webhook notifier fell behind; events dropped");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+            }
+        }
+    }
+
+    async fn deliver_loop(&self, mut shutdown: broadcast::Receiver<()>) {
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = shutdown.recv() => break,
+                _ = interval.tick() => {
+                    if let Err(e) = self.deliver_due(chrono::Utc::now().timestamp()).await {
+                        tracing::error!("Webhook delivery failed: {e}");
+                    }
+                }
+            }
+        }
+    }
+
+    /// Queues one delivery of `event` per configured URL.
+    pub async fn enqueue(&self, event: &IndexerEvent) -> Result<()> {
+        let now = chrono::Utc::now();
+        let event_id = format!(
+            "{:x}-{:x}",
+            now.timestamp_nanos_opt().unwrap_or_default(),
+            self.seq.fetch_add(1, Ordering::Relaxed)
+        );
+        let mut body = serde_json::to_value(event)?;
+        body["id"] = event_id.clone().into();
+        body["timestamp"] = now.timestamp().into();
+        let payload = body.to_string();
+
+        for (i, url) in self.urls.iter().enumerate() {
+            let delivery = WebhookDelivery {
+                id: format!("{event_id}-{i}"),
+                url: url.clone(),
+                event: event.kind().into(),
+                payload: payload.clone(),
+                attempts: 0,
+                next_attempt_at: now.timestamp(),
+                last_error: None,
+                created_at: now.timestamp(),
+            };
+            self.cache.enqueue_webhook(&delivery).await?;
+        }
+        Ok(())
+    }
+
+    /// Attempts each delivery due at `now`; returns how many were delivered.
+    async fn deliver_due(&self, now: i64) -> Result<usize> {
+        let due = self.cache.get_due_webhooks(now, BATCH_SIZE).await?;
+        let results: Vec<Result<bool>> = futures::stream::iter(due)
+            .map(|delivery| self.attempt(delivery, now))
+            .buffer_unordered(MAX_CONCURRENT_DELIVERIES)
+            .collect()
+            .await;
+
+        let mut delivered = 0;
+        for result in results {
+            delivered += usize::from(result?);
+        }
+        Ok(delivered)
+    }
+
+    async fn attempt(&self, delivery: WebhookDelivery, now: i64) -> Result<bool> {
+        let error = match self.post(&delivery).await {
+            Ok(()) => {
+                self.cache.complete_webhook(&delivery.id).await?;
+                return Ok(true);
+            }
+            Err(e) => e,
+        };
+
+        let attempts = delivery.attempts + 1;
+        if attempts >= self.max_attempts {
+            tracing::warn!(
+                url = %delivery.url,
+                id = %delivery.id,
+                attempts,
+                "Webhook delivery abandoned: {error}"
+            );
+            self.cache.dead_letter_webhook(&delivery.id, &error).await?;
+        } else {
+            let retry_at = now + retry_delay(attempts).as_secs() as i64;
+            tracing::debug!(url = %delivery.url, id = %delivery.id, attempts, "Webhook delivery failed: {error}");
+            self.cache.retry_webhook(&delivery.id, retry_at, &error).await?;
+        }
+        Ok(false)
+    }
+
+    async fn post(&self, delivery: &WebhookDelivery) -> std::result::Result<(), String> {
+        let mut request = self
+            .client
+            .post(&delivery.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(EVENT_HEADER, &delivery.event);
+        if let Some(secret) = &self.secret {
+            let signature =
+                signature_header(secret, chrono::Utc::now().timestamp(), &delivery.payload);
+            request = request.header(SIGNATURE_HEADER, signature);
+        }
+
+        let response = request
+            .body(delivery.payload.clone())
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(format!("HTTP {}", response.status()))
+        }
+    }
+}
+
+/// `t=<timestamp>,v1=<hex HMAC-SHA256 of "<timestamp>.<body>">`.
+pub fn signature_header(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(format!("{timestamp}.").as_bytes());
+    mac.update(body.as_bytes());
+    format!("t={timestamp},v1={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Delay before the next attempt after `attempts` failures: 5s, 10s, 20s, … capped at 1h.
+fn retry_delay(attempts: u32) -> Duration {
+    let factor = 1u32
+        .checked_shl(attempts.saturating_sub(1))
+        .unwrap_or(u32::MAX);
+    BASE_RETRY_DELAY.saturating_mul(factor).min(MAX_RETRY_DELAY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::Transaction;
+    use crate::storage::database::DatabaseStorage;
+    use crate::testing::mock_db::MockDatabase;
+    use crate::utils::metrics::IndexerMetrics;
+    use axum::http::{HeaderMap, StatusCode};
+    use std::sync::Mutex;
+
+    type Received = Arc<Mutex<Vec<(HeaderMap, String)>>>;
+
+    async fn receiver(status: StatusCode) -> (String, Received) {
+        let received: Received = Arc::default();
+        let sink = received.clone();
+        let app = axum::Router::new().route(
+            "/hook",
+            axum::routing::post(move |headers: HeaderMap, body: String| {
+                let sink = sink.clone();
+                async move {
+                    sink.lock().unwrap().push((headers, body));
+                    status
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        (url, received)
+    }
+
+    fn notifier(db: Arc<MockDatabase>, url: String, max_attempts: u32) -> WebhookNotifier {
+        let cache = Arc::new(MultiCache::new(10, 10, 10, db, IndexerMetrics::new()));
+        let config = WebhookConfig {
+            urls: vec![url],
+            secret: Some("hook-secret".into()),
+            events: vec!["transaction".into()],
+            max_attempts,
+        };
+        WebhookNotifier::new(cache, &config).with_match_accounts(["wallet".to_string()])
+    }
+
+    fn tx(signature: &str, accounts: &[&str]) -> IndexerEvent {
+        IndexerEvent::Transaction(Transaction {
+            signature: signature.into(),
+            slot: 1,
+            block_time: None,
+            fee: 5000,
+            success: true,
+            accounts: accounts.iter().map(|a| a.to_string()).collect(),
+            program: None,
+            instruction_count: None,
+            compute_units: None,
+        })
+    }
+
+    #[test]
+    fn retry_delay_doubles_up_to_cap() {
+        assert_eq!(retry_delay(1), Duration::from_secs(5));
+        assert_eq!(retry_delay(3), Duration::from_secs(20));
+        assert_eq!(retry_delay(40), MAX_RETRY_DELAY);
+    }
+
+    #[tokio::test]
+    async fn delivers_signed_matching_transactions() {
+        let (url, received) = receiver(StatusCode::OK).await;
+        let db = Arc::new(MockDatabase::new());
+        let notifier = notifier(db.clone(), url, 3);
+
+        for event in [tx("unrelated", &["other"]), tx("sig1", &["payer", "wallet"])] {
+            if notifier.wants(&event) {
+                notifier.enqueue(&event).await.unwrap();
+            }
+        }
+        let now = chrono::Utc::now().timestamp();
+        assert_eq!(notifier.deliver_due(now).await.unwrap(), 1);
+        assert!(db.get_due_webhooks(i64::MAX, 10).await.unwrap().is_empty());
+
+        let received = received.lock().unwrap();
+        let (headers, body) = &received[0];
+        let payload: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(payload["type"], "transaction");
+        assert_eq!(payload["data"]["signature"], "sig1");
+        assert_eq!(headers[EVENT_HEADER], "transaction");
+
+        let signature = headers[SIGNATURE_HEADER].to_str().unwrap();
+        let timestamp: i64 = signature
+            .strip_prefix("t=")
+            .and_then(|s| s.split(',').next())
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(signature, signature_header("hook-secret", timestamp, body));
+    }
+
+    #[tokio::test]
+    async fn failed_deliveries_back_off_then_dead_letter() {
+        let (url, received) = receiver(StatusCode::INTERNAL_SERVER_ERROR).await;
+        let db = Arc::new(MockDatabase::new());
+        let notifier = notifier(db.clone(), url, 2);
+        notifier.enqueue(&tx("sig1", &["wallet"])).await.unwrap();
+
+        let now = chrono::Utc::now().timestamp();
+        assert_eq!(notifier.deliver_due(now).await.unwrap(), 0);
+        let queued = db.get_due_webhooks(i64::MAX, 10).await.unwrap();
+        assert_eq!(queued[0].attempts, 1);
+        assert_eq!(queued[0].next_attempt_at, now + 5);
+        assert_eq!(queued[0].last_error.as_deref(), Some("HTTP 500 Internal Server Error"));
+
+        // Not due yet.
+        assert_eq!(notifier.deliver_due(now).await.unwrap(), 0);
+        assert_eq!(received.lock().unwrap().len(), 1);
+
+        notifier.deliver_due(now + 5).await.unwrap();
+        assert!(db.get_due_webhooks(i64::MAX, 10).await.unwrap().is_empty());
+        let dead = db.get_webhook_dead_letters(10).await.unwrap();
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].attempts, 2);
+        assert_eq!(received.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn counts_events_missed_while_lagging() {
+        let db = Arc::new(MockDatabase::new());
+        let notifier = notifier(db.clone(), "http://127.0.0.1:9/hook".into(), 3);
+        let (events, receiver) = broadcast::channel(2);
+        let (_shutdown, shutdown_rx) = broadcast::channel(1);
+        for i in 0..5 {
+            events.send(tx(&format!("sig{i}"), &["wallet"])).unwrap();
+        }
+        drop(events);
+
+        notifier.enqueue_loop(receiver, shutdown_rx).await;
+        let lagged = &notifier.cache.metrics().webhook_events_lagged;
+        assert_eq!(lagged.load(Ordering::Relaxed), 3);
+        assert_eq!(db.get_due_webhooks(i64::MAX, 10).await.unwrap().len(), 2);
+    }
+}
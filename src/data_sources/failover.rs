@@ -0,0 +1,677 @@
+//! Endpoint pools: several RPC or Yellowstone providers behind one source. Requests go to the
+//! healthiest endpoint and fail over to the next one; each endpoint has a circuit breaker, and a
+//! slow `getBlock` can be hedged to a second endpoint.
+
+use crate::core::channels;
+use crate::core::types::{AccountState, Block, Slot, TransactionInfo};
+use crate::data_sources::solana_rpc::SolanaRpc;
+use crate::data_sources::{AccountSource, SlotSource, YellowstoneSource};
+use crate::utils::config::FailoverConfig;
+use crate::utils::errors::{IndexerError, Result};
+use crate::utils::metrics::IndexerMetrics;
+use crate::utils::redact::endpoint_host;
+use async_trait::async_trait;
+use std::future::Future;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// Weight of the newest request in the latency and error-rate averages.
+const SMOOTHING: f64 = 0.2;
+
+/// Score penalty, in milliseconds of latency, of an endpoint whose every request fails.
+const ERROR_PENALTY_MS: f64 = 2000.0;
+
+/// RPC endpoint pooled by `RpcPool` (any source serving both accounts and blocks).
+pub trait RpcEndpoint: AccountSource + SlotSource {}
+
+impl<T: AccountSource + SlotSource> RpcEndpoint for T {}
+
+#[derive(Debug, Default)]
+struct Health {
+    consecutive_failures: u32,
+    /// Set while the circuit is open; once it has passed, requests are trials (half-open).
+    open_until: Option<Instant>,
+    /// Average latency in milliseconds; `None` until the first request completes.
+    latency_ms: Option<f64>,
+    /// Average share of failed requests.
+    error_rate: f64,
+}
+
+impl Health {
+    fn observe_latency(&mut self, elapsed: Duration) {
+        let ms = elapsed.as_secs_f64() * 1000.0;
+        self.latency_ms = Some(match self.latency_ms {
+            Some(avg) => avg + SMOOTHING * (ms - avg),
+            None => ms,
+        });
+    }
+}
+
+struct Endpoint<S: ?Sized> {
+    label: String,
+    source: Arc<S>,
+    health: Mutex<Health>,
+}
+
+impl<S: ?Sized> Endpoint<S> {
+    fn available(&self, now: Instant) -> bool {
+        self.health
+            .lock()
+            .unwrap()
+            .open_until
+            .is_none_or(|until| now >= until)
+    }
+
+    /// Lower is better: average latency plus a penalty for recent errors. Unmeasured endpoints
+    /// score 0 so they get measured.
+    fn score(&self) -> f64 {
+        let health = self.health.lock().unwrap();
+        health.latency_ms.unwrap_or(0.0) + health.error_rate * ERROR_PENALTY_MS
+    }
+
+    fn record_success(&self, elapsed: Duration) {
+        let mut health = self.health.lock().unwrap();
+        health.observe_latency(elapsed);
+        health.error_rate -= SMOOTHING * health.error_rate;
+        health.consecutive_failures = 0;
+        if health.open_until.take().is_some() {
+            tracing::info!(endpoint = %self.label, "Endpoint recovered; circuit closed");
+        }
+    }
+
+    /// A request that lost a hedge race: not a failure, but its latency still counts.
+    fn record_slow(&self, elapsed: Duration) {
+        self.health.lock().unwrap().observe_latency(elapsed);
+    }
+
+    fn record_failure(&self, config: &FailoverConfig) {
+        let mut health = self.health.lock().unwrap();
+        health.error_rate += SMOOTHING * (1.0 - health.error_rate);
+        health.consecutive_failures = health.consecutive_failures.saturating_add(1);
+        if health.consecutive_failures >= config.failure_threshold {
+            if health.open_until.is_none() {
+                tracing::warn!(
+                    endpoint = %self.label,
+                    failures = health.consecutive_failures,
+                    cooldown_secs = config.cooldown_secs,
+                    "Endpoint failing; circuit opened"
+                );
+            }
+            health.open_until = Some(Instant::now() + Duration::from_secs(config.cooldown_secs));
+        }
+    }
+}
+
+struct EndpointPool<S: ?Sized> {
+    endpoints: Vec<Arc<Endpoint<S>>>,
+    config: FailoverConfig,
+    metrics: Arc<IndexerMetrics>,
+}
+
+impl<S: ?Sized> EndpointPool<S> {
+    fn new(
+        endpoints: Vec<(String, Arc<S>)>,
+        config: FailoverConfig,
+        metrics: Arc<IndexerMetrics>,
+    ) -> Self {
+        Self {
+            endpoints: endpoints
+                .into_iter()
+                .map(|(label, source)| {
+                    Arc::new(Endpoint {
+                        label,
+                        source,
+                        health: Mutex::default(),
+                    })
+                })
+                .collect(),
+            config,
+            metrics,
+        }
+    }
+
+    /// Endpoints to try, best first: those whose circuit is not open, by score (ties keep the
+    /// configured order). Empty while every circuit is open, so requests fail fast.
+    fn ranked(&self) -> Vec<Arc<Endpoint<S>>> {
+        let now = Instant::now();
+        let mut available: Vec<(f64, &Arc<Endpoint<S>>)> = self
+            .endpoints
+            .iter()
+            .filter(|endpoint| endpoint.available(now))
+            .map(|endpoint| (endpoint.score(), endpoint))
+            .collect();
+        available.sort_by(|a, b| a.0.total_cmp(&b.0));
+        available
+            .into_iter()
+            .map(|(_, endpoint)| endpoint.clone())
+            .collect()
+    }
+
+    /// Runs `request` on one endpoint, recording the outcome in its health. Only
+    /// [`IndexerError::EndpointUnavailable`] counts as a failure; any other answer (an invalid
+    /// pubkey, a skipped block) shows the endpoint is up.
+    async fn attempt<T, F, Fut>(&self, endpoint: &Endpoint<S>, request: &F) -> Result<T>
+    where
+        F: Fn(Arc<S>) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let started = Instant::now();
+        let result = request(endpoint.source.clone()).await;
+        match &result {
+            Err(e @ IndexerError::EndpointUnavailable(_)) => {
+                tracing::debug!(endpoint = %endpoint.label, "Endpoint request failed: {e}");
+                endpoint.record_failure(&self.config);
+            }
+            _ => endpoint.record_success(started.elapsed()),
+        }
+        result
+    }
+
+    /// Tries `endpoints` in order until one succeeds, failing over only on endpoint failures;
+    /// other errors are returned straight away.
+    async fn first_ok<T, F, Fut>(
+        &self,
+        endpoints: &[Arc<Endpoint<S>>],
+        request: &F,
+    ) -> Result<(T, Arc<Endpoint<S>>)>
+    where
+        F: Fn(Arc<S>) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut last_error = None;
+        for endpoint in endpoints {
+            if last_error.is_some() {
+                self.metrics
+                    .endpoint_failovers
+                    .fetch_add(1, Ordering::Relaxed);
+            }
+            match self.attempt(endpoint, request).await {
+                Ok(value) => return Ok((value, endpoint.clone())),
+                Err(e @ IndexerError::EndpointUnavailable(_)) => last_error = Some(e),
+                Err(e) => return Err(e),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| {
+            if self.endpoints.is_empty() {
+                IndexerError::ConfigError("no endpoints configured".into())
+            } else {
+                IndexerError::EndpointUnavailable("every endpoint's circuit is open".into())
+            }
+        }))
+    }
+
+    async fn call<T, F, Fut>(&self, request: F) -> Result<T>
+    where
+        F: Fn(Arc<S>) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let (value, _) = self.first_ok(&self.ranked(), &request).await?;
+        Ok(value)
+    }
+}
+
+/// Several RPC endpoints behind one `AccountSource` / `SlotSource`.
+pub struct RpcPool {
+    pool: EndpointPool<dyn RpcEndpoint>,
+}
+
+impl RpcPool {
+    pub fn new(
+        endpoints: Vec<(String, Arc<dyn RpcEndpoint>)>,
+        config: FailoverConfig,
+        metrics: Arc<IndexerMetrics>,
+    ) -> Self {
+        Self {
+            pool: EndpointPool::new(endpoints, config, metrics),
+        }
+    }
+
+    /// One `SolanaRpc` per URL, labelled by host.
+    pub fn from_urls(
+        urls: &[String],
+        config: FailoverConfig,
+        metrics: Arc<IndexerMetrics>,
+    ) -> Self {
+        let endpoints = urls
+            .iter()
+            .map(|url| {
+                let rpc = Arc::new(SolanaRpc::new(url, metrics.clone())) as Arc<dyn RpcEndpoint>;
+                (endpoint_host(url), rpc)
+            })
+            .collect();
+        Self::new(endpoints, config, metrics)
+    }
+
+    /// `getBlock` with failover. With `hedge_after_ms` set, a request still pending after that
+    /// long is also sent to the runner-up endpoint, and the first success wins.
+    async fn hedged_get_block(&self, slot: u64) -> Result<Option<Block>> {
+        let request = move |rpc: Arc<dyn RpcEndpoint>| async move { rpc.get_block(slot).await };
+        let ranked = self.pool.ranked();
+        let (Some(hedge_after_ms), [primary, runner_up, ..]) =
+            (self.pool.config.hedge_after_ms, ranked.as_slice())
+        else {
+            return Ok(self.pool.first_ok(&ranked, &request).await?.0);
+        };
+
+        let started = Instant::now();
+        let first = self.pool.attempt(primary, &request);
+        tokio::pin!(first);
+        match tokio::time::timeout(Duration::from_millis(hedge_after_ms), &mut first).await {
+            Ok(Ok(block)) => return Ok(block),
+            Ok(Err(IndexerError::EndpointUnavailable(_))) => {
+                self.pool
+                    .metrics
+                    .endpoint_failovers
+                    .fetch_add(1, Ordering::Relaxed);
+                return Ok(self.pool.first_ok(&ranked[1..], &request).await?.0);
+            }
+            Ok(Err(e)) => return Err(e),
+            Err(_) => {}
+        }
+
+        self.pool
+            .metrics
+            .rpc_hedged_requests
+            .fetch_add(1, Ordering::Relaxed);
+        let second = self.pool.attempt(runner_up, &request);
+        tokio::pin!(second);
+        tokio::select! {
+            result = &mut first => match result {
+                Err(IndexerError::EndpointUnavailable(_)) => second.await,
+                result => result,
+            },
+            result = &mut second => match result {
+                Ok(block) => {
+                    primary.record_slow(started.elapsed());
+                    Ok(block)
+                }
+                Err(IndexerError::EndpointUnavailable(_)) => first.await,
+                Err(e) => Err(e),
+            },
+        }
+    }
+}
+
+#[async_trait]
+impl AccountSource for RpcPool {
+    async fn get_account(&self, address: &str) -> Result<AccountState> {
+        let address = address.to_string();
+        self.pool
+            .call(move |rpc| {
+                let address = address.clone();
+                async move { rpc.get_account(&address).await }
+            })
+            .await
+    }
+
+    async fn get_program_accounts(&self, program: &str) -> Result<Vec<AccountState>> {
+        let program = program.to_string();
+        self.pool
+            .call(move |rpc| {
+                let program = program.clone();
+                async move { rpc.get_program_accounts(&program).await }
+            })
+            .await
+    }
+}
+
+#[async_trait]
+impl SlotSource for RpcPool {
+    async fn get_block_with_transactions(&self, slot: u64) -> Result<Vec<TransactionInfo>> {
+        match self.hedged_get_block(slot).await {
+            Ok(block) => Ok(block.map(|b| b.transactions).unwrap_or_default()),
+            Err(e) => {
+                tracing::warn!("Failed to fetch block {slot} from any endpoint: {e}");
+                Ok(vec![])
+            }
+        }
+    }
+
+    async fn get_block(&self, slot: u64) -> Result<Option<Block>> {
+        self.hedged_get_block(slot).await
+    }
+
+    async fn get_blocks(&self, start: u64, end: u64) -> Result<Vec<u64>> {
+        self.pool
+            .call(move |rpc| async move { rpc.get_blocks(start, end).await })
+            .await
+    }
+
+    async fn get_leader_at_slot(&self, slot: u64) -> Result<String> {
+        self.pool
+            .call(move |rpc| async move { rpc.get_leader_at_slot(slot).await })
+            .await
+    }
+
+    async fn enrich_slot_block_metadata(&self, slot: &mut Slot) -> Result<()> {
+        let current = slot.clone();
+        let enriched = self
+            .pool
+            .call(move |rpc| {
+                let mut slot = current.clone();
+                async move {
+                    rpc.enrich_slot_block_metadata(&mut slot)
+                        .await
+                        .map(|()| slot)
+                }
+            })
+            .await?;
+        slot.block_hash = enriched.block_hash;
+        slot.block_height = enriched.block_height;
+        Ok(())
+    }
+
+    async fn current_slot(&self) -> Result<u64> {
+        self.pool
+            .call(|rpc| async move { rpc.current_slot().await })
+            .await
+    }
+}
+
+/// Several Geyser endpoints behind one `YellowstoneSource`. Each subscribe connects to the
+/// healthiest endpoint; a stream that drops counts against its endpoint, so the slot tracker's
+/// reconnect moves to another one.
+pub struct YellowstonePool {
+    pool: EndpointPool<dyn YellowstoneSource>,
+}
+
+impl YellowstonePool {
+    pub fn new(
+        endpoints: Vec<(String, Arc<dyn YellowstoneSource>)>,
+        config: FailoverConfig,
+        metrics: Arc<IndexerMetrics>,
+    ) -> Self {
+        Self {
+            pool: EndpointPool::new(endpoints, config, metrics),
+        }
+    }
+
+    /// Forwards `upstream`. When it ends while the tracker still listens, the connection
+    /// dropped, which is recorded as a failure of `endpoint`.
+    fn watch(
+        &self,
+        endpoint: Arc<Endpoint<dyn YellowstoneSource>>,
+        mut upstream: mpsc::Receiver<Slot>,
+    ) -> mpsc::Receiver<Slot> {
+        let (tx, rx) = channels::slot_channel();
+        let config = self.pool.config;
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    slot = upstream.recv() => match slot {
+                        Some(slot) => {
+                            if tx.send(slot).await.is_err() {
+                                return;
+                            }
+                        }
+                        None => break,
+                    },
+                    () = tx.closed() => return,
+                }
+            }
+            tracing::warn!(endpoint = %endpoint.label, "Yellowstone stream dropped");
+            endpoint.record_failure(&config);
+        });
+        rx
+    }
+}
+
+#[async_trait]
+impl YellowstoneSource for YellowstonePool {
+    async fn subscribe_with_transactions(
+        &self,
+        from_slot: Option<u64>,
+    ) -> Result<(mpsc::Receiver<Slot>, mpsc::Receiver<TransactionInfo>)> {
+        let request = move |source: Arc<dyn YellowstoneSource>| async move {
+            source.subscribe_with_transactions(from_slot).await
+        };
+        let ((slots, txs), endpoint) = self.pool.first_ok(&self.pool.ranked(), &request).await?;
+        if self.pool.endpoints.len() > 1 {
+            tracing::info!(endpoint = %endpoint.label, "Streaming from Yellowstone endpoint");
+        }
+        Ok((self.watch(endpoint, slots), txs))
+    }
+
+    async fn subscribe_accounts(
+        &self,
+        accounts: &[String],
+        owners: &[String],
+    ) -> Result<mpsc::Receiver<AccountState>> {
+        let (accounts, owners) = (accounts.to_vec(), owners.to_vec());
+        self.pool
+            .call(move |source| {
+                let (accounts, owners) = (accounts.clone(), owners.clone());
+                async move { source.subscribe_accounts(&accounts, &owners).await }
+            })
+            .await
+    }
+
+    async fn health_ping(&self) -> Result<()> {
+        self.pool
+            .call(|source| async move { source.health_ping().await })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::fixtures::sample_slot;
+    use crate::testing::mock_sources::{MockAccountSource, MockSlotSource, MockYellowstoneSource};
+    use std::sync::atomic::{AtomicBool, AtomicU64};
+
+    /// Mock RPC that can be switched to failing, or made slow.
+    struct FlakyRpc {
+        accounts: MockAccountSource,
+        blocks: MockSlotSource,
+        failing: AtomicBool,
+        delay: Duration,
+        calls: AtomicU64,
+    }
+
+    impl FlakyRpc {
+        fn new(delay: Duration) -> Arc<Self> {
+            Arc::new(Self {
+                accounts: MockAccountSource::new(),
+                blocks: MockSlotSource::with_blocks(vec![Block {
+                    slot: sample_slot(7),
+                    transactions: vec![],
+                }]),
+                failing: AtomicBool::new(false),
+                delay,
+                calls: AtomicU64::new(0),
+            })
+        }
+
+        async fn respond(&self) -> Result<()> {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            tokio::time::sleep(self.delay).await;
+            if self.failing.load(Ordering::Relaxed) {
+                return Err(IndexerError::EndpointUnavailable("mock outage".into()));
+            }
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl AccountSource for FlakyRpc {
+        async fn get_account(&self, address: &str) -> Result<AccountState> {
+            self.respond().await?;
+            self.accounts.get_account(address).await
+        }
+
+        async fn get_program_accounts(&self, program: &str) -> Result<Vec<AccountState>> {
+            self.respond().await?;
+            self.accounts.get_program_accounts(program).await
+        }
+    }
+
+    #[async_trait]
+    impl SlotSource for FlakyRpc {
+        async fn get_block_with_transactions(&self, slot: u64) -> Result<Vec<TransactionInfo>> {
+            self.respond().await?;
+            self.blocks.get_block_with_transactions(slot).await
+        }
+
+        async fn get_block(&self, slot: u64) -> Result<Option<Block>> {
+            self.respond().await?;
+            self.blocks.get_block(slot).await
+        }
+
+        async fn get_blocks(&self, start: u64, end: u64) -> Result<Vec<u64>> {
+            self.respond().await?;
+            self.blocks.get_blocks(start, end).await
+        }
+
+        async fn get_leader_at_slot(&self, slot: u64) -> Result<String> {
+            self.respond().await?;
+            self.blocks.get_leader_at_slot(slot).await
+        }
+
+        async fn enrich_slot_block_metadata(&self, slot: &mut Slot) -> Result<()> {
+            self.respond().await?;
+            self.blocks.enrich_slot_block_metadata(slot).await
+        }
+
+        async fn current_slot(&self) -> Result<u64> {
+            self.respond().await?;
+            self.blocks.current_slot().await
+        }
+    }
+
+    fn config(hedge_after_ms: Option<u64>) -> FailoverConfig {
+        FailoverConfig {
+            failure_threshold: 2,
+            cooldown_secs: 60,
+            hedge_after_ms,
+        }
+    }
+
+    #[tokio::test]
+    async fn fails_over_and_opens_the_circuit_of_a_failing_endpoint() {
+        let primary = FlakyRpc::new(Duration::ZERO);
+        let backup = FlakyRpc::new(Duration::ZERO);
+        let metrics = IndexerMetrics::new();
+        let pool = RpcPool::new(
+            vec![
+                ("primary".into(), primary.clone() as Arc<dyn RpcEndpoint>),
+                ("backup".into(), backup.clone() as Arc<dyn RpcEndpoint>),
+            ],
+            config(None),
+            metrics.clone(),
+        );
+
+        primary.failing.store(true, Ordering::Relaxed);
+        for _ in 0..4 {
+            assert_eq!(pool.current_slot().await.unwrap(), 7);
+        }
+        // One failure ranks the primary behind the healthy backup, so it is not retried.
+        assert_eq!(primary.calls.load(Ordering::Relaxed), 1);
+        assert_eq!(backup.calls.load(Ordering::Relaxed), 4);
+        assert_eq!(metrics.endpoint_failovers.load(Ordering::Relaxed), 1);
+
+        backup.failing.store(true, Ordering::Relaxed);
+        assert!(pool.current_slot().await.is_err());
+        assert!(pool.current_slot().await.is_err());
+        // Both circuits are open now, so requests fail without reaching either endpoint.
+        let before = primary.calls.load(Ordering::Relaxed) + backup.calls.load(Ordering::Relaxed);
+        assert!(matches!(
+            pool.current_slot().await,
+            Err(IndexerError::EndpointUnavailable(_))
+        ));
+        let after = primary.calls.load(Ordering::Relaxed) + backup.calls.load(Ordering::Relaxed);
+        assert_eq!(after - before, 0);
+    }
+
+    #[tokio::test]
+    async fn passes_request_errors_through_without_failing_over() {
+        let primary = FlakyRpc::new(Duration::ZERO);
+        let backup = FlakyRpc::new(Duration::ZERO);
+        let metrics = IndexerMetrics::new();
+        let pool = RpcPool::new(
+            vec![
+                ("primary".into(), primary.clone() as Arc<dyn RpcEndpoint>),
+                ("backup".into(), backup.clone() as Arc<dyn RpcEndpoint>),
+            ],
+            config(None),
+            metrics.clone(),
+        );
+
+        for _ in 0..3 {
+            assert!(matches!(
+                pool.get_account("missing").await,
+                Err(IndexerError::RpcError(_))
+            ));
+        }
+        // Each lookup reached one endpoint, which answered, so no circuit moved towards opening.
+        let calls = primary.calls.load(Ordering::Relaxed) + backup.calls.load(Ordering::Relaxed);
+        assert_eq!(calls, 3);
+        assert_eq!(metrics.endpoint_failovers.load(Ordering::Relaxed), 0);
+        for endpoint in &pool.pool.endpoints {
+            let health = endpoint.health.lock().unwrap();
+            assert_eq!(health.consecutive_failures, 0);
+            assert!(health.open_until.is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn hedges_a_slow_get_block_to_the_runner_up() {
+        let slow = FlakyRpc::new(Duration::from_millis(500));
+        let fast = FlakyRpc::new(Duration::ZERO);
+        let metrics = IndexerMetrics::new();
+        let pool = RpcPool::new(
+            vec![
+                ("slow".into(), slow.clone() as Arc<dyn RpcEndpoint>),
+                ("fast".into(), fast.clone() as Arc<dyn RpcEndpoint>),
+            ],
+            config(Some(20)),
+            metrics.clone(),
+        );
+
+        let started = Instant::now();
+        let block = pool.get_block(7).await.unwrap().unwrap();
+        assert_eq!(block.slot.slot, 7);
+        assert!(started.elapsed() < Duration::from_millis(400));
+        assert_eq!(metrics.rpc_hedged_requests.load(Ordering::Relaxed), 1);
+
+        // The slow endpoint's latency was recorded, so the fast one now ranks first.
+        pool.get_block(7).await.unwrap();
+        assert_eq!(fast.calls.load(Ordering::Relaxed), 2);
+        assert_eq!(slow.calls.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn yellowstone_pool_skips_an_unreachable_endpoint_and_fails_over_on_drop() {
+        let down = Arc::new(MockYellowstoneSource::with_connections(vec![None, None]));
+        let dropping = Arc::new(MockYellowstoneSource::with_connections(vec![Some(vec![
+            sample_slot(1),
+        ])]));
+        let pool = YellowstonePool::new(
+            vec![
+                ("down".into(), down.clone() as Arc<dyn YellowstoneSource>),
+                (
+                    "dropping".into(),
+                    dropping.clone() as Arc<dyn YellowstoneSource>,
+                ),
+            ],
+            config(None),
+            IndexerMetrics::new(),
+        );
+
+        let (mut slots, _txs) = pool.subscribe_with_transactions(None).await.unwrap();
+        assert_eq!(slots.recv().await.unwrap().slot, 1);
+        assert!(slots.recv().await.is_none(), "the scripted stream drops");
+
+        // Both endpoints have one failure and `down` ranks first again; its second failure
+        // opens its circuit and `dropping` takes over.
+        let (_slots, _txs) = pool.subscribe_with_transactions(Some(1)).await.unwrap();
+        assert_eq!(down.requests.lock().unwrap().len(), 2);
+        assert_eq!(*dropping.requests.lock().unwrap(), vec![None, Some(1)]);
+
+        // `down`'s circuit is open, so it is not tried again.
+        let _ = pool.subscribe_with_transactions(Some(1)).await.unwrap();
+        assert_eq!(down.requests.lock().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,359 @@
+//! Redundant Yellowstone streams: subscribes to the same filters on several Geyser endpoints at
+//! once and emits each slot status and transaction signature once, from whichever endpoint
+//! delivered it first.
+
+use crate::core::channels;
+use crate::core::dedupe::UpdateDeduper;
+use crate::core::types::{AccountState, Slot, TransactionInfo};
+use crate::data_sources::YellowstoneSource;
+use crate::utils::errors::{IndexerError, Result};
+use crate::utils::metrics::{IndexerMetrics, StreamSourceMetrics};
+use async_trait::async_trait;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// Delay before a dropped endpoint is subscribed again while the others keep streaming.
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(5);
+
+enum Event {
+    Up,
+    Down,
+    Slot(usize, Slot),
+    Transaction(usize, TransactionInfo),
+}
+
+pub struct MergedYellowstone {
+    sources: Vec<(String, Arc<dyn YellowstoneSource>)>,
+    metrics: Arc<IndexerMetrics>,
+    resubscribe_delay: Duration,
+}
+
+impl MergedYellowstone {
+    /// `sources` are labelled by host for logs and per-endpoint metrics; a host configured more
+    /// than once gets `#<index>` appended so each endpoint keeps its own series.
+    pub fn new(
+        sources: Vec<(String, Arc<dyn YellowstoneSource>)>,
+        metrics: Arc<IndexerMetrics>,
+    ) -> Self {
+        let labels: Vec<String> = sources.iter().map(|(label, _)| label.clone()).collect();
+        let sources = sources
+            .into_iter()
+            .enumerate()
+            .map(|(index, (label, source))| {
+                if labels.iter().filter(|other| **other == label).count() > 1 {
+                    (format!("{label}#{index}"), source)
+                } else {
+                    (label, source)
+                }
+            })
+            .collect();
+        Self {
+            sources,
+            metrics,
+            resubscribe_delay: RESUBSCRIBE_DELAY,
+        }
+    }
+
+    /// Keeps one endpoint subscribed, forwarding its updates as events. Starts with the streams
+    /// from the initial subscribe (when it succeeded, already counted live by `merge`) and
+    /// reports each later resubscribe as `Up`. Returns when the merged stream is gone.
+    async fn supervise(
+        source: Arc<dyn YellowstoneSource>,
+        index: usize,
+        label: String,
+        mut streams: Option<(mpsc::Receiver<Slot>, mpsc::Receiver<TransactionInfo>)>,
+        events: mpsc::Sender<Event>,
+        resubscribe_delay: Duration,
+    ) {
+        loop {
+            if let Some((mut slots, mut txs)) = streams.take() {
+                let mut txs_open = true;
+                loop {
+                    let event = tokio::select! {
+                        slot = slots.recv() => match slot {
+                            Some(slot) => Event::Slot(index, slot),
+                            None => break,
+                        },
+                        tx = txs.recv(), if txs_open => match tx {
+                            Some(tx) => Event::Transaction(index, tx),
+                            None => {
+                                txs_open = false;
+                                continue;
+                            }
+                        },
+                        () = events.closed() => return,
+                    };
+                    if events.send(event).await.is_err() {
+                        return;
+                    }
+                }
+                tracing::warn!(endpoint = %label, "Merged Yellowstone stream dropped");
+                if events.send(Event::Down).await.is_err() {
+                    return;
+                }
+            }
+
+            tokio::select! {
+                () = tokio::time::sleep(resubscribe_delay) => {}
+                () = events.closed() => return,
+            }
+            match source.subscribe_with_transactions(None).await {
+                Ok(resubscribed) => {
+                    tracing::info!(endpoint = %label, "Merged Yellowstone stream resubscribed");
+                    if events.send(Event::Up).await.is_err() {
+                        return;
+                    }
+                    streams = Some(resubscribed);
+                }
+                Err(e) => tracing::debug!(endpoint = %label, "Resubscribe failed: {e}"),
+            }
+        }
+    }
+
+    /// Emits the first delivery of each update. `live` endpoints are streaming at the start. Ends,
+    /// closing the merged streams, once every endpoint is down or the tracker stops listening.
+    async fn merge(
+        mut events: mpsc::Receiver<Event>,
+        slot_tx: mpsc::Sender<Slot>,
+        tx_tx: mpsc::Sender<TransactionInfo>,
+        sources: Vec<Arc<StreamSourceMetrics>>,
+        mut live: usize,
+    ) {
+        let mut deduper = UpdateDeduper::new();
+        loop {
+            let event = tokio::select! {
+                event = events.recv() => match event {
+                    Some(event) => event,
+                    None => return,
+                },
+                () = slot_tx.closed() => return,
+            };
+            match event {
+                Event::Up => live += 1,
+                Event::Down => {
+                    live = live.saturating_sub(1);
+                    if live == 0 {
+                        tracing::warn!("Every merged Yellowstone stream dropped");
+                        return;
+                    }
+                }
+                Event::Slot(source, slot) => {
+                    let first = record(&sources[source], deduper.slot_update_seen(&slot));
+                    if first && slot_tx.send(slot).await.is_err() {
+                        return;
+                    }
+                }
+                Event::Transaction(source, tx) => {
+                    let first = record(&sources[source], deduper.transaction_seen(&tx.signature));
+                    if first && tx_tx.send(tx).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Counts a first delivery, or the lag behind it; returns whether this one was first.
+fn record(source: &StreamSourceMetrics, first_seen: Option<Instant>) -> bool {
+    match first_seen {
+        None => {
+            source.first_deliveries.fetch_add(1, Ordering::Relaxed);
+            true
+        }
+        Some(seen) => {
+            source.lag.observe(seen.elapsed());
+            false
+        }
+    }
+}
+
+#[async_trait]
+impl YellowstoneSource for MergedYellowstone {
+    /// Subscribes every endpoint; fails only when none connects. An endpoint that drops is
+    /// resubscribed in the background, and the merged streams close once all are down.
+    async fn subscribe_with_transactions(
+        &self,
+        from_slot: Option<u64>,
+    ) -> Result<(mpsc::Receiver<Slot>, mpsc::Receiver<TransactionInfo>)> {
+        let subscribed = futures::future::join_all(
+            self.sources
+                .iter()
+                .map(|(_, source)| source.subscribe_with_transactions(from_slot)),
+        )
+        .await;
+
+        let mut initial = Vec::with_capacity(subscribed.len());
+        let mut last_error = None;
+        for ((label, _), result) in self.sources.iter().zip(subscribed) {
+            match result {
+                Ok(streams) => initial.push(Some(streams)),
+                Err(e) => {
+                    tracing::warn!(endpoint = %label, "Merged Yellowstone subscribe failed: {e}");
+                    last_error = Some(e);
+                    initial.push(None);
+                }
+            }
+        }
+        let live = initial.iter().filter(|streams| streams.is_some()).count();
+        if live == 0 {
+            return Err(last_error.unwrap_or_else(|| {
+                IndexerError::ConfigError("no gRPC endpoints configured".into())
+            }));
+        }
+
+        let (events_tx, events_rx) = mpsc::channel(channels::SLOT_CHANNEL_CAPACITY);
+        for (index, ((label, source), streams)) in self.sources.iter().zip(initial).enumerate() {
+            tokio::spawn(Self::supervise(
+                source.clone(),
+                index,
+                label.clone(),
+                streams,
+                events_tx.clone(),
+                self.resubscribe_delay,
+            ));
+        }
+
+        let (slot_tx, slot_rx) = channels::slot_channel();
+        let (tx_tx, tx_rx) = channels::transaction_channel();
+        let sources = self
+            .sources
+            .iter()
+            .map(|(label, _)| self.metrics.stream_sources.source(label))
+            .collect();
+        tokio::spawn(Self::merge(events_rx, slot_tx, tx_tx, sources, live));
+        Ok((slot_rx, tx_rx))
+    }
+
+    /// Account updates come from the first endpoint that accepts the subscription.
+    async fn subscribe_accounts(
+        &self,
+        accounts: &[String],
+        owners: &[String],
+    ) -> Result<mpsc::Receiver<AccountState>> {
+        let mut last_error = None;
+        for (label, source) in &self.sources {
+            match source.subscribe_accounts(accounts, owners).await {
+                Ok(updates) => return Ok(updates),
+                Err(e) => {
+                    tracing::warn!(endpoint = %label, "Account subscribe failed: {e}");
+                    last_error = Some(e);
+                }
+            }
+        }
+        Err(last_error
+            .unwrap_or_else(|| IndexerError::ConfigError("no gRPC endpoints configured".into())))
+    }
+
+    /// Healthy while any endpoint answers.
+    async fn health_ping(&self) -> Result<()> {
+        let mut last_error = None;
+        for (_, source) in &self.sources {
+            match source.health_ping().await {
+                Ok(()) => return Ok(()),
+                Err(e) => last_error = Some(e),
+            }
+        }
+        Err(last_error
+            .unwrap_or_else(|| IndexerError::ConfigError("no gRPC endpoints configured".into())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::SlotStatus;
+    use crate::testing::fixtures::sample_slot;
+    use crate::testing::mock_sources::MockYellowstoneSource;
+
+    async fn wait_for(condition: impl Fn() -> bool) {
+        tokio::time::timeout(Duration::from_secs(2), async {
+            while !condition() {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .expect("condition should hold");
+    }
+
+    #[tokio::test]
+    async fn emits_each_update_once_and_survives_one_endpoint_dropping() {
+        let a = Arc::new(MockYellowstoneSource::default());
+        let b = Arc::new(MockYellowstoneSource::default());
+        let metrics = IndexerMetrics::new();
+        let mut merged = MergedYellowstone::new(
+            vec![
+                ("a".into(), a.clone() as Arc<dyn YellowstoneSource>),
+                ("b".into(), b.clone() as Arc<dyn YellowstoneSource>),
+            ],
+            metrics.clone(),
+        );
+        merged.resubscribe_delay = Duration::from_millis(100);
+        let (mut slots, _txs) = merged.subscribe_with_transactions(Some(1)).await.unwrap();
+        let (stats_a, stats_b) = (
+            metrics.stream_sources.source("a"),
+            metrics.stream_sources.source("b"),
+        );
+
+        a.send_slot(sample_slot(1));
+        assert_eq!(slots.recv().await.unwrap().slot, 1);
+        b.send_slot(sample_slot(1));
+        b.send_slot(sample_slot(2));
+        assert_eq!(slots.recv().await.unwrap().slot, 2);
+        a.send_slot(sample_slot(2));
+        let mut finalized = sample_slot(2);
+        finalized.status = SlotStatus::Finalized;
+        a.send_slot(finalized);
+        let next = slots.recv().await.unwrap();
+        assert_eq!((next.slot, next.status), (2, SlotStatus::Finalized));
+        assert_eq!(stats_a.first_deliveries.load(Ordering::Relaxed), 2);
+        assert_eq!(stats_b.first_deliveries.load(Ordering::Relaxed), 1);
+        assert_eq!(stats_b.lag.count(), 1);
+        assert_eq!(stats_a.lag.count(), 1);
+
+        // `a` goes down for maintenance: `b` keeps the merged stream alive while `a` resubscribes.
+        a.disconnect();
+        b.send_slot(sample_slot(3));
+        assert_eq!(slots.recv().await.unwrap().slot, 3);
+        wait_for(|| a.requests.lock().unwrap().len() == 2).await;
+        assert_eq!(*a.requests.lock().unwrap(), vec![Some(1), None]);
+
+        // With every endpoint down, the merged streams close so the tracker reconnects.
+        a.disconnect();
+        b.disconnect();
+        let closed = tokio::time::timeout(Duration::from_secs(1), slots.recv()).await;
+        assert!(matches!(closed, Ok(None)));
+    }
+
+    #[tokio::test]
+    async fn keeps_separate_metrics_for_endpoints_on_the_same_host() {
+        let a = Arc::new(MockYellowstoneSource::default());
+        let b = Arc::new(MockYellowstoneSource::default());
+        let metrics = IndexerMetrics::new();
+        let merged = MergedYellowstone::new(
+            vec![
+                ("grpc.host".into(), a.clone() as Arc<dyn YellowstoneSource>),
+                ("grpc.host".into(), b.clone() as Arc<dyn YellowstoneSource>),
+            ],
+            metrics.clone(),
+        );
+        let (mut slots, _txs) = merged.subscribe_with_transactions(None).await.unwrap();
+
+        b.send_slot(sample_slot(1));
+        assert_eq!(slots.recv().await.unwrap().slot, 1);
+        a.send_slot(sample_slot(1));
+        a.send_slot(sample_slot(2));
+        assert_eq!(slots.recv().await.unwrap().slot, 2);
+        let first = |label| {
+            metrics
+                .stream_sources
+                .source(label)
+                .first_deliveries
+                .load(Ordering::Relaxed)
+        };
+        assert_eq!(first("grpc.host#0"), 1);
+        assert_eq!(first("grpc.host#1"), 1);
+    }
+}
@@ -1,21 +1,31 @@
-use crate::core::types::{AccountState, Slot, TransactionInfo};
+use crate::core::types::{AccountState, Block, Slot, TransactionInfo};
 use crate::utils::errors::Result;
 use async_trait::async_trait;
 use tokio::sync::mpsc;
 
-/// Fetches on-chain account state (implemented by `SolanaRpc`).
+/// Fetches on-chain account state (implemented by `SolanaRpc`, pooled by `failover::RpcPool`).
 #[async_trait]
 pub trait AccountSource: Send + Sync {
     async fn get_account(&self, address: &str) -> Result<AccountState>;
+
+    /// All accounts owned by `program` (`getProgramAccounts`).
+    async fn get_program_accounts(&self, program: &str) -> Result<Vec<AccountState>>;
 }
 
-/// RPC slot streaming and block reads (implemented by `SolanaRpc`).
+/// RPC block reads (implemented by `SolanaRpc`, pooled by `failover::RpcPool`; polled by
+/// `core::block_poller`).
 #[async_trait]
 pub trait SlotSource: Send + Sync {
-    async fn subscribe_slots(&self) -> Result<mpsc::Receiver<Slot>>;
-
     async fn get_block_with_transactions(&self, slot: u64) -> Result<Vec<TransactionInfo>>;
 
+    /// Full block for `slot` as finalized; `None` when the slot was skipped (no block produced).
+    /// Unlike `get_block_with_transactions`, other failures are returned as errors.
+    async fn get_block(&self, slot: u64) -> Result<Option<Block>>;
+
+    /// Finalized slots in `start..=end` that produced a block (`getBlocks`); the rest were
+    /// skipped by their leader.
+    async fn get_blocks(&self, start: u64, end: u64) -> Result<Vec<u64>>;
+
     async fn get_leader_at_slot(&self, slot: u64) -> Result<String>;
 
     /// Fill `block_hash` / `block_height` when missing (e.g. Yellowstone slot updates).
@@ -27,19 +37,26 @@ pub trait SlotSource: Send + Sync {
 /// Real-time slot + transaction streaming via Yellowstone gRPC.
 #[async_trait]
 pub trait YellowstoneSource: Send + Sync {
+    /// Slot and transaction streams. With `from_slot`, the server first replays updates from
+    /// that slot (within its retention); the streams close when the connection drops.
     async fn subscribe_with_transactions(
         &self,
+        from_slot: Option<u64>,
     ) -> Result<(mpsc::Receiver<Slot>, mpsc::Receiver<TransactionInfo>)>;
 
-    /// Real-time account updates via Geyser account filters (production path).
+    /// Real-time account updates via Geyser account filters (production path):
+    /// `accounts` by address, plus every account owned by one of `owners`.
     async fn subscribe_accounts(
         &self,
         accounts: &[String],
+        owners: &[String],
     ) -> Result<mpsc::Receiver<AccountState>>;
 
     /// Lightweight connectivity check for readiness probes (no long-lived stream).
     async fn health_ping(&self) -> Result<()>;
 }
 
+pub mod failover;
+pub mod merged;
 pub mod solana_rpc;
 pub mod yellowstone_grpc;
@@ -1,23 +1,35 @@
-use crate::core::channels;
-use crate::core::types::{AccountState, Slot, SlotStatus, TransactionInfo};
+use crate::core::types::{
+    AccountState, Block, Instruction, Slot, SlotLifecycle, SlotStatus, SolBalanceChange, TokenBalance,
+    TokenBalanceChange, TransactionInfo,
+};
 use crate::data_sources::{AccountSource, SlotSource};
 use crate::utils::errors::{IndexerError, Result};
 use crate::utils::metrics::IndexerMetrics;
+use std::future::Future;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Instant;
 use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+use solana_rpc_client_api::client_error::{Error as ClientError, ErrorKind as ClientErrorKind};
 use solana_rpc_client_api::config::RpcBlockConfig;
+use solana_rpc_client_api::custom_error::{
+    JSON_RPC_SERVER_ERROR_BLOCK_NOT_AVAILABLE, JSON_RPC_SERVER_ERROR_LONG_TERM_STORAGE_SLOT_SKIPPED,
+    JSON_RPC_SERVER_ERROR_SLOT_SKIPPED,
+};
+use solana_rpc_client_api::request::RpcError;
+use solana_message::compiled_instruction::CompiledInstruction;
+use solana_message::AccountKeys;
 use solana_sdk::pubkey::Pubkey;
-use solana_transaction_status::{EncodedTransaction, TransactionDetails, UiInstruction, UiMessage, UiTransactionEncoding};
-use tokio::sync::mpsc;
+use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::parse_instruction;
+use solana_transaction_status::{
+    EncodedTransaction, TransactionDetails, UiCompiledInstruction, UiInnerInstructions,
+    UiInstruction, UiLoadedAddresses, UiMessage, UiTransactionEncoding, UiTransactionTokenBalance,
+};
 use tokio::time::Duration;
 
-/// Default polling interval in milliseconds for RPC slot subscription
-const DEFAULT_POLL_INTERVAL_MS: u64 = 400;
-
 pub struct SolanaRpc {
     client: Arc<RpcClient>,
-    poll_interval: Duration,
     metrics: Arc<IndexerMetrics>,
 }
 
@@ -25,63 +37,35 @@ impl SolanaRpc {
     pub fn new(url: &str, metrics: Arc<IndexerMetrics>) -> Self {
         Self {
             client: Arc::new(RpcClient::new(url.to_string())),
-            poll_interval: Duration::from_millis(DEFAULT_POLL_INTERVAL_MS),
             metrics,
         }
     }
 
+    /// Runs one RPC request, recording its latency.
+    async fn timed<T>(&self, request: impl Future<Output = T>) -> T {
+        let started = Instant::now();
+        let result = request.await;
+        self.metrics.rpc_latency.observe(started.elapsed());
+        result
+    }
+
     pub async fn current_slot(&self) -> Result<u64> {
-        self.client
-            .get_slot()
+        self.timed(self.client.get_slot())
             .await
             .map_err(|e| {
                 self.metrics.rpc_errors.fetch_add(1, Ordering::Relaxed);
-                IndexerError::RpcError(e.to_string())
+                client_error(e)
             })
     }
 
-    pub async fn subscribe_slots(&self) -> Result<mpsc::Receiver<Slot>> {
-        let (tx, rx) = channels::slot_channel();
-        let client = self.client.clone();
-        let poll_interval = self.poll_interval;
-
-        tokio::spawn(async move {
-            let mut last = 0u64;
-            loop {
-                if let Ok(current) = client.get_slot().await {
-                    if current > last {
-                        let (hash, height) = client.get_block(current).await
-                            .map(|b| (Some(b.blockhash.to_string()), b.block_height))
-                            .unwrap_or((None, None));
-
-                        let slot = Slot {
-                            slot: current,
-                            parent: Some(last),
-                            status: SlotStatus::Confirmed,
-                            timestamp: chrono::Utc::now().timestamp(),
-                            block_hash: hash,
-                            block_height: height,
-                        };
-
-                        if tx.send(slot).await.is_err() { break; }
-                        last = current;
-                    }
-                }
-                tokio::time::sleep(poll_interval).await;
-            }
-        });
-
-        Ok(rx)
-    }
-
     async fn fetch_account_state(&self, address: &str) -> Result<AccountState> {
         let pubkey: Pubkey = address.parse()
             .map_err(|e| IndexerError::RpcError(format!("Invalid address: {}", e)))?;
 
-        let account = self.client.get_account(&pubkey).await
-            .map_err(|e| IndexerError::RpcError(format!("RPC error: {}", e)))?;
+        let account = self.timed(self.client.get_account(&pubkey)).await
+            .map_err(client_error)?;
 
-        let slot = match self.client.get_slot().await {
+        let slot = match self.timed(self.client.get_slot()).await {
             Ok(s) => s,
             Err(e) => {
                 tracing::warn!("Could not fetch current slot for account {}: {}", address, e);
@@ -97,28 +81,71 @@ impl SolanaRpc {
             executable: account.executable,
             data: account.data,
             rent_epoch: account.rent_epoch,
+            write_version: 0,
+            decoded: None,
         })
     }
 
+    async fn fetch_program_accounts(&self, program: &str) -> Result<Vec<AccountState>> {
+        let program_id: Pubkey = program.parse()
+            .map_err(|e| IndexerError::RpcError(format!("Invalid program id: {}", e)))?;
+
+        let slot = self.current_slot().await.unwrap_or(0);
+        let accounts = self.timed(self.client.get_program_accounts(&program_id)).await
+            .map_err(|e| {
+                self.metrics.rpc_errors.fetch_add(1, Ordering::Relaxed);
+                client_error(e)
+            })?;
+
+        Ok(accounts
+            .into_iter()
+            .map(|(pubkey, account)| AccountState {
+                address: pubkey.to_string(),
+                slot,
+                lamports: account.lamports,
+                owner: account.owner.to_string(),
+                executable: account.executable,
+                data: account.data,
+                rent_epoch: account.rent_epoch,
+                write_version: 0,
+                decoded: None,
+            })
+            .collect())
+    }
+
     pub async fn get_block_with_transactions(&self, slot: u64) -> Result<Vec<TransactionInfo>> {
+        match self.get_block(slot).await {
+            Ok(Some(block)) => Ok(block.transactions),
+            Ok(None) => {
+                tracing::debug!("Block {} skipped or not available", slot);
+                Ok(vec![])
+            }
+            Err(e) => {
+                tracing::warn!("Failed to fetch block {}: {}", slot, e);
+                Ok(vec![])
+            }
+        }
+    }
+
+    /// `getBlock` with full transactions. Skipped slots and blocks the node does not have yet
+    /// are `Ok(None)`; pruned blocks and failed requests are errors. Blocks are fetched with raw
+    /// (`json`) instructions so every one keeps its account indices and data; the parsed payload
+    /// is added locally for the programs JsonParsed would cover.
+    pub async fn get_block(&self, slot: u64) -> Result<Option<Block>> {
         let config = RpcBlockConfig {
-            encoding: Some(UiTransactionEncoding::JsonParsed),
+            encoding: Some(UiTransactionEncoding::Json),
             transaction_details: Some(TransactionDetails::Full),
             rewards: Some(false),
             commitment: None,
             max_supported_transaction_version: Some(0),
         };
 
-        let block = match self.client.get_block_with_config(slot, config).await {
+        let block = match self.timed(self.client.get_block_with_config(slot, config)).await {
             Ok(b) => b,
+            Err(e) if block_missing(&e) => return Ok(None),
             Err(e) => {
-                let err_str = e.to_string();
-                if err_str.contains("skipped") || err_str.contains("not available") {
-                    tracing::debug!("Block {} skipped or not available", slot);
-                } else {
-                    tracing::warn!("Failed to fetch block {}: {}", slot, e);
-                }
-                return Ok(vec![]);
+                self.metrics.rpc_errors.fetch_add(1, Ordering::Relaxed);
+                return Err(client_error(e));
             }
         };
 
@@ -129,39 +156,66 @@ impl SolanaRpc {
             let EncodedTransaction::Json(ui_tx) = &tx.transaction else { continue };
             let Some(sig) = ui_tx.signatures.first() else { continue };
 
-            let accounts: Vec<String> = match &ui_tx.message {
-                UiMessage::Parsed(p) => p.account_keys.iter().map(|k| k.pubkey.as_str()).map(String::from).collect(),
-                UiMessage::Raw(r) => r.account_keys.clone(),
-            };
-
-            let program = match &ui_tx.message {
-                UiMessage::Parsed(p) => {
-                    p.instructions.first()
-                        .and_then(|ix| match ix {
-                            UiInstruction::Compiled(compiled) => {
-                                accounts.get(compiled.program_id_index as usize).cloned()
-                            }
-                            UiInstruction::Parsed(_parsed) => {
-                                // For parsed instructions, we can't easily extract program_id
-                                // The first account is typically the program
-                                accounts.get(0).cloned()
-                            }
-                        })
-                        .or_else(|| accounts.get(0).cloned())
-                        .unwrap_or_else(|| "Unknown".into())
-                }
-                UiMessage::Raw(r) => {
-                    r.instructions.first()
-                        .and_then(|ix| accounts.get(ix.program_id_index as usize))
-                        .cloned()
-                        .unwrap_or_else(|| "Unknown".into())
+            let UiMessage::Raw(message) = &ui_tx.message else { continue };
+
+            // Static keys first, then address-table lookups, matching instruction account indices.
+            let loaded: Option<&UiLoadedAddresses> = Option::from(meta.loaded_addresses.as_ref());
+            let accounts: Vec<String> = message
+                .account_keys
+                .iter()
+                .chain(loaded.into_iter().flat_map(|l| &l.writable))
+                .chain(loaded.into_iter().flat_map(|l| &l.readonly))
+                .cloned()
+                .collect();
+            let keys: Vec<Pubkey> = accounts.iter().filter_map(|k| k.parse().ok()).collect();
+            // Instructions are only parsed when every key is valid, so indices line up.
+            let keys = (keys.len() == accounts.len()).then_some(keys.as_slice());
+
+            let top_level = &message.instructions;
+            let inner: Vec<UiInnerInstructions> =
+                Option::from(meta.inner_instructions.clone()).unwrap_or_default();
+
+            let mut instruction_details = Vec::new();
+            for (index, ix) in top_level.iter().enumerate() {
+                let index = index as u32;
+                instruction_details.push(to_instruction(ix, &accounts, keys, sig, slot, index, None));
+                for group in inner.iter().filter(|g| u32::from(g.index) == index) {
+                    let compiled = group.instructions.iter().filter_map(|ix| match ix {
+                        UiInstruction::Compiled(c) => Some(c),
+                        UiInstruction::Parsed(_) => None,
+                    });
+                    for (inner_index, ix) in compiled.enumerate() {
+                        instruction_details.push(to_instruction(
+                            ix,
+                            &accounts,
+                            keys,
+                            sig,
+                            slot,
+                            index,
+                            Some(inner_index as u32),
+                        ));
+                    }
                 }
-            };
+            }
 
-            let instruction_count = match &ui_tx.message {
-                UiMessage::Parsed(p) => p.instructions.len(),
-                UiMessage::Raw(r) => r.instructions.len(),
-            };
+            let program = instruction_details
+                .first()
+                .map(|ix| ix.program_id.clone())
+                .unwrap_or_else(|| "Unknown".into());
+            let token_balance_changes = TokenBalanceChange::from_balances(
+                sig,
+                slot,
+                &accounts,
+                &token_balances(&meta.pre_token_balances),
+                &token_balances(&meta.post_token_balances),
+            );
+            let sol_balance_changes = SolBalanceChange::from_balances(
+                sig,
+                slot,
+                &accounts,
+                &meta.pre_balances,
+                &meta.post_balances,
+            );
 
             txs.push(TransactionInfo {
                 signature: sig.clone(),
@@ -169,23 +223,46 @@ impl SolanaRpc {
                 success: meta.err.is_none(),
                 fee: meta.fee,
                 program,
-                instructions: instruction_count,
+                instructions: top_level.len(),
                 compute_units: meta.compute_units_consumed.clone().unwrap_or(0),
                 accounts,
                 timestamp: chrono::Utc::now().timestamp(),
+                block_time: block.block_time,
+                instruction_details,
+                token_balance_changes,
+                sol_balance_changes,
             });
         }
 
-        Ok(txs)
+        Ok(Some(Block {
+            slot: Slot {
+                slot,
+                parent: Some(block.parent_slot),
+                status: SlotStatus::Finalized,
+                timestamp: block.block_time.unwrap_or_else(|| chrono::Utc::now().timestamp()),
+                block_hash: Some(block.blockhash),
+                block_height: block.block_height,
+                lifecycle: SlotLifecycle::default(),
+            },
+            transactions: txs,
+        }))
+    }
+
+    pub async fn get_blocks(&self, start: u64, end: u64) -> Result<Vec<u64>> {
+        self.timed(self.client.get_blocks(start, Some(end)))
+            .await
+            .map_err(|e| {
+                self.metrics.rpc_errors.fetch_add(1, Ordering::Relaxed);
+                client_error(e)
+            })
     }
 
     pub async fn get_leader_at_slot(&self, slot: u64) -> Result<String> {
-        self.client
-            .get_slot_leaders(slot, 1)
+        self.timed(self.client.get_slot_leaders(slot, 1))
             .await
             .map_err(|e| {
                 self.metrics.rpc_errors.fetch_add(1, Ordering::Relaxed);
-                IndexerError::RpcError(e.to_string())
+                client_error(e)
             })?
             .first()
             .map(|l| l.to_string())
@@ -204,7 +281,7 @@ impl SolanaRpc {
                 tokio::time::sleep(RETRY_DELAY).await;
             }
 
-            match self.client.get_block(slot.slot).await {
+            match self.timed(self.client.get_block(slot.slot)).await {
                 Ok(block) => {
                     slot.block_hash = Some(block.blockhash.to_string());
                     slot.block_height = block.block_height;
@@ -241,23 +318,124 @@ impl SolanaRpc {
     }
 }
 
+/// Transport failures, timeouts and HTTP 5xx / 429 responses are the endpoint's fault; anything
+/// else (an RPC error answer, a bad request) is returned as is.
+fn client_error(e: ClientError) -> IndexerError {
+    let endpoint_failed = match e.kind() {
+        ClientErrorKind::Io(_) => true,
+        ClientErrorKind::Reqwest(e) => e.status().is_none_or(|status| {
+            status.is_server_error() || status.as_u16() == 429
+        }),
+        _ => false,
+    };
+    if endpoint_failed {
+        IndexerError::EndpointUnavailable(e.to_string())
+    } else {
+        IndexerError::RpcError(e.to_string())
+    }
+}
+
+/// `getBlock` errors meaning there is no block to return, rather than a failed request.
+fn block_missing(e: &ClientError) -> bool {
+    matches!(
+        e.kind(),
+        ClientErrorKind::RpcError(RpcError::RpcResponseError { code, .. })
+            if matches!(
+                *code,
+                JSON_RPC_SERVER_ERROR_SLOT_SKIPPED
+                    | JSON_RPC_SERVER_ERROR_LONG_TERM_STORAGE_SLOT_SKIPPED
+                    | JSON_RPC_SERVER_ERROR_BLOCK_NOT_AVAILABLE
+            )
+    )
+}
+
+/// Converts a raw instruction, keeping its account indices and data. `parsed` is filled the way
+/// JsonParsed would (System, SPL Token, ATA, Memo, Stake, Vote, …) when `keys` are known.
+fn to_instruction(
+    ix: &UiCompiledInstruction,
+    accounts: &[String],
+    keys: Option<&[Pubkey]>,
+    signature: &str,
+    slot: u64,
+    index: u32,
+    inner_index: Option<u32>,
+) -> Instruction {
+    let parsed = keys.and_then(|keys| {
+        let program_id = keys.get(ix.program_id_index as usize)?;
+        let compiled = CompiledInstruction {
+            program_id_index: ix.program_id_index,
+            accounts: ix.accounts.clone(),
+            data: bs58::decode(&ix.data).into_vec().ok()?,
+        };
+        parse_instruction::parse(
+            program_id,
+            &compiled,
+            &AccountKeys::new(keys, None),
+            ix.stack_height,
+        )
+        .ok()
+        .map(|p| p.parsed)
+    });
+    Instruction {
+        signature: signature.to_string(),
+        slot,
+        index,
+        inner_index,
+        stack_height: ix.stack_height,
+        program_id: accounts
+            .get(ix.program_id_index as usize)
+            .cloned()
+            .unwrap_or_else(|| "Unknown".into()),
+        accounts: ix.accounts.clone(),
+        data: Some(ix.data.clone()),
+        parsed,
+    }
+}
+
+/// Converts the RPC's pre/post token balances, skipping entries whose amount does not parse.
+fn token_balances(balances: &OptionSerializer<Vec<UiTransactionTokenBalance>>) -> Vec<TokenBalance> {
+    let OptionSerializer::Some(balances) = balances else {
+        return vec![];
+    };
+    balances
+        .iter()
+        .filter_map(|b| {
+            Some(TokenBalance {
+                account_index: u32::from(b.account_index),
+                mint: b.mint.clone(),
+                owner: Option::from(b.owner.clone()),
+                amount: b.ui_token_amount.amount.parse().ok()?,
+                decimals: b.ui_token_amount.decimals,
+            })
+        })
+        .collect()
+}
+
 #[async_trait::async_trait]
 impl AccountSource for SolanaRpc {
     async fn get_account(&self, address: &str) -> Result<AccountState> {
         self.fetch_account_state(address).await
     }
+
+    async fn get_program_accounts(&self, program: &str) -> Result<Vec<AccountState>> {
+        self.fetch_program_accounts(program).await
+    }
 }
 
 #[async_trait::async_trait]
 impl SlotSource for SolanaRpc {
-    async fn subscribe_slots(&self) -> Result<mpsc::Receiver<Slot>> {
-        SolanaRpc::subscribe_slots(self).await
-    }
-
     async fn get_block_with_transactions(&self, slot: u64) -> Result<Vec<TransactionInfo>> {
         SolanaRpc::get_block_with_transactions(self, slot).await
     }
 
+    async fn get_block(&self, slot: u64) -> Result<Option<Block>> {
+        SolanaRpc::get_block(self, slot).await
+    }
+
+    async fn get_blocks(&self, start: u64, end: u64) -> Result<Vec<u64>> {
+        SolanaRpc::get_blocks(self, start, end).await
+    }
+
     async fn get_leader_at_slot(&self, slot: u64) -> Result<String> {
         SolanaRpc::get_leader_at_slot(self, slot).await
     }
@@ -270,3 +448,36 @@ impl SlotSource for SolanaRpc {
         SolanaRpc::current_slot(self).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn system_transfer_keeps_indices_data_and_parsed_payload() {
+        let from = Pubkey::new_unique();
+        let to = Pubkey::new_unique();
+        let system = Pubkey::default();
+        let keys = [from, to, system];
+        let accounts: Vec<String> = keys.iter().map(Pubkey::to_string).collect();
+
+        // SystemInstruction::Transfer { lamports: 5_000 }
+        let mut data = 2u32.to_le_bytes().to_vec();
+        data.extend_from_slice(&5_000u64.to_le_bytes());
+        let ix = UiCompiledInstruction {
+            program_id_index: 2,
+            accounts: vec![0, 1],
+            data: bs58::encode(&data).into_string(),
+            stack_height: None,
+        };
+
+        let out = to_instruction(&ix, &accounts, Some(&keys), "sig", 7, 0, None);
+        assert_eq!(out.program_id, "11111111111111111111111111111111");
+        assert_eq!(out.accounts, vec![0, 1]);
+        assert_eq!(out.data.as_deref(), Some(ix.data.as_str()));
+        let parsed = out.parsed.expect("system transfer parses");
+        assert_eq!(parsed["type"], "transfer");
+        assert_eq!(parsed["info"]["lamports"], 5_000);
+        assert_eq!(parsed["info"]["source"], from.to_string());
+    }
+}
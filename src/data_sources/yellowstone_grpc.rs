@@ -1,13 +1,19 @@
-use crate::core::types::{AccountState, Slot, SlotStatus, TransactionInfo};
+use crate::core::types::{
+    AccountState, Instruction, Slot, SlotLifecycle, SlotStatus, SolBalanceChange,
+    TokenBalanceChange, TransactionInfo,
+};
 use crate::utils::errors::{IndexerError, Result};
 use futures::{SinkExt, StreamExt};
 use std::collections::HashMap;
 use std::sync::Once;
 use std::time::Duration;
 use tokio::sync::mpsc;
-use yellowstone_grpc_client::{ClientTlsConfig, GeyserGrpcBuilder};
+use yellowstone_grpc_client::{
+    ClientTlsConfig, GeyserGrpcBuilder, GeyserGrpcBuilderError, GeyserGrpcClientError,
+};
 use yellowstone_grpc_proto::prelude::*;
 use yellowstone_grpc_proto::prelude::subscribe_update::UpdateOneof;
+use yellowstone_grpc_proto::tonic::Code;
 
 static RUSTLS_PROVIDER: Once = Once::new();
 
@@ -19,6 +25,51 @@ fn ensure_rustls_provider() {
     });
 }
 
+/// A connect that failed in transport is the endpoint's fault; a bad token is configuration.
+fn connect_error(e: GeyserGrpcBuilderError) -> IndexerError {
+    match e {
+        GeyserGrpcBuilderError::TonicError(e) => IndexerError::EndpointUnavailable(e.to_string()),
+        e => IndexerError::ConfigError(e.to_string()),
+    }
+}
+
+/// gRPC statuses standing in for transport failures, timeouts, 5xx and 429 count against the
+/// endpoint; others (e.g. a rejected filter) are returned as is.
+fn client_error(e: GeyserGrpcClientError) -> IndexerError {
+    let endpoint_failed = match &e {
+        GeyserGrpcClientError::TonicStatus(status) => matches!(
+            status.code(),
+            Code::Unavailable
+                | Code::DeadlineExceeded
+                | Code::ResourceExhausted
+                | Code::Internal
+                | Code::Unknown
+        ),
+        GeyserGrpcClientError::SubscribeSendError(_) => true,
+    };
+    if endpoint_failed {
+        IndexerError::EndpointUnavailable(e.to_string())
+    } else {
+        IndexerError::RpcError(e.to_string())
+    }
+}
+
+fn token_balances(balances: &[TokenBalance]) -> Vec<crate::core::types::TokenBalance> {
+    balances
+        .iter()
+        .filter_map(|b| {
+            let amount = b.ui_token_amount.as_ref()?;
+            Some(crate::core::types::TokenBalance {
+                account_index: b.account_index,
+                mint: b.mint.clone(),
+                owner: Some(b.owner.clone()).filter(|o| !o.is_empty()),
+                amount: amount.amount.parse().ok()?,
+                decimals: u8::try_from(amount.decimals).ok()?,
+            })
+        })
+        .collect()
+}
+
 fn normalize_grpc_url(url: &str) -> String {
     let url = url.trim();
     if url.starts_with("http://") || url.starts_with("https://") {
@@ -32,6 +83,7 @@ pub struct YellowstoneGrpc {
     url: String,
     token: Option<String>,
     tx_accounts: Vec<String>,
+    programs: Vec<String>,
 }
 
 impl YellowstoneGrpc {
@@ -40,9 +92,16 @@ impl YellowstoneGrpc {
             url: url.to_string(),
             token,
             tx_accounts,
+            programs: vec![],
         }
     }
 
+    /// Also stream every transaction that invokes (or references) one of `programs`.
+    pub fn with_programs(mut self, programs: Vec<String>) -> Self {
+        self.programs = programs;
+        self
+    }
+
     fn build_client(&self) -> Result<GeyserGrpcBuilder> {
         let url = normalize_grpc_url(&self.url);
         let mut builder = GeyserGrpcBuilder::from_shared(url.clone())
@@ -71,58 +130,88 @@ impl YellowstoneGrpc {
         m.insert(
             "slots".into(),
             SubscribeRequestFilterSlots {
-                filter_by_commitment: Some(true),
-                interslot_updates: Some(false),
+                // Receive every commitment transition so forks can be resolved on finalization.
+                filter_by_commitment: Some(false),
+                // Needed for SLOT_DEAD; the other interslot statuses are dropped in `parse_slot`.
+                interslot_updates: Some(true),
             },
         );
         m
     }
 
-    fn tx_filter(accounts: &[String]) -> HashMap<String, SubscribeRequestFilterTransactions> {
-        if accounts.is_empty() {
-            return HashMap::new();
-        }
-
+    /// Named filters are OR-ed by Geyser; an empty map disables the tx stream.
+    fn tx_filter(
+        accounts: &[String],
+        programs: &[String],
+    ) -> HashMap<String, SubscribeRequestFilterTransactions> {
         let mut m = HashMap::new();
-        m.insert(
-            "txs".into(),
-            SubscribeRequestFilterTransactions {
-                vote: Some(false),
-                account_include: accounts.to_vec(),
-                ..Default::default()
-            },
-        );
+        for (name, include) in [("txs", accounts), ("programs", programs)] {
+            if include.is_empty() {
+                continue;
+            }
+            m.insert(
+                name.into(),
+                SubscribeRequestFilterTransactions {
+                    vote: Some(false),
+                    account_include: include.to_vec(),
+                    ..Default::default()
+                },
+            );
+        }
         m
     }
 
-    fn account_filter(accounts: &[String]) -> HashMap<String, SubscribeRequestFilterAccounts> {
+    /// Watches `accounts` by address plus every account owned by `owners`.
+    /// Each side gets its own filter so an empty list never widens to "all accounts".
+    fn account_filter(
+        accounts: &[String],
+        owners: &[String],
+    ) -> HashMap<String, SubscribeRequestFilterAccounts> {
         let mut m = HashMap::new();
-        m.insert(
-            "accounts".into(),
-            SubscribeRequestFilterAccounts {
-                account: accounts.to_vec(),
-                owner: vec![],
-                filters: vec![],
-                nonempty_txn_signature: None,
-            },
-        );
+        if !accounts.is_empty() {
+            m.insert(
+                "accounts".into(),
+                SubscribeRequestFilterAccounts {
+                    account: accounts.to_vec(),
+                    owner: vec![],
+                    filters: vec![],
+                    nonempty_txn_signature: None,
+                },
+            );
+        }
+        if !owners.is_empty() {
+            m.insert(
+                "program_accounts".into(),
+                SubscribeRequestFilterAccounts {
+                    account: vec![],
+                    owner: owners.to_vec(),
+                    filters: vec![],
+                    nonempty_txn_signature: None,
+                },
+            );
+        }
         m
     }
 
-    fn parse_slot(s: &SubscribeUpdateSlot) -> Slot {
-        let parent = s.parent.filter(|&p| p > 0);
-        Slot {
+    /// `None` for the interslot progress updates (first shred, completed, bank created).
+    fn parse_slot(s: &SubscribeUpdateSlot) -> Option<Slot> {
+        let status = match s.status {
+            0 => SlotStatus::Processed,
+            1 => SlotStatus::Confirmed,
+            2 => SlotStatus::Finalized,
+            // SLOT_DEAD: the validator abandoned this fork.
+            6 => SlotStatus::Dead,
+            _ => return None,
+        };
+        Some(Slot {
             slot: s.slot,
-            parent,
-            status: match s.status {
-                0 => SlotStatus::Processed,
-                1 => SlotStatus::Confirmed,
-                _ => SlotStatus::Finalized,
-            },
+            parent: s.parent.filter(|&p| p > 0),
+            status,
             timestamp: chrono::Utc::now().timestamp(),
             block_hash: None,
             block_height: None,
-        }
+            lifecycle: SlotLifecycle::default(),
+        })
     }
 
     fn parse_tx(t: &SubscribeUpdateTransaction) -> Option<TransactionInfo> {
@@ -130,21 +219,84 @@ impl YellowstoneGrpc {
         let meta = tx.meta.as_ref();
         let msg = tx.transaction.as_ref()?.message.as_ref()?;
 
+        // Static keys first, then address-table lookups, matching instruction account indices.
         let accounts: Vec<String> = msg
             .account_keys
             .iter()
+            .chain(meta.into_iter().flat_map(|m| &m.loaded_writable_addresses))
+            .chain(meta.into_iter().flat_map(|m| &m.loaded_readonly_addresses))
             .map(|k| bs58::encode(k).into_string())
             .collect();
 
-        let program = msg
-            .instructions
+        let signature = bs58::encode(&tx.signature).into_string();
+        let program_of = |index: u32| {
+            accounts
+                .get(index as usize)
+                .cloned()
+                .unwrap_or_else(|| "Unknown".into())
+        };
+
+        let mut instruction_details = Vec::new();
+        for (index, ix) in msg.instructions.iter().enumerate() {
+            let index = index as u32;
+            instruction_details.push(Instruction {
+                signature: signature.clone(),
+                slot: t.slot,
+                index,
+                inner_index: None,
+                stack_height: None,
+                program_id: program_of(ix.program_id_index),
+                accounts: ix.accounts.clone(),
+                data: Some(bs58::encode(&ix.data).into_string()),
+                parsed: None,
+            });
+            let groups = meta
+                .into_iter()
+                .flat_map(|m| &m.inner_instructions)
+                .filter(|g| g.index == index);
+            for group in groups {
+                for (inner_index, inner) in group.instructions.iter().enumerate() {
+                    instruction_details.push(Instruction {
+                        signature: signature.clone(),
+                        slot: t.slot,
+                        index,
+                        inner_index: Some(inner_index as u32),
+                        stack_height: inner.stack_height,
+                        program_id: program_of(inner.program_id_index),
+                        accounts: inner.accounts.clone(),
+                        data: Some(bs58::encode(&inner.data).into_string()),
+                        parsed: None,
+                    });
+                }
+            }
+        }
+
+        let program = instruction_details
             .first()
-            .and_then(|ix| accounts.get(ix.program_id_index as usize))
-            .cloned()
+            .map(|ix| ix.program_id.clone())
             .unwrap_or_else(|| "Unknown".into());
+        let (token_balance_changes, sol_balance_changes) = match meta {
+            Some(m) => (
+                TokenBalanceChange::from_balances(
+                    &signature,
+                    t.slot,
+                    &accounts,
+                    &token_balances(&m.pre_token_balances),
+                    &token_balances(&m.post_token_balances),
+                ),
+                SolBalanceChange::from_balances(
+                    &signature,
+                    t.slot,
+                    &accounts,
+                    &m.pre_balances,
+                    &m.post_balances,
+                ),
+            ),
+            None => (vec![], vec![]),
+        };
 
         Some(TransactionInfo {
-            signature: bs58::encode(&tx.signature).into_string(),
+            signature,
             slot: t.slot,
             success: meta.map(|m| m.err.is_none()).unwrap_or(false),
             fee: meta.map(|m| m.fee).unwrap_or(0),
@@ -153,6 +305,10 @@ impl YellowstoneGrpc {
             compute_units: meta.and_then(|m| m.compute_units_consumed).unwrap_or(0),
             accounts,
             timestamp: chrono::Utc::now().timestamp(),
+            block_time: None,
+            instruction_details,
+            token_balance_changes,
+            sol_balance_changes,
         })
     }
 
@@ -166,11 +322,14 @@ impl YellowstoneGrpc {
             executable: info.executable,
             data: info.data.clone(),
             rent_epoch: info.rent_epoch,
+            write_version: info.write_version,
+            decoded: None,
         })
     }
 
     pub async fn subscribe_with_transactions(
         &self,
+        from_slot: Option<u64>,
     ) -> Result<(mpsc::Receiver<Slot>, mpsc::Receiver<TransactionInfo>)> {
         let (slot_tx, slot_rx) = crate::core::channels::slot_channel();
         let (tx_tx, tx_rx) = crate::core::channels::transaction_channel();
@@ -179,50 +338,52 @@ impl YellowstoneGrpc {
             .build_client()?
             .connect()
             .await
-            .map_err(|e| IndexerError::ConfigError(e.to_string()))?;
+            .map_err(connect_error)?;
 
-        let (mut sink, mut stream) = client
-            .subscribe()
-            .await
-            .map_err(|e| IndexerError::RpcError(e.to_string()))?;
+        let (mut sink, mut stream) = client.subscribe().await.map_err(client_error)?;
 
-        let tx_filter = Self::tx_filter(&self.tx_accounts);
+        let tx_filter = Self::tx_filter(&self.tx_accounts, &self.programs);
         if tx_filter.is_empty() {
-            tracing::debug!("Yellowstone tx stream disabled (set YELLOWSTONE_TX_ACCOUNTS to enable)");
+            tracing::debug!(
+                "Yellowstone tx stream disabled (set YELLOWSTONE_TX_ACCOUNTS or INDEX_PROGRAMS to enable)"
+            );
         }
 
         let request = SubscribeRequest {
             slots: Self::slot_filter(),
             transactions: tx_filter,
             commitment: Some(CommitmentLevel::Confirmed as i32),
+            from_slot,
             ..Default::default()
         };
 
         sink.send(request)
             .await
-            .map_err(|e| IndexerError::RpcError(e.to_string()))?;
+            .map_err(|e| IndexerError::EndpointUnavailable(e.to_string()))?;
 
         tokio::spawn(async move {
             while let Some(msg) = stream.next().await {
-                let Ok(msg) = msg else {
-                    tracing::error!("Stream error");
-                    break;
+                let msg = match msg {
+                    Ok(msg) => msg,
+                    Err(status) => {
+                        tracing::warn!("Yellowstone stream error: {status}");
+                        break;
+                    }
                 };
 
-                match msg.update_oneof {
-                    Some(UpdateOneof::Slot(s)) => {
-                        if slot_tx.send(Self::parse_slot(&s)).await.is_err() {
-                            break;
-                        }
-                    }
-                    Some(UpdateOneof::Transaction(t)) => {
-                        if let Some(info) = Self::parse_tx(&t) {
-                            if tx_tx.send(info).await.is_err() {
-                                break;
-                            }
-                        }
-                    }
-                    _ => {}
+                let delivered = match msg.update_oneof {
+                    Some(UpdateOneof::Slot(s)) => match Self::parse_slot(&s) {
+                        Some(slot) => slot_tx.send(slot).await.is_ok(),
+                        None => true,
+                    },
+                    Some(UpdateOneof::Transaction(t)) => match Self::parse_tx(&t) {
+                        Some(info) => tx_tx.send(info).await.is_ok(),
+                        None => true,
+                    },
+                    _ => true,
+                };
+                if !delivered {
+                    break;
                 }
             }
         });
@@ -233,10 +394,11 @@ impl YellowstoneGrpc {
     pub async fn subscribe_accounts(
         &self,
         accounts: &[String],
+        owners: &[String],
     ) -> Result<mpsc::Receiver<AccountState>> {
-        if accounts.is_empty() {
+        if accounts.is_empty() && owners.is_empty() {
             return Err(IndexerError::ConfigError(
-                "account subscription requires at least one address".into(),
+                "account subscription requires at least one address or owner".into(),
             ));
         }
 
@@ -245,22 +407,19 @@ impl YellowstoneGrpc {
             .build_client()?
             .connect()
             .await
-            .map_err(|e| IndexerError::ConfigError(e.to_string()))?;
+            .map_err(connect_error)?;
 
-        let (mut sink, mut stream) = client
-            .subscribe()
-            .await
-            .map_err(|e| IndexerError::RpcError(e.to_string()))?;
+        let (mut sink, mut stream) = client.subscribe().await.map_err(client_error)?;
 
         let request = SubscribeRequest {
-            accounts: Self::account_filter(accounts),
+            accounts: Self::account_filter(accounts, owners),
             commitment: Some(CommitmentLevel::Confirmed as i32),
             ..Default::default()
         };
 
         sink.send(request)
             .await
-            .map_err(|e| IndexerError::RpcError(e.to_string()))?;
+            .map_err(|e| IndexerError::EndpointUnavailable(e.to_string()))?;
 
         tokio::spawn(async move {
             while let Some(msg) = stream.next().await {
@@ -286,7 +445,7 @@ impl YellowstoneGrpc {
         self.build_client()?
             .connect()
             .await
-            .map_err(|e| IndexerError::ConfigError(e.to_string()))?;
+            .map_err(connect_error)?;
         Ok(())
     }
 }
@@ -295,18 +454,70 @@ impl YellowstoneGrpc {
 impl crate::data_sources::YellowstoneSource for YellowstoneGrpc {
     async fn subscribe_with_transactions(
         &self,
+        from_slot: Option<u64>,
     ) -> Result<(mpsc::Receiver<Slot>, mpsc::Receiver<TransactionInfo>)> {
-        YellowstoneGrpc::subscribe_with_transactions(self).await
+        YellowstoneGrpc::subscribe_with_transactions(self, from_slot).await
     }
 
     async fn subscribe_accounts(
         &self,
         accounts: &[String],
+        owners: &[String],
     ) -> Result<mpsc::Receiver<AccountState>> {
-        YellowstoneGrpc::subscribe_accounts(self, accounts).await
+        YellowstoneGrpc::subscribe_accounts(self, accounts, owners).await
     }
 
     async fn health_ping(&self) -> Result<()> {
         YellowstoneGrpc::health_ping(self).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filters_skip_empty_lists() {
+        assert!(YellowstoneGrpc::tx_filter(&[], &[]).is_empty());
+        assert!(YellowstoneGrpc::account_filter(&[], &[]).is_empty());
+
+        let programs = vec!["Prog1111111111111111111111111111111111111111".to_string()];
+        let txs = YellowstoneGrpc::tx_filter(&[], &programs);
+        assert_eq!(txs.len(), 1);
+        assert_eq!(txs["programs"].account_include, programs);
+
+        let accounts = YellowstoneGrpc::account_filter(&[], &programs);
+        assert_eq!(accounts.len(), 1);
+        assert!(accounts["program_accounts"].account.is_empty());
+        assert_eq!(accounts["program_accounts"].owner, programs);
+    }
+
+    #[test]
+    fn slot_updates_keep_commitments_and_dead_only() {
+        let update = |status| SubscribeUpdateSlot {
+            slot: 50,
+            parent: Some(49),
+            status,
+            dead_error: None,
+        };
+        let statuses: Vec<_> = (0..=6)
+            .map(|s| YellowstoneGrpc::parse_slot(&update(s)).map(|slot| slot.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                Some(SlotStatus::Processed),
+                Some(SlotStatus::Confirmed),
+                Some(SlotStatus::Finalized),
+                None,
+                None,
+                None,
+                Some(SlotStatus::Dead),
+            ]
+        );
+        assert_eq!(
+            YellowstoneGrpc::slot_filter()["slots"].interslot_updates,
+            Some(true)
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use crate::core::types::Slot;
+use crate::core::types::{Slot, SlotStatus};
 use std::collections::BTreeMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -32,6 +32,12 @@ impl L1HotSlots {
         cache.insert(slot.slot, slot);
     }
 
+    pub async fn mark_dead(&self, slot: u64) {
+        if let Some(cached) = self.cache.write().await.get_mut(&slot) {
+            cached.status = SlotStatus::Dead;
+        }
+    }
+
     pub async fn get_latest_slot(&self) -> Option<Slot> {
         let cache = self.cache.read().await;
         cache.values().max_by_key(|slot| slot.slot).cloned()
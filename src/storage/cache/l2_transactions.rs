@@ -12,6 +12,7 @@ impl L2Transactions {
         let cache = Cache::builder()
             .max_capacity(max_size as u64)
             .time_to_live(Duration::from_secs(3600)) 
+            .support_invalidation_closures()
             .build();
         
         Self {
@@ -26,4 +27,11 @@ impl L2Transactions {
     pub async fn insert(&self, tx: Transaction) {
         self.cache.insert(tx.signature.clone(), tx).await;
     }
+
+    /// Drops every cached transaction belonging to `slot` (fork rollback).
+    pub fn invalidate_slot(&self, slot: u64) {
+        if let Err(e) = self.cache.invalidate_entries_if(move |_, tx| tx.slot == slot) {
+            tracing::debug!("L2 invalidation for slot {slot} failed: {e}");
+        }
+    }
 }
\ No newline at end of file
@@ -1,12 +1,15 @@
+use crate::core::events::AccountChange;
 use crate::core::types::AccountState;
 use crate::storage::database::DatabaseStorage;
 use crate::utils::errors::Result;
 use crate::utils::metrics::IndexerMetrics;
 use moka::future::Cache;
+use std::collections::HashMap;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
 /// L3: in-memory account cache with database persistence and DB fallback on miss.
+/// Every state change is also appended to the account history.
 pub struct L3Accounts {
     memory: Arc<Cache<String, AccountState>>,
     db: Arc<dyn DatabaseStorage>,
@@ -42,10 +45,53 @@ impl L3Accounts {
         Ok(None)
     }
 
-    pub async fn store(&self, account: AccountState) -> Result<()> {
+    /// The known accounts among `addresses`, in the order given (unknown ones are skipped).
+    /// Memory misses are read from the database in a single query.
+    pub async fn get_many(&self, addresses: &[String]) -> Result<Vec<AccountState>> {
+        let mut found = HashMap::new();
+        let mut missing = Vec::new();
+        for address in addresses {
+            match self.memory.get(address).await {
+                Some(account) => {
+                    found.insert(address.clone(), account);
+                }
+                None => missing.push(address.clone()),
+            }
+        }
+        for account in self.db.get_accounts(&missing).await? {
+            self.memory
+                .insert(account.address.clone(), account.clone())
+                .await;
+            found.insert(account.address.clone(), account);
+        }
+
+        let hits = found.len() as u64;
+        self.metrics.l3_hits.fetch_add(hits, Ordering::Relaxed);
+        self.metrics
+            .l3_misses
+            .fetch_add(addresses.len() as u64 - hits, Ordering::Relaxed);
+        Ok(addresses.iter().filter_map(|a| found.remove(a)).collect())
+    }
+
+    /// Stores `account`; returns the change when its contents differ from the previous version.
+    pub async fn store(&self, account: AccountState) -> Result<Option<AccountChange>> {
+        // Polling re-reads unchanged accounts every few seconds; only record actual changes.
+        let previous = match self.memory.get(&account.address).await {
+            Some(previous) => Some(previous),
+            None => self.db.get_account(&account.address).await?,
+        };
+        let changed = previous.as_ref().is_none_or(|p| !p.same_state(&account));
+
         self.memory
             .insert(account.address.clone(), account.clone())
             .await;
-        self.db.store_account(account).await
+        if changed {
+            self.db.append_account_history(&account).await?;
+        }
+        self.db.store_account(account.clone()).await?;
+        Ok(changed.then_some(AccountChange {
+            previous,
+            current: account,
+        }))
     }
 }
@@ -1,12 +1,22 @@
-use crate::core::types::{AccountState, Slot, Transaction};
+use crate::core::decoders::{builtin, DecodeContext, DecoderRegistry};
+use crate::core::events::{IndexerEvent, EVENT_CHANNEL_CAPACITY};
+use crate::core::types::{
+    AccountState, Block, DecodedAccount, Instruction, Slot, SlotStatus, SolBalanceChange,
+    TokenBalanceChange, Transaction, WebhookDelivery,
+};
 use crate::storage::cache::l1_hot_slots::L1HotSlots;
 use crate::storage::cache::l2_transactions::L2Transactions;
 use crate::storage::cache::l3_accounts::L3Accounts;
-use crate::storage::database::DatabaseStorage;
+use crate::storage::database::{
+    AccountHistoryRange, DatabaseStorage, Page, SlotRange, TokenChangeCursor, TxCursor,
+};
 use crate::utils::errors::Result;
 use crate::utils::metrics::IndexerMetrics;
+use std::future::Future;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::broadcast;
 
 pub struct MultiCache {
     l1: Arc<L1HotSlots>,
@@ -14,6 +24,8 @@ pub struct MultiCache {
     l3: Arc<L3Accounts>,
     db: Arc<dyn DatabaseStorage>,
     metrics: Arc<IndexerMetrics>,
+    decoders: Arc<DecoderRegistry>,
+    events: broadcast::Sender<IndexerEvent>,
 }
 
 impl MultiCache {
@@ -30,20 +42,93 @@ impl MultiCache {
             l3: Arc::new(L3Accounts::new(db.clone(), l3_size, metrics.clone())),
             db,
             metrics,
+            decoders: Arc::new(DecoderRegistry::with_builtin()),
+            events: broadcast::channel(EVENT_CHANNEL_CAPACITY).0,
         }
     }
 
-    pub async fn store_slot(&self, slot: Slot) -> Result<()> {
+    /// Stream of slots, transactions and account changes as they are stored.
+    pub fn subscribe(&self) -> broadcast::Receiver<IndexerEvent> {
+        self.events.subscribe()
+    }
+
+    fn publish(&self, event: IndexerEvent) {
+        // No subscribers is fine; events are best-effort notifications.
+        let _ = self.events.send(event);
+    }
+
+    pub fn metrics(&self) -> &Arc<IndexerMetrics> {
+        &self.metrics
+    }
+
+    /// Runs a database write, recording its latency.
+    async fn timed_write<T>(&self, write: impl Future<Output = Result<T>>) -> Result<T> {
+        let started = Instant::now();
+        let result = write.await;
+        self.metrics.db_write_latency.observe(started.elapsed());
+        result
+    }
+
+    /// Account decoders applied on `store_account`; register custom program decoders here.
+    pub fn decoders(&self) -> &Arc<DecoderRegistry> {
+        &self.decoders
+    }
+
+    /// Decodes `account` with its owner's decoder, resolving SPL mint decimals from L3/DB.
+    pub async fn decode_account(&self, account: &AccountState) -> Option<DecodedAccount> {
+        let mut ctx = DecodeContext::default();
+        if let Some(mint) = builtin::token_account_mint(account) {
+            if let Ok(Some(mint)) = self.l3.get(&mint).await {
+                ctx.mint_decimals = builtin::mint_decimals(&mint);
+            }
+        }
+        self.decoders.decode(account, &ctx)
+    }
+
+    /// Fills `parsed` on raw instructions that have a registered decoder for their program.
+    pub fn decode_instructions(&self, instructions: &mut [Instruction], tx_accounts: &[String]) {
+        for ix in instructions.iter_mut().filter(|ix| ix.parsed.is_none()) {
+            ix.parsed = self.decoders.decode_instruction(ix, tx_accounts);
+        }
+    }
+
+    pub async fn store_slot(&self, mut slot: Slot) -> Result<()> {
+        slot.lifecycle.record(&slot.status, slot.timestamp);
+        if let Some(existing) = self.l1.get(slot.slot).await {
+            slot.lifecycle.merge_earlier(&existing.lifecycle);
+            // A late metadata backfill must not revive a rolled-back slot.
+            if matches!(existing.status, SlotStatus::Dead) {
+                slot.status = SlotStatus::Dead;
+            }
+        }
         self.l1.insert(slot.clone()).await;
         self.metrics.slots_ingested.fetch_add(1, Ordering::Relaxed);
-        self.db.store_slot(&slot).await?;
-        self.db.set_checkpoint(slot.slot).await
+        self.timed_write(async {
+            self.db.store_slot(&slot).await?;
+            self.db.set_checkpoint(slot.slot).await
+        })
+        .await?;
+        self.publish(IndexerEvent::Slot(slot));
+        Ok(())
+    }
+
+    /// Marks an orphaned slot `Dead` and drops its transactions from L2 and the database.
+    pub async fn rollback_slot(&self, slot: u64) -> Result<u64> {
+        self.l1.mark_dead(slot).await;
+        self.l2.invalidate_slot(slot);
+        self.metrics.slots_orphaned.fetch_add(1, Ordering::Relaxed);
+        self.db.rollback_slot(slot).await
     }
 
     pub async fn get_checkpoint(&self) -> Result<Option<u64>> {
         self.db.get_checkpoint().await
     }
 
+    /// Checkpoint slot and when it was last written (shared across processes via the database).
+    pub async fn get_checkpoint_status(&self) -> Result<Option<(u64, i64)>> {
+        self.db.get_checkpoint_status().await
+    }
+
     /// Lightweight DB probe for readiness checks.
     pub async fn ping_db(&self) -> Result<()> {
         self.db.get_checkpoint().await?;
@@ -53,15 +138,136 @@ impl MultiCache {
     pub async fn store_transaction(&self, tx: Transaction) -> Result<()> {
         self.l2.insert(tx.clone()).await;
         self.metrics.txs_ingested.fetch_add(1, Ordering::Relaxed);
-        self.db.store_transaction(tx).await
+        self.timed_write(self.db.store_transaction(tx.clone()))
+            .await?;
+        self.publish(IndexerEvent::Transaction(tx));
+        Ok(())
+    }
+
+    /// Writes historical blocks straight to the database in one batch. Skips the caches, the live
+    /// checkpoint and event fan-out, so a backfill never rewinds or notifies the running indexer.
+    pub async fn store_backfilled_blocks(&self, blocks: Vec<Block>) -> Result<()> {
+        let mut slots = Vec::with_capacity(blocks.len());
+        let mut transactions = Vec::new();
+        for block in blocks {
+            slots.push(block.slot);
+            for mut tx in block.transactions {
+                self.decode_instructions(&mut tx.instruction_details, &tx.accounts);
+                transactions.push(tx);
+            }
+        }
+
+        self.timed_write(self.db.store_block_batch(&slots, &transactions))
+            .await
+    }
+
+    pub async fn get_backfill_cursor(&self, from: u64, to: u64) -> Result<Option<u64>> {
+        self.db.get_backfill_cursor(from, to).await
+    }
+
+    pub async fn set_backfill_cursor(&self, from: u64, to: u64, next_slot: u64) -> Result<()> {
+        self.db.set_backfill_cursor(from, to, next_slot).await
+    }
+
+    pub async fn store_instructions(
+        &self,
+        signature: &str,
+        instructions: &[Instruction],
+    ) -> Result<()> {
+        self.timed_write(self.db.store_instructions(signature, instructions))
+            .await
+    }
+
+    pub async fn get_instructions(&self, signature: &str) -> Result<Vec<Instruction>> {
+        self.db.get_instructions(signature).await
+    }
+
+    pub async fn store_token_balance_changes(
+        &self,
+        signature: &str,
+        changes: &[TokenBalanceChange],
+    ) -> Result<()> {
+        self.timed_write(self.db.store_token_balance_changes(signature, changes))
+            .await
+    }
+
+    /// Token movements of `owner`'s token accounts, newest first.
+    pub async fn get_token_balance_changes_for_owner(
+        &self,
+        owner: &str,
+        limit: u32,
+        cursor: Option<TokenChangeCursor>,
+    ) -> Result<Page<TokenBalanceChange>> {
+        self.db
+            .get_token_balance_changes_for_owner(owner, limit, cursor)
+            .await
+    }
+
+    pub async fn store_sol_balance_changes(
+        &self,
+        signature: &str,
+        changes: &[SolBalanceChange],
+    ) -> Result<()> {
+        self.timed_write(self.db.store_sol_balance_changes(signature, changes))
+            .await
+    }
+
+    pub async fn get_sol_balance_changes(&self, signature: &str) -> Result<Vec<SolBalanceChange>> {
+        self.db.get_sol_balance_changes(signature).await
+    }
+
+    /// Per-transaction lamport changes of `address`, newest first.
+    pub async fn get_sol_balance_history(
+        &self,
+        address: &str,
+        limit: u32,
+        cursor: Option<TxCursor>,
+    ) -> Result<Page<SolBalanceChange>> {
+        self.db.get_sol_balance_history(address, limit, cursor).await
     }
 
     pub async fn get_account(&self, address: &str) -> Result<Option<AccountState>> {
         self.l3.get(address).await
     }
 
-    pub async fn store_account(&self, account: AccountState) -> Result<()> {
-        self.l3.store(account).await
+    /// The indexed accounts among `addresses`, in the order given.
+    pub async fn get_accounts(&self, addresses: &[String]) -> Result<Vec<AccountState>> {
+        self.l3.get_many(addresses).await
+    }
+
+    pub async fn store_account(&self, mut account: AccountState) -> Result<()> {
+        account.decoded = self.decode_account(&account).await;
+        if let Some(change) = self.timed_write(self.l3.store(account)).await? {
+            self.publish(IndexerEvent::Account(change));
+        }
+        Ok(())
+    }
+
+    /// Recorded versions of `address`, newest first (history is only in the database).
+    pub async fn get_account_history(
+        &self,
+        address: &str,
+        range: AccountHistoryRange,
+    ) -> Result<Page<AccountState>> {
+        self.db.get_account_history(address, range).await
+    }
+
+    pub async fn get_account_at_slot(
+        &self,
+        address: &str,
+        slot: u64,
+    ) -> Result<Option<AccountState>> {
+        self.db.get_account_at_slot(address, slot).await
+    }
+
+    /// Accounts owned by `program`, ordered by address (straight from the database).
+    pub async fn get_program_accounts(
+        &self,
+        program: &str,
+        limit: u32,
+        cursor: Option<String>,
+    ) -> Result<Page<AccountState>> {
+        self.db.get_program_accounts(program, limit, cursor).await
     }
 
     pub async fn get_slot(&self, slot: u64) -> Result<Option<Slot>> {
@@ -90,6 +296,16 @@ impl MultiCache {
         Ok(None)
     }
 
+    /// Range scans go straight to the database (L1 only holds the hottest slots).
+    pub async fn get_slots_range(&self, range: SlotRange) -> Result<Page<Slot>> {
+        self.db.get_slots_range(range).await
+    }
+
+    /// Runs of slot numbers in `from..=to` that are not stored.
+    pub async fn get_slot_gaps(&self, from: u64, to: u64) -> Result<Vec<(u64, u64)>> {
+        self.db.get_slot_gaps(from, to).await
+    }
+
     pub async fn get_transaction(&self, signature: &str) -> Result<Option<Transaction>> {
         if let Some(cached) = self.l2.get(signature).await {
             self.metrics.l2_hits.fetch_add(1, Ordering::Relaxed);
@@ -103,6 +319,67 @@ impl MultiCache {
         Ok(None)
     }
 
+    pub async fn get_transactions_in_slot(
+        &self,
+        slot: u64,
+        limit: u32,
+        cursor: Option<String>,
+    ) -> Result<Page<Transaction>> {
+        self.db.get_transactions_in_slot(slot, limit, cursor).await
+    }
+
+    pub async fn count_transactions_in_slot(&self, slot: u64) -> Result<u64> {
+        self.db.count_transactions_in_slot(slot).await
+    }
+
+    /// Wallet history from the `transaction_accounts` index, newest first.
+    pub async fn get_transactions_for_account(
+        &self,
+        address: &str,
+        limit: u32,
+        cursor: Option<TxCursor>,
+    ) -> Result<Page<Transaction>> {
+        self.db
+            .get_transactions_for_account(address, limit, cursor)
+            .await
+    }
+
+    /// Program history from the `instructions` table (top-level and CPI), newest first.
+    pub async fn get_transactions_for_program(
+        &self,
+        program: &str,
+        limit: u32,
+        cursor: Option<TxCursor>,
+    ) -> Result<Page<Transaction>> {
+        self.db
+            .get_transactions_for_program(program, limit, cursor)
+            .await
+    }
+
+    pub async fn enqueue_webhook(&self, delivery: &WebhookDelivery) -> Result<()> {
+        self.db.enqueue_webhook(delivery).await
+    }
+
+    pub async fn get_due_webhooks(&self, now: i64, limit: u32) -> Result<Vec<WebhookDelivery>> {
+        self.db.get_due_webhooks(now, limit).await
+    }
+
+    pub async fn complete_webhook(&self, id: &str) -> Result<()> {
+        self.db.complete_webhook(id).await
+    }
+
+    pub async fn retry_webhook(&self, id: &str, next_attempt_at: i64, error: &str) -> Result<()> {
+        self.db.retry_webhook(id, next_attempt_at, error).await
+    }
+
+    pub async fn dead_letter_webhook(&self, id: &str, error: &str) -> Result<()> {
+        self.db.dead_letter_webhook(id, error).await
+    }
+
+    pub async fn get_webhook_dead_letters(&self, limit: u32) -> Result<Vec<WebhookDelivery>> {
+        self.db.get_webhook_dead_letters(limit).await
+    }
+
     pub async fn add_wallet(&self, address: String, name: Option<String>) -> Result<()> {
         self.db.add_wallet(address, name).await
     }
@@ -118,6 +395,15 @@ impl MultiCache {
         self.db.list_wallets(active_only).await
     }
 
+    pub async fn list_wallets_page(
+        &self,
+        active_only: bool,
+        limit: u32,
+        cursor: Option<String>,
+    ) -> Result<Page<(String, Option<String>, i64)>> {
+        self.db.list_wallets_page(active_only, limit, cursor).await
+    }
+
     pub async fn get_active_wallets(&self) -> Result<Vec<String>> {
         self.db.get_active_wallets().await
     }
@@ -147,6 +433,26 @@ mod tests {
         assert_eq!(cached.slot, 42);
     }
 
+    #[tokio::test]
+    async fn store_account_records_history_only_on_change() {
+        let db = Arc::new(MockDatabase::new());
+        let cache = test_cache(db.clone());
+        let mut account = crate::testing::mock_sources::sample_account("wallet", 100);
+
+        for slot in [1, 2, 3] {
+            account.slot = slot;
+            account.lamports = if slot == 3 { 50 } else { 100 };
+            cache.store_account(account.clone()).await.unwrap();
+        }
+
+        let history = db
+            .get_account_history("wallet", AccountHistoryRange { limit: 10, ..Default::default() })
+            .await
+            .unwrap();
+        let slots: Vec<u64> = history.items.iter().map(|a| a.slot).collect();
+        assert_eq!(slots, vec![3, 1]);
+    }
+
     #[tokio::test]
     async fn get_transaction_backfills_l2_from_db() {
         let db = Arc::new(MockDatabase::new());
@@ -157,6 +463,9 @@ mod tests {
             fee: 5000,
             success: true,
             accounts: vec![],
+            program: None,
+            instruction_count: None,
+            compute_units: None,
         };
         db.store_transaction(tx.clone()).await.unwrap();
 
@@ -1,5 +1,135 @@
-use crate::core::types::{AccountState, Slot, Transaction};
+use crate::core::types::{
+    AccountState, Instruction, Slot, SolBalanceChange, TokenBalanceChange, Transaction,
+    TransactionInfo, WebhookDelivery,
+};
 use crate::utils::errors::Result;
+use serde::Serialize;
+
+/// Slot range filter for [`DatabaseStorage::get_slots_range`]. Results are newest first;
+/// `cursor` is the last slot number of the previous page (exclusive).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SlotRange {
+    pub from: Option<u64>,
+    pub to: Option<u64>,
+    pub limit: u32,
+    pub cursor: Option<u64>,
+}
+
+/// Version of an account within the history table: Geyser updates are ordered by
+/// `(slot, write_version)`. Encoded as `<slot>:<write_version>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccountVersion {
+    pub slot: u64,
+    pub write_version: u64,
+}
+
+impl std::fmt::Display for AccountVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.slot, self.write_version)
+    }
+}
+
+impl std::str::FromStr for AccountVersion {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (slot, write_version) = s
+            .split_once(':')
+            .ok_or_else(|| "cursor must look like <slot>:<write_version>".to_string())?;
+        Ok(Self {
+            slot: slot.parse().map_err(|_| format!("invalid cursor slot: {slot}"))?,
+            write_version: write_version
+                .parse()
+                .map_err(|_| format!("invalid cursor write version: {write_version}"))?,
+        })
+    }
+}
+
+/// Filter for [`DatabaseStorage::get_account_history`]. Results are newest first;
+/// `cursor` is the version of the last entry of the previous page (exclusive).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AccountHistoryRange {
+    pub from_slot: Option<u64>,
+    pub to_slot: Option<u64>,
+    pub limit: u32,
+    pub cursor: Option<AccountVersion>,
+}
+
+/// Position in a newest-first transaction listing, encoded as `<slot>:<signature>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxCursor {
+    pub slot: u64,
+    pub signature: String,
+}
+
+impl std::fmt::Display for TxCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.slot, self.signature)
+    }
+}
+
+impl std::str::FromStr for TxCursor {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (slot, signature) = s
+            .split_once(':')
+            .ok_or_else(|| "cursor must look like <slot>:<signature>".to_string())?;
+        Ok(Self {
+            slot: slot.parse().map_err(|_| format!("invalid cursor slot: {slot}"))?,
+            signature: signature.to_string(),
+        })
+    }
+}
+
+/// Position in a newest-first token balance change listing, encoded as
+/// `<slot>:<signature>:<account>` (a transaction can move several of a wallet's token accounts).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenChangeCursor {
+    pub tx: TxCursor,
+    pub account: String,
+}
+
+impl std::fmt::Display for TokenChangeCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.tx, self.account)
+    }
+}
+
+impl std::str::FromStr for TokenChangeCursor {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (tx, account) = s
+            .rsplit_once(':')
+            .filter(|(tx, _)| tx.contains(':'))
+            .ok_or_else(|| "cursor must look like <slot>:<signature>:<account>".to_string())?;
+        Ok(Self {
+            tx: tx.parse()?,
+            account: account.to_string(),
+        })
+    }
+}
+
+/// One page of a cursor-paginated query; pass `next_cursor` back to continue.
+#[derive(Debug, Clone, Serialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+}
+
+impl<T> Page<T> {
+    /// Builds a page from rows fetched with `LIMIT limit + 1` (the extra row signals more data).
+    pub fn from_overfetch(mut items: Vec<T>, limit: u32, cursor_of: impl Fn(&T) -> String) -> Self {
+        let next_cursor = if items.len() > limit as usize {
+            items.truncate(limit as usize);
+            items.last().map(cursor_of)
+        } else {
+            None
+        };
+        Self { items, next_cursor }
+    }
+}
 
 #[async_trait::async_trait]
 pub trait DatabaseStorage: Send + Sync {
@@ -9,23 +139,161 @@ pub trait DatabaseStorage: Send + Sync {
 
     async fn get_account(&self, address: &str) -> Result<Option<AccountState>>;
 
+    /// The stored accounts among `addresses` in one query, in no particular order.
+    async fn get_accounts(&self, addresses: &[String]) -> Result<Vec<AccountState>>;
+
+    /// Accounts owned by `program`, ordered by address (`cursor` is the last address seen).
+    async fn get_program_accounts(
+        &self,
+        program: &str,
+        limit: u32,
+        cursor: Option<String>,
+    ) -> Result<Page<AccountState>>;
+
+    /// Appends `account` to `account_history`; a version already recorded is ignored.
+    async fn append_account_history(&self, account: &AccountState) -> Result<()>;
+
+    async fn get_account_history(
+        &self,
+        address: &str,
+        range: AccountHistoryRange,
+    ) -> Result<Page<AccountState>>;
+
+    /// Latest recorded state of `address` at or before `slot`.
+    async fn get_account_at_slot(&self, address: &str, slot: u64) -> Result<Option<AccountState>>;
+
     async fn get_slot(&self, slot: u64) -> Result<Option<Slot>>;
 
     async fn store_transaction(&self, tx: Transaction) -> Result<()>;
 
+    /// Stores `slots` and `transactions` (with their account index, instructions and balance
+    /// changes) in one database transaction.
+    async fn store_block_batch(
+        &self,
+        slots: &[Slot],
+        transactions: &[TransactionInfo],
+    ) -> Result<()>;
+
     async fn get_transaction(&self, signature: &str) -> Result<Option<Transaction>>;
 
+    /// Transactions stored for `slot`, ordered by signature (`cursor` is the last signature seen).
+    async fn get_transactions_in_slot(
+        &self,
+        slot: u64,
+        limit: u32,
+        cursor: Option<String>,
+    ) -> Result<Page<Transaction>>;
+
+    async fn count_transactions_in_slot(&self, slot: u64) -> Result<u64>;
+
+    /// Transactions touching `address`, newest first.
+    async fn get_transactions_for_account(
+        &self,
+        address: &str,
+        limit: u32,
+        cursor: Option<TxCursor>,
+    ) -> Result<Page<Transaction>>;
+
+    /// Replaces the stored instructions of `signature` (given in execution order).
+    async fn store_instructions(&self, signature: &str, instructions: &[Instruction]) -> Result<()>;
+
+    async fn get_instructions(&self, signature: &str) -> Result<Vec<Instruction>>;
+
+    /// Transactions with any (top-level or inner) instruction of `program`, newest first.
+    async fn get_transactions_for_program(
+        &self,
+        program: &str,
+        limit: u32,
+        cursor: Option<TxCursor>,
+    ) -> Result<Page<Transaction>>;
+
+    /// Replaces the stored token balance changes of `signature`.
+    async fn store_token_balance_changes(
+        &self,
+        signature: &str,
+        changes: &[TokenBalanceChange],
+    ) -> Result<()>;
+
+    /// Token balance changes of accounts owned by `owner`, newest first.
+    async fn get_token_balance_changes_for_owner(
+        &self,
+        owner: &str,
+        limit: u32,
+        cursor: Option<TokenChangeCursor>,
+    ) -> Result<Page<TokenBalanceChange>>;
+
+    /// Replaces the stored lamport balance changes of `signature` (given in account order).
+    async fn store_sol_balance_changes(
+        &self,
+        signature: &str,
+        changes: &[SolBalanceChange],
+    ) -> Result<()>;
+
+    async fn get_sol_balance_changes(&self, signature: &str) -> Result<Vec<SolBalanceChange>>;
+
+    /// Lamport balance changes of `address`, newest first.
+    async fn get_sol_balance_history(
+        &self,
+        address: &str,
+        limit: u32,
+        cursor: Option<TxCursor>,
+    ) -> Result<Page<SolBalanceChange>>;
+
+    /// Queues a webhook POST (ignored if a delivery with the same id is already queued).
+    async fn enqueue_webhook(&self, delivery: &WebhookDelivery) -> Result<()>;
+
+    /// Queued deliveries whose next attempt is at or before `now`, oldest first.
+    async fn get_due_webhooks(&self, now: i64, limit: u32) -> Result<Vec<WebhookDelivery>>;
+
+    /// Removes a delivered webhook from the queue.
+    async fn complete_webhook(&self, id: &str) -> Result<()>;
+
+    /// Records a failed attempt and schedules the next one.
+    async fn retry_webhook(&self, id: &str, next_attempt_at: i64, error: &str) -> Result<()>;
+
+    /// Records a final failed attempt and moves the delivery to the dead-letter table.
+    async fn dead_letter_webhook(&self, id: &str, error: &str) -> Result<()>;
+
+    /// Abandoned deliveries, most recently failed first.
+    async fn get_webhook_dead_letters(&self, limit: u32) -> Result<Vec<WebhookDelivery>>;
+
     async fn get_latest_slot(&self) -> Result<Option<Slot>>;
 
+    async fn get_slots_range(&self, range: SlotRange) -> Result<Page<Slot>>;
+
+    /// Inclusive `(start, end)` runs of slot numbers in `from..=to` with no row in `slots`,
+    /// in ascending order.
+    async fn get_slot_gaps(&self, from: u64, to: u64) -> Result<Vec<(u64, u64)>>;
+
     async fn add_wallet(&self, address: String, name: Option<String>) -> Result<()>;
 
     async fn remove_wallet(&self, address: &str) -> Result<()>;
 
     async fn list_wallets(&self, active_only: bool) -> Result<Vec<(String, Option<String>, i64)>>;
 
+    /// Wallets ordered by address (`cursor` is the last address seen).
+    async fn list_wallets_page(
+        &self,
+        active_only: bool,
+        limit: u32,
+        cursor: Option<String>,
+    ) -> Result<Page<(String, Option<String>, i64)>>;
+
     async fn get_active_wallets(&self) -> Result<Vec<String>>;
 
     async fn get_checkpoint(&self) -> Result<Option<u64>>;
 
+    /// Checkpoint slot and the unix time it was last written (i.e. when a slot was last stored).
+    async fn get_checkpoint_status(&self) -> Result<Option<(u64, i64)>>;
+
     async fn set_checkpoint(&self, slot: u64) -> Result<()>;
+
+    /// Next slot to fetch for the historical backfill of `from..=to`, if one was started.
+    async fn get_backfill_cursor(&self, from: u64, to: u64) -> Result<Option<u64>>;
+
+    async fn set_backfill_cursor(&self, from: u64, to: u64, next_slot: u64) -> Result<()>;
+
+    /// Marks `slot` as `Dead` and deletes the transactions (and account versions) stored for it.
+    /// Returns the number of transactions removed.
+    async fn rollback_slot(&self, slot: u64) -> Result<u64>;
 }
\ No newline at end of file
@@ -1,41 +1,105 @@
 //! SQL for SQLite and PostgreSQL — per-backend macros (shared read shape, dialect-specific writes).
 
+/// Upserted slot status: the stored one is kept unless the incoming status ranks at least as
+/// high (Processed < Confirmed < Finalized < Dead), so a late update never downgrades a slot.
+macro_rules! slot_status_upsert {
+    () => {
+        concat!(
+            "status = CASE WHEN ",
+            "(CASE slots.status WHEN 'Dead' THEN 3 WHEN 'Finalized' THEN 2 ",
+            "WHEN 'Confirmed' THEN 1 ELSE 0 END) > ",
+            "(CASE excluded.status WHEN 'Dead' THEN 3 WHEN 'Finalized' THEN 2 ",
+            "WHEN 'Confirmed' THEN 1 ELSE 0 END) ",
+            "THEN slots.status ELSE excluded.status END"
+        )
+    };
+}
+
 macro_rules! sqlite_backend {
     () => {
         pub mod sqlite {
             pub const GET_ACCOUNT: &str = concat!(
-                "SELECT address, slot, lamports, owner, executable, data, rent_epoch ",
+                "SELECT address, slot, lamports, owner, executable, data, rent_epoch, decoded, ",
+                "write_version ",
                 "FROM accounts WHERE address = ?1"
             );
+            /// `?1` is a JSON array of addresses.
+            pub const GET_ACCOUNTS: &str = concat!(
+                "SELECT address, slot, lamports, owner, executable, data, rent_epoch, decoded, ",
+                "write_version ",
+                "FROM accounts WHERE address IN (SELECT value FROM json_each(?1))"
+            );
+            pub const GET_PROGRAM_ACCOUNTS: &str = concat!(
+                "SELECT address, slot, lamports, owner, executable, data, rent_epoch, decoded, ",
+                "write_version ",
+                "FROM accounts WHERE owner = ?1 AND address > ?2 ",
+                "ORDER BY address LIMIT ?3"
+            );
             pub const GET_SLOT: &str = concat!(
-                "SELECT slot_number, timestamp, parent, status, block_hash, block_height ",
+                "SELECT slot_number, timestamp, parent, status, block_hash, block_height, ",
+                "processed_at, confirmed_at, finalized_at ",
                 "FROM slots WHERE slot_number = ?1"
             );
             pub const GET_LATEST_SLOT: &str = concat!(
-                "SELECT slot_number, timestamp, parent, status, block_hash, block_height ",
+                "SELECT slot_number, timestamp, parent, status, block_hash, block_height, ",
+                "processed_at, confirmed_at, finalized_at ",
                 "FROM slots ORDER BY slot_number DESC LIMIT 1"
             );
+            pub const GET_SLOTS_RANGE: &str = concat!(
+                "SELECT slot_number, timestamp, parent, status, block_hash, block_height, ",
+                "processed_at, confirmed_at, finalized_at ",
+                "FROM slots WHERE slot_number >= ?1 AND slot_number <= ?2 AND slot_number < ?3 ",
+                "ORDER BY slot_number DESC LIMIT ?4"
+            );
+            pub const GET_SLOT_GAPS: &str = concat!(
+                "SELECT prev + 1, slot_number - 1 FROM (",
+                "SELECT slot_number, LAG(slot_number, 1, CAST(?1 AS BIGINT) - 1) ",
+                "OVER (ORDER BY slot_number) AS prev FROM (",
+                "SELECT slot_number FROM slots WHERE slot_number >= ?1 AND slot_number <= ?2 ",
+                "UNION ALL SELECT CAST(?2 AS BIGINT) + 1",
+                ") AS bounded) AS stepped ",
+                "WHERE slot_number > prev + 1 ORDER BY slot_number"
+            );
             pub const GET_TRANSACTION: &str = concat!(
-                "SELECT signature, slot, block_time, fee, success, accounts ",
+                "SELECT signature, slot, block_time, fee, success, accounts, ",
+                "program, instruction_count, compute_units ",
                 "FROM transactions WHERE signature = ?1"
             );
+            pub const GET_SLOT_TRANSACTIONS: &str = concat!(
+                "SELECT signature, slot, block_time, fee, success, accounts, ",
+                "program, instruction_count, compute_units ",
+                "FROM transactions WHERE slot = ?1 AND signature > ?2 ",
+                "ORDER BY signature LIMIT ?3"
+            );
+            pub const COUNT_SLOT_TRANSACTIONS: &str =
+                "SELECT COUNT(*) FROM transactions WHERE slot = ?1";
             pub const LIST_WALLETS_ALL: &str =
                 "SELECT address, name, created_at FROM wallets ORDER BY created_at DESC";
 
-            pub const STORE_SLOT: &str = "\
-                INSERT OR REPLACE INTO slots \
-                (slot_number, timestamp, parent, status, block_hash, block_height) \
-                VALUES (?1, ?2, ?3, ?4, ?5, ?6)";
+            pub const STORE_SLOT: &str = concat!(
+                "INSERT INTO slots ",
+                "(slot_number, timestamp, parent, status, block_hash, block_height, ",
+                "processed_at, confirmed_at, finalized_at) ",
+                "VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9) ",
+                "ON CONFLICT (slot_number) DO UPDATE SET ",
+                "timestamp = ?2, parent = ?3, block_hash = ?5, block_height = ?6, ",
+                slot_status_upsert!(),
+                ", processed_at = COALESCE(slots.processed_at, ?7), ",
+                "confirmed_at = COALESCE(slots.confirmed_at, ?8), ",
+                "finalized_at = COALESCE(slots.finalized_at, ?9)"
+            );
 
             pub const STORE_ACCOUNT: &str = "\
                 INSERT OR REPLACE INTO accounts \
-                (address, slot, lamports, owner, executable, data, rent_epoch, updated_at) \
-                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)";
+                (address, slot, lamports, owner, executable, data, rent_epoch, updated_at, decoded, \
+                write_version) \
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)";
 
             pub const STORE_TRANSACTION: &str = "\
                 INSERT OR REPLACE INTO transactions \
-                (signature, slot, block_time, fee, success, accounts) \
-                VALUES (?1, ?2, ?3, ?4, ?5, ?6)";
+                (signature, slot, block_time, fee, success, accounts, \
+                program, instruction_count, compute_units) \
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)";
 
             pub const ADD_WALLET: &str = "\
                 INSERT OR REPLACE INTO wallets (address, name, is_active, created_at) \
@@ -48,15 +112,178 @@ macro_rules! sqlite_backend {
                 SELECT address, name, created_at FROM wallets \
                 WHERE is_active = 1 ORDER BY created_at DESC";
 
+            pub const LIST_WALLETS_PAGE_ALL: &str = "\
+                SELECT address, name, created_at FROM wallets \
+                WHERE address > ?1 ORDER BY address LIMIT ?2";
+
+            pub const LIST_WALLETS_PAGE_ACTIVE: &str = "\
+                SELECT address, name, created_at FROM wallets \
+                WHERE is_active = 1 AND address > ?1 ORDER BY address LIMIT ?2";
+
             pub const GET_ACTIVE_WALLETS: &str =
                 "SELECT address FROM wallets WHERE is_active = 1";
 
             pub const GET_CHECKPOINT: &str =
                 "SELECT last_processed_slot FROM indexer_checkpoint WHERE id = 1";
 
+            pub const GET_CHECKPOINT_STATUS: &str =
+                "SELECT last_processed_slot, updated_at FROM indexer_checkpoint WHERE id = 1";
+
             pub const SET_CHECKPOINT: &str = "\
-                INSERT OR REPLACE INTO indexer_checkpoint (id, last_processed_slot, updated_at) \
-                VALUES (1, ?1, ?2)";
+                INSERT INTO indexer_checkpoint (id, last_processed_slot, updated_at) \
+                VALUES (1, ?1, ?2) \
+                ON CONFLICT (id) DO UPDATE SET \
+                last_processed_slot = MAX(indexer_checkpoint.last_processed_slot, excluded.last_processed_slot), \
+                updated_at = excluded.updated_at";
+
+            pub const MARK_SLOT_DEAD: &str =
+                "UPDATE slots SET status = 'Dead' WHERE slot_number = ?1";
+
+            pub const DELETE_SLOT_TRANSACTIONS: &str =
+                "DELETE FROM transactions WHERE slot = ?1";
+
+            pub const STORE_TRANSACTION_ACCOUNT: &str = "\
+                INSERT OR IGNORE INTO transaction_accounts (account, signature, slot) \
+                VALUES (?1, ?2, ?3)";
+
+            pub const DELETE_TRANSACTION_ACCOUNTS: &str =
+                "DELETE FROM transaction_accounts WHERE signature = ?1";
+
+            pub const DELETE_SLOT_TRANSACTION_ACCOUNTS: &str =
+                "DELETE FROM transaction_accounts WHERE slot = ?1";
+
+            pub const GET_ACCOUNT_TRANSACTIONS: &str = "\
+                SELECT t.signature, t.slot, t.block_time, t.fee, t.success, t.accounts, \
+                t.program, t.instruction_count, t.compute_units \
+                FROM transaction_accounts ta \
+                JOIN transactions t ON t.signature = ta.signature \
+                WHERE ta.account = ?1 AND (ta.slot < ?2 OR (ta.slot = ?2 AND ta.signature < ?3)) \
+                ORDER BY ta.slot DESC, ta.signature DESC LIMIT ?4";
+
+            pub const STORE_INSTRUCTION: &str = "\
+                INSERT INTO instructions \
+                (signature, position, slot, instruction_index, inner_index, stack_height, \
+                program_id, accounts, data, parsed) \
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)";
+
+            pub const DELETE_TRANSACTION_INSTRUCTIONS: &str =
+                "DELETE FROM instructions WHERE signature = ?1";
+
+            pub const DELETE_SLOT_INSTRUCTIONS: &str =
+                "DELETE FROM instructions WHERE slot = ?1";
+
+            pub const GET_TRANSACTION_INSTRUCTIONS: &str = "\
+                SELECT signature, slot, instruction_index, inner_index, stack_height, \
+                program_id, accounts, data, parsed \
+                FROM instructions WHERE signature = ?1 ORDER BY position";
+
+            // Walks idx_instructions_program_slot newest-first; DISTINCT folds repeat calls
+            // to the program within one transaction.
+            pub const GET_PROGRAM_TRANSACTIONS: &str = "\
+                SELECT DISTINCT i.signature, i.slot, t.block_time, t.fee, t.success, t.accounts, \
+                t.program, t.instruction_count, t.compute_units \
+                FROM instructions i JOIN transactions t ON t.signature = i.signature \
+                WHERE i.program_id = ?1 \
+                AND (i.slot < ?2 OR (i.slot = ?2 AND i.signature < ?3)) \
+                ORDER BY i.slot DESC, i.signature DESC LIMIT ?4";
+
+            pub const STORE_TOKEN_BALANCE_CHANGE: &str = "\
+                INSERT OR REPLACE INTO token_balance_changes \
+                (signature, account, slot, owner, mint, pre, post, decimals) \
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)";
+
+            pub const DELETE_TRANSACTION_TOKEN_BALANCE_CHANGES: &str =
+                "DELETE FROM token_balance_changes WHERE signature = ?1";
+
+            pub const DELETE_SLOT_TOKEN_BALANCE_CHANGES: &str =
+                "DELETE FROM token_balance_changes WHERE slot = ?1";
+
+            pub const GET_OWNER_TOKEN_BALANCE_CHANGES: &str = "\
+                SELECT signature, slot, account, owner, mint, pre, post, decimals \
+                FROM token_balance_changes \
+                WHERE owner = ?1 AND (slot < ?2 OR (slot = ?2 AND (signature < ?3 \
+                OR (signature = ?3 AND account < ?4)))) \
+                ORDER BY slot DESC, signature DESC, account DESC LIMIT ?5";
+
+            pub const STORE_SOL_BALANCE_CHANGE: &str = "\
+                INSERT OR REPLACE INTO sol_balance_changes \
+                (signature, account, slot, position, pre, post) \
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6)";
+
+            pub const DELETE_TRANSACTION_SOL_BALANCE_CHANGES: &str =
+                "DELETE FROM sol_balance_changes WHERE signature = ?1";
+
+            pub const DELETE_SLOT_SOL_BALANCE_CHANGES: &str =
+                "DELETE FROM sol_balance_changes WHERE slot = ?1";
+
+            pub const GET_TRANSACTION_SOL_BALANCE_CHANGES: &str = "\
+                SELECT signature, slot, account, pre, post \
+                FROM sol_balance_changes WHERE signature = ?1 ORDER BY position";
+
+            pub const GET_ACCOUNT_SOL_BALANCE_CHANGES: &str = "\
+                SELECT signature, slot, account, pre, post \
+                FROM sol_balance_changes \
+                WHERE account = ?1 AND (slot < ?2 OR (slot = ?2 AND signature < ?3)) \
+                ORDER BY slot DESC, signature DESC LIMIT ?4";
+
+            pub const APPEND_ACCOUNT_HISTORY: &str = "\
+                INSERT OR IGNORE INTO account_history \
+                (address, slot, write_version, lamports, owner, executable, data, rent_epoch, \
+                decoded, recorded_at) \
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)";
+
+            pub const DELETE_SLOT_ACCOUNT_HISTORY: &str =
+                "DELETE FROM account_history WHERE slot = ?1";
+
+            pub const GET_ACCOUNT_HISTORY: &str = "\
+                SELECT address, slot, lamports, owner, executable, data, rent_epoch, decoded, \
+                write_version \
+                FROM account_history \
+                WHERE address = ?1 AND slot >= ?2 AND slot <= ?3 \
+                AND (slot < ?4 OR (slot = ?4 AND write_version < ?5)) \
+                ORDER BY slot DESC, write_version DESC LIMIT ?6";
+
+            pub const GET_ACCOUNT_AT_SLOT: &str = "\
+                SELECT address, slot, lamports, owner, executable, data, rent_epoch, decoded, \
+                write_version \
+                FROM account_history WHERE address = ?1 AND slot <= ?2 \
+                ORDER BY slot DESC, write_version DESC LIMIT 1";
+
+            pub const ENQUEUE_WEBHOOK: &str = "\
+                INSERT OR IGNORE INTO webhook_deliveries \
+                (id, url, event, payload, attempts, next_attempt_at, last_error, created_at) \
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)";
+
+            pub const GET_DUE_WEBHOOKS: &str = "\
+                SELECT id, url, event, payload, attempts, next_attempt_at, last_error, created_at \
+                FROM webhook_deliveries WHERE next_attempt_at <= ?1 \
+                ORDER BY next_attempt_at, id LIMIT ?2";
+
+            pub const DELETE_WEBHOOK: &str =
+                "DELETE FROM webhook_deliveries WHERE id = ?1";
+
+            pub const RETRY_WEBHOOK: &str = "\
+                UPDATE webhook_deliveries \
+                SET attempts = attempts + 1, next_attempt_at = ?2, last_error = ?3 \
+                WHERE id = ?1";
+
+            pub const DEAD_LETTER_WEBHOOK: &str = "\
+                INSERT OR REPLACE INTO webhook_dead_letters \
+                (id, url, event, payload, attempts, failed_at, last_error, created_at) \
+                SELECT id, url, event, payload, attempts + 1, ?2, ?3, created_at \
+                FROM webhook_deliveries WHERE id = ?1";
+
+            pub const GET_WEBHOOK_DEAD_LETTERS: &str = "\
+                SELECT id, url, event, payload, attempts, failed_at, last_error, created_at \
+                FROM webhook_dead_letters ORDER BY failed_at DESC, id DESC LIMIT ?1";
+
+            pub const GET_BACKFILL_CURSOR: &str = "\
+                SELECT next_slot FROM backfill_progress \
+                WHERE range_start = ?1 AND range_end = ?2";
+
+            pub const SET_BACKFILL_CURSOR: &str = "\
+                INSERT OR REPLACE INTO backfill_progress \
+                (range_start, range_end, next_slot, updated_at) VALUES (?1, ?2, ?3, ?4)";
         }
     };
 }
@@ -65,42 +292,93 @@ macro_rules! postgres_backend {
     () => {
         pub mod postgres {
             pub const GET_ACCOUNT: &str = concat!(
-                "SELECT address, slot, lamports, owner, executable, data, rent_epoch ",
+                "SELECT address, slot, lamports, owner, executable, data, rent_epoch, decoded, ",
+                "write_version ",
                 "FROM accounts WHERE address = $1"
             );
+            /// `$1` is a JSON array of addresses.
+            pub const GET_ACCOUNTS: &str = concat!(
+                "SELECT address, slot, lamports, owner, executable, data, rent_epoch, decoded, ",
+                "write_version ",
+                "FROM accounts WHERE address IN (SELECT json_array_elements_text($1::json))"
+            );
+            pub const GET_PROGRAM_ACCOUNTS: &str = concat!(
+                "SELECT address, slot, lamports, owner, executable, data, rent_epoch, decoded, ",
+                "write_version ",
+                "FROM accounts WHERE owner = $1 AND address > $2 ",
+                "ORDER BY address LIMIT $3"
+            );
             pub const GET_SLOT: &str = concat!(
-                "SELECT slot_number, timestamp, parent, status, block_hash, block_height ",
+                "SELECT slot_number, timestamp, parent, status, block_hash, block_height, ",
+                "processed_at, confirmed_at, finalized_at ",
                 "FROM slots WHERE slot_number = $1"
             );
             pub const GET_LATEST_SLOT: &str = concat!(
-                "SELECT slot_number, timestamp, parent, status, block_hash, block_height ",
+                "SELECT slot_number, timestamp, parent, status, block_hash, block_height, ",
+                "processed_at, confirmed_at, finalized_at ",
                 "FROM slots ORDER BY slot_number DESC LIMIT 1"
             );
+            pub const GET_SLOTS_RANGE: &str = concat!(
+                "SELECT slot_number, timestamp, parent, status, block_hash, block_height, ",
+                "processed_at, confirmed_at, finalized_at ",
+                "FROM slots WHERE slot_number >= $1 AND slot_number <= $2 AND slot_number < $3 ",
+                "ORDER BY slot_number DESC LIMIT $4"
+            );
+            pub const GET_SLOT_GAPS: &str = concat!(
+                "SELECT prev + 1, slot_number - 1 FROM (",
+                "SELECT slot_number, LAG(slot_number, 1, CAST($1 AS BIGINT) - 1) ",
+                "OVER (ORDER BY slot_number) AS prev FROM (",
+                "SELECT slot_number FROM slots WHERE slot_number >= $1 AND slot_number <= $2 ",
+                "UNION ALL SELECT CAST($2 AS BIGINT) + 1",
+                ") AS bounded) AS stepped ",
+                "WHERE slot_number > prev + 1 ORDER BY slot_number"
+            );
             pub const GET_TRANSACTION: &str = concat!(
-                "SELECT signature, slot, block_time, fee, success, accounts ",
+                "SELECT signature, slot, block_time, fee, success, accounts, ",
+                "program, instruction_count, compute_units ",
                 "FROM transactions WHERE signature = $1"
             );
+            pub const GET_SLOT_TRANSACTIONS: &str = concat!(
+                "SELECT signature, slot, block_time, fee, success, accounts, ",
+                "program, instruction_count, compute_units ",
+                "FROM transactions WHERE slot = $1 AND signature > $2 ",
+                "ORDER BY signature LIMIT $3"
+            );
+            pub const COUNT_SLOT_TRANSACTIONS: &str =
+                "SELECT COUNT(*) FROM transactions WHERE slot = $1";
             pub const LIST_WALLETS_ALL: &str =
                 "SELECT address, name, created_at FROM wallets ORDER BY created_at DESC";
 
-            pub const STORE_SLOT: &str = "\
-                INSERT INTO slots (slot_number, timestamp, parent, status, block_hash, block_height) \
-                VALUES ($1, $2, $3, $4, $5, $6) \
-                ON CONFLICT (slot_number) DO UPDATE SET \
-                timestamp = $2, parent = $3, status = $4, block_hash = $5, block_height = $6";
+            pub const STORE_SLOT: &str = concat!(
+                "INSERT INTO slots ",
+                "(slot_number, timestamp, parent, status, block_hash, block_height, ",
+                "processed_at, confirmed_at, finalized_at) ",
+                "VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) ",
+                "ON CONFLICT (slot_number) DO UPDATE SET ",
+                "timestamp = $2, parent = $3, block_hash = $5, block_height = $6, ",
+                slot_status_upsert!(),
+                ", processed_at = COALESCE(slots.processed_at, $7), ",
+                "confirmed_at = COALESCE(slots.confirmed_at, $8), ",
+                "finalized_at = COALESCE(slots.finalized_at, $9)"
+            );
 
             pub const STORE_ACCOUNT: &str = "\
                 INSERT INTO accounts \
-                (address, slot, lamports, owner, executable, data, rent_epoch, updated_at) \
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8) \
+                (address, slot, lamports, owner, executable, data, rent_epoch, updated_at, decoded, \
+                write_version) \
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) \
                 ON CONFLICT (address) DO UPDATE SET \
-                slot = $2, lamports = $3, owner = $4, executable = $5, data = $6, rent_epoch = $7, updated_at = $8";
+                slot = $2, lamports = $3, owner = $4, executable = $5, data = $6, rent_epoch = $7, \
+                updated_at = $8, decoded = $9, write_version = $10";
 
             pub const STORE_TRANSACTION: &str = "\
-                INSERT INTO transactions (signature, slot, block_time, fee, success, accounts) \
-                VALUES ($1, $2, $3, $4, $5, $6) \
+                INSERT INTO transactions \
+                (signature, slot, block_time, fee, success, accounts, \
+                program, instruction_count, compute_units) \
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) \
                 ON CONFLICT (signature) DO UPDATE SET \
-                slot = $2, block_time = $3, fee = $4, success = $5, accounts = $6";
+                slot = $2, block_time = $3, fee = $4, success = $5, accounts = $6, \
+                program = $7, instruction_count = $8, compute_units = $9";
 
             pub const ADD_WALLET: &str = "\
                 INSERT INTO wallets (address, name, is_active, created_at) \
@@ -114,17 +392,188 @@ macro_rules! postgres_backend {
                 SELECT address, name, created_at FROM wallets \
                 WHERE is_active = TRUE ORDER BY created_at DESC";
 
+            pub const LIST_WALLETS_PAGE_ALL: &str = "\
+                SELECT address, name, created_at FROM wallets \
+                WHERE address > $1 ORDER BY address LIMIT $2";
+
+            pub const LIST_WALLETS_PAGE_ACTIVE: &str = "\
+                SELECT address, name, created_at FROM wallets \
+                WHERE is_active = TRUE AND address > $1 ORDER BY address LIMIT $2";
+
             pub const GET_ACTIVE_WALLETS: &str =
                 "SELECT address FROM wallets WHERE is_active = TRUE";
 
             pub const GET_CHECKPOINT: &str =
                 "SELECT last_processed_slot FROM indexer_checkpoint WHERE id = 1";
 
+            pub const GET_CHECKPOINT_STATUS: &str =
+                "SELECT last_processed_slot, updated_at FROM indexer_checkpoint WHERE id = 1";
+
             pub const SET_CHECKPOINT: &str = "\
                 INSERT INTO indexer_checkpoint (id, last_processed_slot, updated_at) \
                 VALUES (1, $1, $2) \
                 ON CONFLICT (id) DO UPDATE SET \
-                last_processed_slot = $1, updated_at = $2";
+                last_processed_slot = GREATEST(indexer_checkpoint.last_processed_slot, excluded.last_processed_slot), \
+                updated_at = excluded.updated_at";
+
+            pub const MARK_SLOT_DEAD: &str =
+                "UPDATE slots SET status = 'Dead' WHERE slot_number = $1";
+
+            pub const DELETE_SLOT_TRANSACTIONS: &str =
+                "DELETE FROM transactions WHERE slot = $1";
+
+            pub const STORE_TRANSACTION_ACCOUNT: &str = "\
+                INSERT INTO transaction_accounts (account, signature, slot) \
+                VALUES ($1, $2, $3) \
+                ON CONFLICT (account, signature) DO NOTHING";
+
+            pub const DELETE_TRANSACTION_ACCOUNTS: &str =
+                "DELETE FROM transaction_accounts WHERE signature = $1";
+
+            pub const DELETE_SLOT_TRANSACTION_ACCOUNTS: &str =
+                "DELETE FROM transaction_accounts WHERE slot = $1";
+
+            pub const GET_ACCOUNT_TRANSACTIONS: &str = "\
+                SELECT t.signature, t.slot, t.block_time, t.fee, t.success, t.accounts, \
+                t.program, t.instruction_count, t.compute_units \
+                FROM transaction_accounts ta \
+                JOIN transactions t ON t.signature = ta.signature \
+                WHERE ta.account = $1 AND (ta.slot < $2 OR (ta.slot = $2 AND ta.signature < $3)) \
+                ORDER BY ta.slot DESC, ta.signature DESC LIMIT $4";
+
+            pub const STORE_INSTRUCTION: &str = "\
+                INSERT INTO instructions \
+                (signature, position, slot, instruction_index, inner_index, stack_height, \
+                program_id, accounts, data, parsed) \
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)";
+
+            pub const DELETE_TRANSACTION_INSTRUCTIONS: &str =
+                "DELETE FROM instructions WHERE signature = $1";
+
+            pub const DELETE_SLOT_INSTRUCTIONS: &str =
+                "DELETE FROM instructions WHERE slot = $1";
+
+            pub const GET_TRANSACTION_INSTRUCTIONS: &str = "\
+                SELECT signature, slot, instruction_index, inner_index, stack_height, \
+                program_id, accounts, data, parsed \
+                FROM instructions WHERE signature = $1 ORDER BY position";
+
+            // Walks idx_instructions_program_slot newest-first; DISTINCT folds repeat calls
+            // to the program within one transaction.
+            pub const GET_PROGRAM_TRANSACTIONS: &str = "\
+                SELECT DISTINCT i.signature, i.slot, t.block_time, t.fee, t.success, t.accounts, \
+                t.program, t.instruction_count, t.compute_units \
+                FROM instructions i JOIN transactions t ON t.signature = i.signature \
+                WHERE i.program_id = $1 \
+                AND (i.slot < $2 OR (i.slot = $2 AND i.signature < $3)) \
+                ORDER BY i.slot DESC, i.signature DESC LIMIT $4";
+
+            pub const STORE_TOKEN_BALANCE_CHANGE: &str = "\
+                INSERT INTO token_balance_changes \
+                (signature, account, slot, owner, mint, pre, post, decimals) \
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8) \
+                ON CONFLICT (signature, account) DO UPDATE SET \
+                slot = $3, owner = $4, mint = $5, pre = $6, post = $7, decimals = $8";
+
+            pub const DELETE_TRANSACTION_TOKEN_BALANCE_CHANGES: &str =
+                "DELETE FROM token_balance_changes WHERE signature = $1";
+
+            pub const DELETE_SLOT_TOKEN_BALANCE_CHANGES: &str =
+                "DELETE FROM token_balance_changes WHERE slot = $1";
+
+            pub const GET_OWNER_TOKEN_BALANCE_CHANGES: &str = "\
+                SELECT signature, slot, account, owner, mint, pre, post, decimals \
+                FROM token_balance_changes \
+                WHERE owner = $1 AND (slot < $2 OR (slot = $2 AND (signature < $3 \
+                OR (signature = $3 AND account < $4)))) \
+                ORDER BY slot DESC, signature DESC, account DESC LIMIT $5";
+
+            pub const STORE_SOL_BALANCE_CHANGE: &str = "\
+                INSERT INTO sol_balance_changes \
+                (signature, account, slot, position, pre, post) \
+                VALUES ($1, $2, $3, $4, $5, $6) \
+                ON CONFLICT (signature, account) DO UPDATE SET \
+                slot = $3, position = $4, pre = $5, post = $6";
+
+            pub const DELETE_TRANSACTION_SOL_BALANCE_CHANGES: &str =
+                "DELETE FROM sol_balance_changes WHERE signature = $1";
+
+            pub const DELETE_SLOT_SOL_BALANCE_CHANGES: &str =
+                "DELETE FROM sol_balance_changes WHERE slot = $1";
+
+            pub const GET_TRANSACTION_SOL_BALANCE_CHANGES: &str = "\
+                SELECT signature, slot, account, pre, post \
+                FROM sol_balance_changes WHERE signature = $1 ORDER BY position";
+
+            pub const GET_ACCOUNT_SOL_BALANCE_CHANGES: &str = "\
+                SELECT signature, slot, account, pre, post \
+                FROM sol_balance_changes \
+                WHERE account = $1 AND (slot < $2 OR (slot = $2 AND signature < $3)) \
+                ORDER BY slot DESC, signature DESC LIMIT $4";
+
+            pub const APPEND_ACCOUNT_HISTORY: &str = "\
+                INSERT INTO account_history \
+                (address, slot, write_version, lamports, owner, executable, data, rent_epoch, \
+                decoded, recorded_at) \
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) \
+                ON CONFLICT (address, slot, write_version) DO NOTHING";
+
+            pub const DELETE_SLOT_ACCOUNT_HISTORY: &str =
+                "DELETE FROM account_history WHERE slot = $1";
+
+            pub const GET_ACCOUNT_HISTORY: &str = "\
+                SELECT address, slot, lamports, owner, executable, data, rent_epoch, decoded, \
+                write_version \
+                FROM account_history \
+                WHERE address = $1 AND slot >= $2 AND slot <= $3 \
+                AND (slot < $4 OR (slot = $4 AND write_version < $5)) \
+                ORDER BY slot DESC, write_version DESC LIMIT $6";
+
+            pub const GET_ACCOUNT_AT_SLOT: &str = "\
+                SELECT address, slot, lamports, owner, executable, data, rent_epoch, decoded, \
+                write_version \
+                FROM account_history WHERE address = $1 AND slot <= $2 \
+                ORDER BY slot DESC, write_version DESC LIMIT 1";
+
+            pub const ENQUEUE_WEBHOOK: &str = "\
+                INSERT INTO webhook_deliveries \
+                (id, url, event, payload, attempts, next_attempt_at, last_error, created_at) \
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8) \
+                ON CONFLICT (id) DO NOTHING";
+
+            pub const GET_DUE_WEBHOOKS: &str = "\
+                SELECT id, url, event, payload, attempts, next_attempt_at, last_error, created_at \
+                FROM webhook_deliveries WHERE next_attempt_at <= $1 \
+                ORDER BY next_attempt_at, id LIMIT $2";
+
+            pub const DELETE_WEBHOOK: &str =
+                "DELETE FROM webhook_deliveries WHERE id = $1";
+
+            pub const RETRY_WEBHOOK: &str = "\
+                UPDATE webhook_deliveries \
+                SET attempts = attempts + 1, next_attempt_at = $2, last_error = $3 \
+                WHERE id = $1";
+
+            pub const DEAD_LETTER_WEBHOOK: &str = "\
+                INSERT INTO webhook_dead_letters \
+                (id, url, event, payload, attempts, failed_at, last_error, created_at) \
+                SELECT id, url, event, payload, attempts + 1, $2, $3, created_at \
+                FROM webhook_deliveries WHERE id = $1 \
+                ON CONFLICT (id) DO NOTHING";
+
+            pub const GET_WEBHOOK_DEAD_LETTERS: &str = "\
+                SELECT id, url, event, payload, attempts, failed_at, last_error, created_at \
+                FROM webhook_dead_letters ORDER BY failed_at DESC, id DESC LIMIT $1";
+
+            pub const GET_BACKFILL_CURSOR: &str = "\
+                SELECT next_slot FROM backfill_progress \
+                WHERE range_start = $1 AND range_end = $2";
+
+            pub const SET_BACKFILL_CURSOR: &str = "\
+                INSERT INTO backfill_progress (range_start, range_end, next_slot, updated_at) \
+                VALUES ($1, $2, $3, $4) \
+                ON CONFLICT (range_start, range_end) DO UPDATE SET \
+                next_slot = $3, updated_at = $4";
         }
     };
 }
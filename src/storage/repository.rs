@@ -53,8 +53,12 @@ pub async fn run_postgres_migrations(pool: &PgPool) -> Result<()> {
 
 /// Shared field → domain mapping (one source of truth for SQLite and Postgres rows).
 pub mod mappers {
-    use crate::core::types::{AccountState, Slot, SlotStatus, Transaction};
+    use crate::core::types::{
+        AccountState, Instruction, Slot, SlotLifecycle, SlotStatus, SolBalanceChange,
+        TokenBalanceChange, Transaction, WebhookDelivery,
+    };
 
+    #[allow(clippy::too_many_arguments)]
     pub fn account(
         address: String,
         slot: i64,
@@ -63,6 +67,8 @@ pub mod mappers {
         executable: bool,
         data: Vec<u8>,
         rent_epoch: i64,
+        decoded_json: Option<String>,
+        write_version: i64,
     ) -> AccountState {
         AccountState {
             address,
@@ -72,9 +78,12 @@ pub mod mappers {
             executable,
             data,
             rent_epoch: rent_epoch as u64,
+            write_version: write_version as u64,
+            decoded: decoded_json.and_then(|d| serde_json::from_str(&d).ok()),
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn slot(
         slot: i64,
         timestamp: i64,
@@ -82,6 +91,9 @@ pub mod mappers {
         status: &str,
         block_hash: Option<String>,
         block_height: Option<i64>,
+        processed_at: Option<i64>,
+        confirmed_at: Option<i64>,
+        finalized_at: Option<i64>,
     ) -> Slot {
         Slot {
             slot: slot as u64,
@@ -90,9 +102,15 @@ pub mod mappers {
             status: status.parse().unwrap_or(SlotStatus::Processed),
             block_hash,
             block_height: block_height.map(|h| h as u64),
+            lifecycle: SlotLifecycle {
+                processed_at,
+                confirmed_at,
+                finalized_at,
+            },
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn transaction(
         signature: String,
         slot: i64,
@@ -100,6 +118,9 @@ pub mod mappers {
         fee: i64,
         success: bool,
         accounts_json: &str,
+        program: Option<String>,
+        instruction_count: Option<i64>,
+        compute_units: Option<i64>,
     ) -> Transaction {
         Transaction {
             signature,
@@ -108,6 +129,90 @@ pub mod mappers {
             fee: fee as u64,
             success,
             accounts: serde_json::from_str(accounts_json).unwrap_or_default(),
+            program,
+            instruction_count: instruction_count.map(|n| n as u32),
+            compute_units: compute_units.map(|cu| cu as u64),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn instruction(
+        signature: String,
+        slot: i64,
+        index: i32,
+        inner_index: Option<i32>,
+        stack_height: Option<i32>,
+        program_id: String,
+        accounts_json: &str,
+        data: Option<String>,
+        parsed_json: Option<String>,
+    ) -> Instruction {
+        Instruction {
+            signature,
+            slot: slot as u64,
+            index: index as u32,
+            inner_index: inner_index.map(|i| i as u32),
+            stack_height: stack_height.map(|h| h as u32),
+            program_id,
+            accounts: serde_json::from_str(accounts_json).unwrap_or_default(),
+            data,
+            parsed: parsed_json.and_then(|p| serde_json::from_str(&p).ok()),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn token_balance_change(
+        signature: String,
+        slot: i64,
+        account: String,
+        owner: Option<String>,
+        mint: String,
+        pre: &str,
+        post: &str,
+        decimals: i32,
+    ) -> TokenBalanceChange {
+        TokenBalanceChange {
+            signature,
+            slot: slot as u64,
+            account,
+            owner,
+            mint,
+            pre: pre.parse().unwrap_or_default(),
+            post: post.parse().unwrap_or_default(),
+            decimals: decimals as u8,
+        }
+    }
+
+    pub fn sol_balance_change(
+        signature: String,
+        slot: i64,
+        account: String,
+        pre: i64,
+        post: i64,
+    ) -> SolBalanceChange {
+        SolBalanceChange::new(signature, slot as u64, account, pre as u64, post as u64)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn webhook_delivery(
+        id: String,
+        url: String,
+        event: String,
+        payload: String,
+        attempts: i32,
+        next_attempt_at: i64,
+        last_error: Option<String>,
+        created_at: i64,
+    ) -> WebhookDelivery {
+        WebhookDelivery {
+            id,
+            url,
+            event,
+            payload,
+            attempts: attempts as u32,
+            next_attempt_at,
+            last_error,
+            created_at,
         }
     }
 
@@ -121,6 +226,8 @@ macro_rules! row_mappers {
         pub mod $mod_name {
             use sqlx::Row;
 
+            pub type Db = <$row as sqlx::Row>::Database;
+
             pub fn map_account(row: &$row) -> crate::core::types::AccountState {
                 crate::storage::repository::mappers::account(
                     row.get(0),
@@ -130,6 +237,8 @@ macro_rules! row_mappers {
                     row.get(4),
                     row.get(5),
                     row.get(6),
+                    row.get(7),
+                    row.get(8),
                 )
             }
 
@@ -141,6 +250,9 @@ macro_rules! row_mappers {
                     row.get(3),
                     row.get(4),
                     row.get(5),
+                    row.get(6),
+                    row.get(7),
+                    row.get(8),
                 )
             }
 
@@ -152,6 +264,61 @@ macro_rules! row_mappers {
                     row.get(3),
                     row.get(4),
                     row.get(5),
+                    row.get(6),
+                    row.get(7),
+                    row.get(8),
+                )
+            }
+
+            pub fn map_instruction(row: &$row) -> crate::core::types::Instruction {
+                crate::storage::repository::mappers::instruction(
+                    row.get(0),
+                    row.get(1),
+                    row.get(2),
+                    row.get(3),
+                    row.get(4),
+                    row.get(5),
+                    row.get(6),
+                    row.get(7),
+                    row.get(8),
+                )
+            }
+
+            pub fn map_token_balance_change(
+                row: &$row,
+            ) -> crate::core::types::TokenBalanceChange {
+                crate::storage::repository::mappers::token_balance_change(
+                    row.get(0),
+                    row.get(1),
+                    row.get(2),
+                    row.get(3),
+                    row.get(4),
+                    row.get(5),
+                    row.get(6),
+                    row.get(7),
+                )
+            }
+
+            pub fn map_sol_balance_change(row: &$row) -> crate::core::types::SolBalanceChange {
+                crate::storage::repository::mappers::sol_balance_change(
+                    row.get(0),
+                    row.get(1),
+                    row.get(2),
+                    row.get(3),
+                    row.get(4),
+                )
+            }
+
+            pub fn map_webhook_delivery(row: &$row) -> crate::core::types::WebhookDelivery {
+                crate::storage::repository::mappers::webhook_delivery(
+                    row.get(0),
+                    row.get(1),
+                    row.get(2),
+                    row.get(3),
+                    row.get(4),
+                    row.get(5),
+                    row.get(6),
+                    row.get(7),
                 )
             }
 
@@ -177,16 +344,8 @@ macro_rules! impl_database_storage {
                 &self,
                 slot: &$crate::core::types::Slot,
             ) -> $crate::utils::errors::Result<()> {
-                sqlx::query($crate::storage::queries::$queries::STORE_SLOT)
-                    .bind(slot.slot as i64)
-                    .bind(slot.timestamp)
-                    .bind(slot.parent.map(|p| p as i64))
-                    .bind(slot.status.as_str())
-                    .bind(&slot.block_hash)
-                    .bind(slot.block_height.map(|h| h as i64))
-                    .execute(&self.pool)
-                    .await?;
-                Ok(())
+                let mut conn = self.pool.acquire().await?;
+                Self::write_slot(&mut *conn, slot).await
             }
 
             async fn store_account(
@@ -202,11 +361,87 @@ macro_rules! impl_database_storage {
                     .bind(&a.data)
                     .bind(a.rent_epoch as i64)
                     .bind(chrono::Utc::now().timestamp())
+                    .bind(a.decoded.as_ref().map(serde_json::to_string).transpose()?)
+                    .bind(a.write_version as i64)
+                    .execute(&self.pool)
+                    .await?;
+                Ok(())
+            }
+
+            async fn append_account_history(
+                &self,
+                a: &$crate::core::types::AccountState,
+            ) -> $crate::utils::errors::Result<()> {
+                sqlx::query($crate::storage::queries::$queries::APPEND_ACCOUNT_HISTORY)
+                    .bind(&a.address)
+                    .bind(a.slot as i64)
+                    .bind(a.write_version as i64)
+                    .bind(a.lamports as i64)
+                    .bind(&a.owner)
+                    .bind(a.executable)
+                    .bind(&a.data)
+                    .bind(a.rent_epoch as i64)
+                    .bind(a.decoded.as_ref().map(serde_json::to_string).transpose()?)
+                    .bind(chrono::Utc::now().timestamp())
                     .execute(&self.pool)
                     .await?;
                 Ok(())
             }
 
+            async fn get_account_history(
+                &self,
+                address: &str,
+                range: $crate::storage::database::AccountHistoryRange,
+            ) -> $crate::utils::errors::Result<
+                $crate::storage::database::Page<$crate::core::types::AccountState>,
+            > {
+                let (before_slot, before_version) = range
+                    .cursor
+                    .map(|c| (c.slot as i64, c.write_version as i64))
+                    .unwrap_or((i64::MAX, i64::MAX));
+                let rows = sqlx::query($crate::storage::queries::$queries::GET_ACCOUNT_HISTORY)
+                    .bind(address)
+                    .bind(range.from_slot.map_or(0, |s| s as i64))
+                    .bind(range.to_slot.map_or(i64::MAX, |s| s as i64))
+                    .bind(before_slot)
+                    .bind(before_version)
+                    .bind(i64::from(range.limit) + 1)
+                    .fetch_all(&self.pool)
+                    .await?;
+                let accounts = rows
+                    .iter()
+                    .map($crate::storage::repository::$mapper::map_account)
+                    .collect();
+                Ok($crate::storage::database::Page::from_overfetch(
+                    accounts,
+                    range.limit,
+                    |a| {
+                        $crate::storage::database::AccountVersion {
+                            slot: a.slot,
+                            write_version: a.write_version,
+                        }
+                        .to_string()
+                    },
+                ))
+            }
+
+            async fn get_account_at_slot(
+                &self,
+                address: &str,
+                slot: u64,
+            ) -> $crate::utils::errors::Result<
+                Option<$crate::core::types::AccountState>,
+            > {
+                let row = sqlx::query($crate::storage::queries::$queries::GET_ACCOUNT_AT_SLOT)
+                    .bind(address)
+                    .bind(slot as i64)
+                    .fetch_optional(&self.pool)
+                    .await?;
+                Ok(row
+                    .as_ref()
+                    .map($crate::storage::repository::$mapper::map_account))
+            }
+
             async fn get_account(
                 &self,
                 address: &str,
@@ -222,6 +457,46 @@ macro_rules! impl_database_storage {
                     .map($crate::storage::repository::$mapper::map_account))
             }
 
+            async fn get_accounts(
+                &self,
+                addresses: &[String],
+            ) -> $crate::utils::errors::Result<Vec<$crate::core::types::AccountState>> {
+                if addresses.is_empty() {
+                    return Ok(Vec::new());
+                }
+                let rows = sqlx::query($crate::storage::queries::$queries::GET_ACCOUNTS)
+                    .bind(serde_json::to_string(addresses)?)
+                    .fetch_all(&self.pool)
+                    .await?;
+                Ok(rows
+                    .iter()
+                    .map($crate::storage::repository::$mapper::map_account)
+                    .collect())
+            }
+
+            async fn get_program_accounts(
+                &self,
+                program: &str,
+                limit: u32,
+                cursor: Option<String>,
+            ) -> $crate::utils::errors::Result<
+                $crate::storage::database::Page<$crate::core::types::AccountState>,
+            > {
+                let rows = sqlx::query($crate::storage::queries::$queries::GET_PROGRAM_ACCOUNTS)
+                    .bind(program)
+                    .bind(cursor.unwrap_or_default())
+                    .bind(i64::from(limit) + 1)
+                    .fetch_all(&self.pool)
+                    .await?;
+                let accounts = rows
+                    .iter()
+                    .map($crate::storage::repository::$mapper::map_account)
+                    .collect();
+                Ok($crate::storage::database::Page::from_overfetch(accounts, limit, |a| {
+                    a.address.clone()
+                }))
+            }
+
             async fn get_slot(
                 &self,
                 slot: u64,
@@ -246,20 +521,82 @@ macro_rules! impl_database_storage {
                     .map($crate::storage::repository::$mapper::map_slot))
             }
 
+            async fn get_slots_range(
+                &self,
+                range: $crate::storage::database::SlotRange,
+            ) -> $crate::utils::errors::Result<
+                $crate::storage::database::Page<$crate::core::types::Slot>,
+            > {
+                let rows = sqlx::query($crate::storage::queries::$queries::GET_SLOTS_RANGE)
+                    .bind(range.from.unwrap_or(0) as i64)
+                    .bind(range.to.map(|t| t as i64).unwrap_or(i64::MAX))
+                    .bind(range.cursor.map(|c| c as i64).unwrap_or(i64::MAX))
+                    .bind(i64::from(range.limit) + 1)
+                    .fetch_all(&self.pool)
+                    .await?;
+                let slots = rows
+                    .iter()
+                    .map($crate::storage::repository::$mapper::map_slot)
+                    .collect();
+                Ok($crate::storage::database::Page::from_overfetch(
+                    slots,
+                    range.limit,
+                    |s| s.slot.to_string(),
+                ))
+            }
+
+            async fn get_slot_gaps(
+                &self,
+                from: u64,
+                to: u64,
+            ) -> $crate::utils::errors::Result<Vec<(u64, u64)>> {
+                let rows = sqlx::query($crate::storage::queries::$queries::GET_SLOT_GAPS)
+                    .bind(from as i64)
+                    .bind(to as i64)
+                    .fetch_all(&self.pool)
+                    .await?;
+                Ok(rows
+                    .iter()
+                    .map(|r| {
+                        (
+                            sqlx::Row::get::<i64, _>(r, 0) as u64,
+                            sqlx::Row::get::<i64, _>(r, 1) as u64,
+                        )
+                    })
+                    .collect())
+            }
+
             async fn store_transaction(
                 &self,
                 tx: $crate::core::types::Transaction,
             ) -> $crate::utils::errors::Result<()> {
-                let accounts = serde_json::to_string(&tx.accounts)?;
-                sqlx::query($crate::storage::queries::$queries::STORE_TRANSACTION)
-                    .bind(&tx.signature)
-                    .bind(tx.slot as i64)
-                    .bind(tx.block_time)
-                    .bind(tx.fee as i64)
-                    .bind(tx.success)
-                    .bind(&accounts)
-                    .execute(&self.pool)
-                    .await?;
+                let mut db_tx = self.pool.begin().await?;
+                Self::write_transaction(&mut *db_tx, &tx).await?;
+                db_tx.commit().await?;
+                Ok(())
+            }
+
+            async fn store_block_batch(
+                &self,
+                slots: &[$crate::core::types::Slot],
+                transactions: &[$crate::core::types::TransactionInfo],
+            ) -> $crate::utils::errors::Result<()> {
+                let mut db_tx = self.pool.begin().await?;
+                for slot in slots {
+                    Self::write_slot(&mut *db_tx, slot).await?;
+                }
+                for tx in transactions {
+                    let signature = &tx.signature;
+                    let summary = $crate::core::types::Transaction::from(tx.clone());
+                    Self::write_transaction(&mut *db_tx, &summary).await?;
+                    Self::write_instructions(&mut *db_tx, signature, &tx.instruction_details)
+                        .await?;
+                    Self::write_token_balance_changes(&mut *db_tx, signature, &tx.token_balance_changes)
+                        .await?;
+                    Self::write_sol_balance_changes(&mut *db_tx, signature, &tx.sol_balance_changes)
+                        .await?;
+                }
+                db_tx.commit().await?;
                 Ok(())
             }
 
@@ -276,6 +613,320 @@ macro_rules! impl_database_storage {
                     .map($crate::storage::repository::$mapper::map_transaction))
             }
 
+            async fn get_transactions_in_slot(
+                &self,
+                slot: u64,
+                limit: u32,
+                cursor: Option<String>,
+            ) -> $crate::utils::errors::Result<
+                $crate::storage::database::Page<$crate::core::types::Transaction>,
+            > {
+                let rows = sqlx::query($crate::storage::queries::$queries::GET_SLOT_TRANSACTIONS)
+                    .bind(slot as i64)
+                    .bind(cursor.unwrap_or_default())
+                    .bind(i64::from(limit) + 1)
+                    .fetch_all(&self.pool)
+                    .await?;
+                let txs = rows
+                    .iter()
+                    .map($crate::storage::repository::$mapper::map_transaction)
+                    .collect();
+                Ok($crate::storage::database::Page::from_overfetch(txs, limit, |tx| {
+                    tx.signature.clone()
+                }))
+            }
+
+            async fn count_transactions_in_slot(
+                &self,
+                slot: u64,
+            ) -> $crate::utils::errors::Result<u64> {
+                let row = sqlx::query($crate::storage::queries::$queries::COUNT_SLOT_TRANSACTIONS)
+                    .bind(slot as i64)
+                    .fetch_one(&self.pool)
+                    .await?;
+                Ok(sqlx::Row::get::<i64, _>(&row, 0) as u64)
+            }
+
+            async fn get_transactions_for_account(
+                &self,
+                address: &str,
+                limit: u32,
+                cursor: Option<$crate::storage::database::TxCursor>,
+            ) -> $crate::utils::errors::Result<
+                $crate::storage::database::Page<$crate::core::types::Transaction>,
+            > {
+                let (before_slot, before_sig) = cursor
+                    .map(|c| (c.slot as i64, c.signature))
+                    .unwrap_or((i64::MAX, String::new()));
+                let rows = sqlx::query($crate::storage::queries::$queries::GET_ACCOUNT_TRANSACTIONS)
+                    .bind(address)
+                    .bind(before_slot)
+                    .bind(&before_sig)
+                    .bind(i64::from(limit) + 1)
+                    .fetch_all(&self.pool)
+                    .await?;
+                let txs = rows
+                    .iter()
+                    .map($crate::storage::repository::$mapper::map_transaction)
+                    .collect();
+                Ok($crate::storage::database::Page::from_overfetch(txs, limit, |tx| {
+                    $crate::storage::database::TxCursor {
+                        slot: tx.slot,
+                        signature: tx.signature.clone(),
+                    }
+                    .to_string()
+                }))
+            }
+
+            async fn get_transactions_for_program(
+                &self,
+                program: &str,
+                limit: u32,
+                cursor: Option<$crate::storage::database::TxCursor>,
+            ) -> $crate::utils::errors::Result<
+                $crate::storage::database::Page<$crate::core::types::Transaction>,
+            > {
+                let (before_slot, before_sig) = cursor
+                    .map(|c| (c.slot as i64, c.signature))
+                    .unwrap_or((i64::MAX, String::new()));
+                let rows = sqlx::query($crate::storage::queries::$queries::GET_PROGRAM_TRANSACTIONS)
+                    .bind(program)
+                    .bind(before_slot)
+                    .bind(&before_sig)
+                    .bind(i64::from(limit) + 1)
+                    .fetch_all(&self.pool)
+                    .await?;
+                let txs = rows
+                    .iter()
+                    .map($crate::storage::repository::$mapper::map_transaction)
+                    .collect();
+                Ok($crate::storage::database::Page::from_overfetch(txs, limit, |tx| {
+                    $crate::storage::database::TxCursor {
+                        slot: tx.slot,
+                        signature: tx.signature.clone(),
+                    }
+                    .to_string()
+                }))
+            }
+
+            async fn store_instructions(
+                &self,
+                signature: &str,
+                instructions: &[$crate::core::types::Instruction],
+            ) -> $crate::utils::errors::Result<()> {
+                let mut db_tx = self.pool.begin().await?;
+                Self::write_instructions(&mut *db_tx, signature, instructions).await?;
+                db_tx.commit().await?;
+                Ok(())
+            }
+
+            async fn get_instructions(
+                &self,
+                signature: &str,
+            ) -> $crate::utils::errors::Result<Vec<$crate::core::types::Instruction>> {
+                let rows = sqlx::query($crate::storage::queries::$queries::GET_TRANSACTION_INSTRUCTIONS)
+                    .bind(signature)
+                    .fetch_all(&self.pool)
+                    .await?;
+                Ok(rows
+                    .iter()
+                    .map($crate::storage::repository::$mapper::map_instruction)
+                    .collect())
+            }
+
+            async fn store_token_balance_changes(
+                &self,
+                signature: &str,
+                changes: &[$crate::core::types::TokenBalanceChange],
+            ) -> $crate::utils::errors::Result<()> {
+                let mut db_tx = self.pool.begin().await?;
+                Self::write_token_balance_changes(&mut *db_tx, signature, changes).await?;
+                db_tx.commit().await?;
+                Ok(())
+            }
+
+            async fn get_token_balance_changes_for_owner(
+                &self,
+                owner: &str,
+                limit: u32,
+                cursor: Option<$crate::storage::database::TokenChangeCursor>,
+            ) -> $crate::utils::errors::Result<
+                $crate::storage::database::Page<$crate::core::types::TokenBalanceChange>,
+            > {
+                let (before_slot, before_sig, before_account) = cursor
+                    .map(|c| (c.tx.slot as i64, c.tx.signature, c.account))
+                    .unwrap_or((i64::MAX, String::new(), String::new()));
+                let rows = sqlx::query($crate::storage::queries::$queries::GET_OWNER_TOKEN_BALANCE_CHANGES)
+                    .bind(owner)
+                    .bind(before_slot)
+                    .bind(&before_sig)
+                    .bind(&before_account)
+                    .bind(i64::from(limit) + 1)
+                    .fetch_all(&self.pool)
+                    .await?;
+                let changes = rows
+                    .iter()
+                    .map($crate::storage::repository::$mapper::map_token_balance_change)
+                    .collect();
+                Ok($crate::storage::database::Page::from_overfetch(changes, limit, |c| {
+                    $crate::storage::database::TokenChangeCursor {
+                        tx: $crate::storage::database::TxCursor {
+                            slot: c.slot,
+                            signature: c.signature.clone(),
+                        },
+                        account: c.account.clone(),
+                    }
+                    .to_string()
+                }))
+            }
+
+            async fn store_sol_balance_changes(
+                &self,
+                signature: &str,
+                changes: &[$crate::core::types::SolBalanceChange],
+            ) -> $crate::utils::errors::Result<()> {
+                let mut db_tx = self.pool.begin().await?;
+                Self::write_sol_balance_changes(&mut *db_tx, signature, changes).await?;
+                db_tx.commit().await?;
+                Ok(())
+            }
+
+            async fn get_sol_balance_changes(
+                &self,
+                signature: &str,
+            ) -> $crate::utils::errors::Result<Vec<$crate::core::types::SolBalanceChange>> {
+                let rows = sqlx::query($crate::storage::queries::$queries::GET_TRANSACTION_SOL_BALANCE_CHANGES)
+                    .bind(signature)
+                    .fetch_all(&self.pool)
+                    .await?;
+                Ok(rows
+                    .iter()
+                    .map($crate::storage::repository::$mapper::map_sol_balance_change)
+                    .collect())
+            }
+
+            async fn get_sol_balance_history(
+                &self,
+                address: &str,
+                limit: u32,
+                cursor: Option<$crate::storage::database::TxCursor>,
+            ) -> $crate::utils::errors::Result<
+                $crate::storage::database::Page<$crate::core::types::SolBalanceChange>,
+            > {
+                let (before_slot, before_sig) = cursor
+                    .map(|c| (c.slot as i64, c.signature))
+                    .unwrap_or((i64::MAX, String::new()));
+                let rows = sqlx::query($crate::storage::queries::$queries::GET_ACCOUNT_SOL_BALANCE_CHANGES)
+                    .bind(address)
+                    .bind(before_slot)
+                    .bind(&before_sig)
+                    .bind(i64::from(limit) + 1)
+                    .fetch_all(&self.pool)
+                    .await?;
+                let changes = rows
+                    .iter()
+                    .map($crate::storage::repository::$mapper::map_sol_balance_change)
+                    .collect();
+                Ok($crate::storage::database::Page::from_overfetch(changes, limit, |c| {
+                    $crate::storage::database::TxCursor {
+                        slot: c.slot,
+                        signature: c.signature.clone(),
+                    }
+                    .to_string()
+                }))
+            }
+
+            async fn enqueue_webhook(
+                &self,
+                delivery: &$crate::core::types::WebhookDelivery,
+            ) -> $crate::utils::errors::Result<()> {
+                sqlx::query($crate::storage::queries::$queries::ENQUEUE_WEBHOOK)
+                    .bind(&delivery.id)
+                    .bind(&delivery.url)
+                    .bind(&delivery.event)
+                    .bind(&delivery.payload)
+                    .bind(delivery.attempts as i32)
+                    .bind(delivery.next_attempt_at)
+                    .bind(delivery.last_error.as_deref())
+                    .bind(delivery.created_at)
+                    .execute(&self.pool)
+                    .await?;
+                Ok(())
+            }
+
+            async fn get_due_webhooks(
+                &self,
+                now: i64,
+                limit: u32,
+            ) -> $crate::utils::errors::Result<Vec<$crate::core::types::WebhookDelivery>> {
+                let rows = sqlx::query($crate::storage::queries::$queries::GET_DUE_WEBHOOKS)
+                    .bind(now)
+                    .bind(i64::from(limit))
+                    .fetch_all(&self.pool)
+                    .await?;
+                Ok(rows
+                    .iter()
+                    .map($crate::storage::repository::$mapper::map_webhook_delivery)
+                    .collect())
+            }
+
+            async fn complete_webhook(&self, id: &str) -> $crate::utils::errors::Result<()> {
+                sqlx::query($crate::storage::queries::$queries::DELETE_WEBHOOK)
+                    .bind(id)
+                    .execute(&self.pool)
+                    .await?;
+                Ok(())
+            }
+
+            async fn retry_webhook(
+                &self,
+                id: &str,
+                next_attempt_at: i64,
+                error: &str,
+            ) -> $crate::utils::errors::Result<()> {
+                sqlx::query($crate::storage::queries::$queries::RETRY_WEBHOOK)
+                    .bind(id)
+                    .bind(next_attempt_at)
+                    .bind(error)
+                    .execute(&self.pool)
+                    .await?;
+                Ok(())
+            }
+
+            async fn dead_letter_webhook(
+                &self,
+                id: &str,
+                error: &str,
+            ) -> $crate::utils::errors::Result<()> {
+                let mut db_tx = self.pool.begin().await?;
+                sqlx::query($crate::storage::queries::$queries::DEAD_LETTER_WEBHOOK)
+                    .bind(id)
+                    .bind(chrono::Utc::now().timestamp())
+                    .bind(error)
+                    .execute(&mut *db_tx)
+                    .await?;
+                sqlx::query($crate::storage::queries::$queries::DELETE_WEBHOOK)
+                    .bind(id)
+                    .execute(&mut *db_tx)
+                    .await?;
+                db_tx.commit().await?;
+                Ok(())
+            }
+
+            async fn get_webhook_dead_letters(
+                &self,
+                limit: u32,
+            ) -> $crate::utils::errors::Result<Vec<$crate::core::types::WebhookDelivery>> {
+                let rows = sqlx::query($crate::storage::queries::$queries::GET_WEBHOOK_DEAD_LETTERS)
+                    .bind(i64::from(limit))
+                    .fetch_all(&self.pool)
+                    .await?;
+                Ok(rows
+                    .iter()
+                    .map($crate::storage::repository::$mapper::map_webhook_delivery)
+                    .collect())
+            }
+
             async fn add_wallet(
                 &self,
                 address: String,
@@ -314,6 +965,33 @@ macro_rules! impl_database_storage {
                     .collect())
             }
 
+            async fn list_wallets_page(
+                &self,
+                active_only: bool,
+                limit: u32,
+                cursor: Option<String>,
+            ) -> $crate::utils::errors::Result<
+                $crate::storage::database::Page<(String, Option<String>, i64)>,
+            > {
+                let sql = if active_only {
+                    $crate::storage::queries::$queries::LIST_WALLETS_PAGE_ACTIVE
+                } else {
+                    $crate::storage::queries::$queries::LIST_WALLETS_PAGE_ALL
+                };
+                let rows = sqlx::query(sql)
+                    .bind(cursor.unwrap_or_default())
+                    .bind(i64::from(limit) + 1)
+                    .fetch_all(&self.pool)
+                    .await?;
+                let wallets = rows
+                    .iter()
+                    .map($crate::storage::repository::$mapper::map_wallet)
+                    .collect();
+                Ok($crate::storage::database::Page::from_overfetch(wallets, limit, |w| {
+                    w.0.clone()
+                }))
+            }
+
             async fn get_active_wallets(&self) -> $crate::utils::errors::Result<Vec<String>> {
                 let rows = sqlx::query($crate::storage::queries::$queries::GET_ACTIVE_WALLETS)
                     .fetch_all(&self.pool)
@@ -328,6 +1006,20 @@ macro_rules! impl_database_storage {
                 Ok(row.as_ref().map(|r| sqlx::Row::get::<i64, _>(r, 0) as u64))
             }
 
+            async fn get_checkpoint_status(
+                &self,
+            ) -> $crate::utils::errors::Result<Option<(u64, i64)>> {
+                let row = sqlx::query($crate::storage::queries::$queries::GET_CHECKPOINT_STATUS)
+                    .fetch_optional(&self.pool)
+                    .await?;
+                Ok(row.as_ref().map(|r| {
+                    (
+                        sqlx::Row::get::<i64, _>(r, 0) as u64,
+                        sqlx::Row::get::<i64, _>(r, 1),
+                    )
+                }))
+            }
+
             async fn set_checkpoint(&self, slot: u64) -> $crate::utils::errors::Result<()> {
                 sqlx::query($crate::storage::queries::$queries::SET_CHECKPOINT)
                     .bind(slot as i64)
@@ -336,6 +1028,204 @@ macro_rules! impl_database_storage {
                     .await?;
                 Ok(())
             }
+
+            async fn get_backfill_cursor(
+                &self,
+                from: u64,
+                to: u64,
+            ) -> $crate::utils::errors::Result<Option<u64>> {
+                let row = sqlx::query($crate::storage::queries::$queries::GET_BACKFILL_CURSOR)
+                    .bind(from as i64)
+                    .bind(to as i64)
+                    .fetch_optional(&self.pool)
+                    .await?;
+                Ok(row.as_ref().map(|r| sqlx::Row::get::<i64, _>(r, 0) as u64))
+            }
+
+            async fn set_backfill_cursor(
+                &self,
+                from: u64,
+                to: u64,
+                next_slot: u64,
+            ) -> $crate::utils::errors::Result<()> {
+                sqlx::query($crate::storage::queries::$queries::SET_BACKFILL_CURSOR)
+                    .bind(from as i64)
+                    .bind(to as i64)
+                    .bind(next_slot as i64)
+                    .bind(chrono::Utc::now().timestamp())
+                    .execute(&self.pool)
+                    .await?;
+                Ok(())
+            }
+
+            async fn rollback_slot(&self, slot: u64) -> $crate::utils::errors::Result<u64> {
+                let mut db_tx = self.pool.begin().await?;
+                sqlx::query($crate::storage::queries::$queries::MARK_SLOT_DEAD)
+                    .bind(slot as i64)
+                    .execute(&mut *db_tx)
+                    .await?;
+                sqlx::query($crate::storage::queries::$queries::DELETE_SLOT_TRANSACTION_ACCOUNTS)
+                    .bind(slot as i64)
+                    .execute(&mut *db_tx)
+                    .await?;
+                sqlx::query($crate::storage::queries::$queries::DELETE_SLOT_INSTRUCTIONS)
+                    .bind(slot as i64)
+                    .execute(&mut *db_tx)
+                    .await?;
+                sqlx::query($crate::storage::queries::$queries::DELETE_SLOT_TOKEN_BALANCE_CHANGES)
+                    .bind(slot as i64)
+                    .execute(&mut *db_tx)
+                    .await?;
+                sqlx::query($crate::storage::queries::$queries::DELETE_SLOT_SOL_BALANCE_CHANGES)
+                    .bind(slot as i64)
+                    .execute(&mut *db_tx)
+                    .await?;
+                sqlx::query($crate::storage::queries::$queries::DELETE_SLOT_ACCOUNT_HISTORY)
+                    .bind(slot as i64)
+                    .execute(&mut *db_tx)
+                    .await?;
+                let removed = sqlx::query($crate::storage::queries::$queries::DELETE_SLOT_TRANSACTIONS)
+                    .bind(slot as i64)
+                    .execute(&mut *db_tx)
+                    .await?
+                    .rows_affected();
+                db_tx.commit().await?;
+                Ok(removed)
+            }
+        }
+
+        impl $storage {
+            /// Upserts `slot` on `conn`; shared by single and batched writes.
+            async fn write_slot(
+                conn: &mut <$crate::storage::repository::$mapper::Db as sqlx::Database>::Connection,
+                slot: &$crate::core::types::Slot,
+            ) -> $crate::utils::errors::Result<()> {
+                sqlx::query($crate::storage::queries::$queries::STORE_SLOT)
+                    .bind(slot.slot as i64)
+                    .bind(slot.timestamp)
+                    .bind(slot.parent.map(|p| p as i64))
+                    .bind(slot.status.as_str())
+                    .bind(&slot.block_hash)
+                    .bind(slot.block_height.map(|h| h as i64))
+                    .bind(slot.lifecycle.processed_at)
+                    .bind(slot.lifecycle.confirmed_at)
+                    .bind(slot.lifecycle.finalized_at)
+                    .execute(&mut *conn)
+                    .await?;
+                Ok(())
+            }
+
+            /// Upserts `tx` and rewrites its account index rows on `conn`; shared by single and
+            /// batched writes, which supply the surrounding transaction.
+            async fn write_transaction(
+                conn: &mut <$crate::storage::repository::$mapper::Db as sqlx::Database>::Connection,
+                tx: &$crate::core::types::Transaction,
+            ) -> $crate::utils::errors::Result<()> {
+                sqlx::query($crate::storage::queries::$queries::STORE_TRANSACTION)
+                    .bind(&tx.signature)
+                    .bind(tx.slot as i64)
+                    .bind(tx.block_time)
+                    .bind(tx.fee as i64)
+                    .bind(tx.success)
+                    .bind(serde_json::to_string(&tx.accounts)?)
+                    .bind(&tx.program)
+                    .bind(tx.instruction_count.map(i64::from))
+                    .bind(tx.compute_units.map(|cu| cu as i64))
+                    .execute(&mut *conn)
+                    .await?;
+                sqlx::query($crate::storage::queries::$queries::DELETE_TRANSACTION_ACCOUNTS)
+                    .bind(&tx.signature)
+                    .execute(&mut *conn)
+                    .await?;
+                for account in &tx.accounts {
+                    sqlx::query($crate::storage::queries::$queries::STORE_TRANSACTION_ACCOUNT)
+                        .bind(account)
+                        .bind(&tx.signature)
+                        .bind(tx.slot as i64)
+                        .execute(&mut *conn)
+                        .await?;
+                }
+                Ok(())
+            }
+
+            /// Replaces the instruction rows of `signature` on `conn`.
+            async fn write_instructions(
+                conn: &mut <$crate::storage::repository::$mapper::Db as sqlx::Database>::Connection,
+                signature: &str,
+                instructions: &[$crate::core::types::Instruction],
+            ) -> $crate::utils::errors::Result<()> {
+                sqlx::query($crate::storage::queries::$queries::DELETE_TRANSACTION_INSTRUCTIONS)
+                    .bind(signature)
+                    .execute(&mut *conn)
+                    .await?;
+                for (position, ix) in instructions.iter().enumerate() {
+                    let parsed = ix.parsed.as_ref().map(serde_json::to_string).transpose()?;
+                    sqlx::query($crate::storage::queries::$queries::STORE_INSTRUCTION)
+                        .bind(signature)
+                        .bind(position as i32)
+                        .bind(ix.slot as i64)
+                        .bind(ix.index as i32)
+                        .bind(ix.inner_index.map(|i| i as i32))
+                        .bind(ix.stack_height.map(|h| h as i32))
+                        .bind(&ix.program_id)
+                        .bind(serde_json::to_string(&ix.accounts)?)
+                        .bind(&ix.data)
+                        .bind(parsed)
+                        .execute(&mut *conn)
+                        .await?;
+                }
+                Ok(())
+            }
+
+            /// Replaces the token balance changes of `signature` on `conn`.
+            async fn write_token_balance_changes(
+                conn: &mut <$crate::storage::repository::$mapper::Db as sqlx::Database>::Connection,
+                signature: &str,
+                changes: &[$crate::core::types::TokenBalanceChange],
+            ) -> $crate::utils::errors::Result<()> {
+                sqlx::query($crate::storage::queries::$queries::DELETE_TRANSACTION_TOKEN_BALANCE_CHANGES)
+                    .bind(signature)
+                    .execute(&mut *conn)
+                    .await?;
+                for change in changes {
+                    sqlx::query($crate::storage::queries::$queries::STORE_TOKEN_BALANCE_CHANGE)
+                        .bind(signature)
+                        .bind(&change.account)
+                        .bind(change.slot as i64)
+                        .bind(&change.owner)
+                        .bind(&change.mint)
+                        .bind(change.pre.to_string())
+                        .bind(change.post.to_string())
+                        .bind(i32::from(change.decimals))
+                        .execute(&mut *conn)
+                        .await?;
+                }
+                Ok(())
+            }
+
+            /// Replaces the SOL balance changes of `signature` on `conn`.
+            async fn write_sol_balance_changes(
+                conn: &mut <$crate::storage::repository::$mapper::Db as sqlx::Database>::Connection,
+                signature: &str,
+                changes: &[$crate::core::types::SolBalanceChange],
+            ) -> $crate::utils::errors::Result<()> {
+                sqlx::query($crate::storage::queries::$queries::DELETE_TRANSACTION_SOL_BALANCE_CHANGES)
+                    .bind(signature)
+                    .execute(&mut *conn)
+                    .await?;
+                for (position, change) in changes.iter().enumerate() {
+                    sqlx::query($crate::storage::queries::$queries::STORE_SOL_BALANCE_CHANGE)
+                        .bind(signature)
+                        .bind(&change.account)
+                        .bind(change.slot as i64)
+                        .bind(position as i32)
+                        .bind(change.pre as i64)
+                        .bind(change.post as i64)
+                        .execute(&mut *conn)
+                        .await?;
+                }
+                Ok(())
+            }
         }
     };
 }
@@ -29,8 +29,11 @@ crate::impl_database_storage!(SqliteStorage, sqlite, sqlite);
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::types::{Slot, SlotStatus};
-    use crate::storage::database::DatabaseStorage;
+    use crate::core::types::{
+        AccountState, DecodedAccount, Instruction, Slot, SlotLifecycle, SlotStatus,
+        SolBalanceChange, TokenBalanceChange, Transaction, TransactionInfo,
+    };
+    use crate::storage::database::{AccountHistoryRange, DatabaseStorage, SlotRange};
 
     #[tokio::test]
     async fn sqlite_persists_and_reads_slot() {
@@ -46,6 +49,7 @@ mod tests {
             timestamp: 1_700_000_000,
             block_hash: Some("abc".into()),
             block_height: Some(100),
+            lifecycle: SlotLifecycle::default(),
         };
 
         storage.store_slot(&slot).await.unwrap();
@@ -53,4 +57,630 @@ mod tests {
         assert_eq!(loaded.slot, 99);
         assert_eq!(loaded.block_hash.as_deref(), Some("abc"));
     }
+
+    #[tokio::test]
+    async fn sqlite_persists_decoded_account_data() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = SqliteStorage::new(dir.path().join("test.db"))
+            .await
+            .expect("sqlite should init");
+
+        let mut account = crate::testing::mock_sources::sample_account("addr1", 10);
+        account.data = vec![1, 2, 3];
+        account.decoded = Some(DecodedAccount {
+            program: "custom".into(),
+            parsed: serde_json::json!({ "type": "counter", "info": { "count": 3 } }),
+        });
+        storage.store_account(account.clone()).await.unwrap();
+
+        let loaded = storage.get_account("addr1").await.unwrap().expect("stored");
+        assert_eq!(loaded.data, vec![1, 2, 3]);
+        assert_eq!(loaded.decoded, account.decoded);
+
+        let mut other = crate::testing::mock_sources::sample_account("addr0", 10);
+        other.owner = "OtherProgram".into();
+        storage.store_account(other).await.unwrap();
+        let owned = storage
+            .get_program_accounts(&account.owner, 10, None)
+            .await
+            .unwrap();
+        assert_eq!(owned.items.len(), 1);
+        assert_eq!(owned.items[0].decoded, account.decoded);
+    }
+
+    #[tokio::test]
+    async fn sqlite_reads_several_accounts_in_one_query() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = SqliteStorage::new(dir.path().join("test.db"))
+            .await
+            .expect("sqlite should init");
+
+        for address in ["addr1", "addr2", "addr3"] {
+            let account = crate::testing::mock_sources::sample_account(address, 10);
+            storage.store_account(account).await.unwrap();
+        }
+
+        let keys = ["addr3", "unknown", "addr1"].map(String::from);
+        let mut found: Vec<_> = storage
+            .get_accounts(&keys)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|a| a.address)
+            .collect();
+        found.sort();
+        assert_eq!(found, vec!["addr1", "addr3"]);
+        assert!(storage.get_accounts(&[]).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn sqlite_range_query_pages_by_cursor() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = SqliteStorage::new(dir.path().join("test.db"))
+            .await
+            .expect("sqlite should init");
+        for n in 10..20 {
+            storage
+                .store_slot(&crate::testing::fixtures::sample_slot(n))
+                .await
+                .unwrap();
+        }
+
+        let range = SlotRange {
+            from: Some(12),
+            to: Some(17),
+            limit: 4,
+            cursor: None,
+        };
+        let first = storage.get_slots_range(range).await.unwrap();
+        let numbers: Vec<u64> = first.items.iter().map(|s| s.slot).collect();
+        assert_eq!(numbers, vec![17, 16, 15, 14]);
+        assert_eq!(first.next_cursor.as_deref(), Some("14"));
+
+        let second = storage
+            .get_slots_range(SlotRange {
+                cursor: Some(14),
+                ..range
+            })
+            .await
+            .unwrap();
+        let numbers: Vec<u64> = second.items.iter().map(|s| s.slot).collect();
+        assert_eq!(numbers, vec![13, 12]);
+        assert!(second.next_cursor.is_none());
+    }
+
+    #[tokio::test]
+    async fn sqlite_keeps_first_seen_commitment_times() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = SqliteStorage::new(dir.path().join("test.db"))
+            .await
+            .expect("sqlite should init");
+
+        let mut slot = crate::testing::fixtures::sample_slot(5);
+        slot.status = SlotStatus::Processed;
+        slot.lifecycle.processed_at = Some(100);
+        storage.store_slot(&slot).await.unwrap();
+
+        slot.status = SlotStatus::Confirmed;
+        slot.lifecycle = SlotLifecycle {
+            confirmed_at: Some(101),
+            ..SlotLifecycle::default()
+        };
+        storage.store_slot(&slot).await.unwrap();
+
+        let loaded = storage.get_slot(5).await.unwrap().expect("slot stored");
+        assert!(matches!(loaded.status, SlotStatus::Confirmed));
+        assert_eq!(loaded.lifecycle.processed_at, Some(100));
+        assert_eq!(loaded.lifecycle.confirmed_at, Some(101));
+    }
+
+    #[tokio::test]
+    async fn sqlite_indexes_transactions_by_account() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = SqliteStorage::new(dir.path().join("test.db"))
+            .await
+            .expect("sqlite should init");
+
+        for (sig, slot) in [("s1", 1), ("s2", 2), ("s3", 2)] {
+            storage
+                .store_transaction(Transaction {
+                    signature: sig.into(),
+                    slot,
+                    block_time: None,
+                    fee: 5000,
+                    success: true,
+                    accounts: vec!["payer".into(), "wallet".into()],
+                    program: None,
+                    instruction_count: None,
+                    compute_units: None,
+                })
+                .await
+                .unwrap();
+        }
+
+        let first = storage
+            .get_transactions_for_account("wallet", 2, None)
+            .await
+            .unwrap();
+        let sigs: Vec<&str> = first.items.iter().map(|t| t.signature.as_str()).collect();
+        assert_eq!(sigs, vec!["s3", "s2"]);
+
+        let cursor = first.next_cursor.expect("more pages").parse().unwrap();
+        let second = storage
+            .get_transactions_for_account("wallet", 2, Some(cursor))
+            .await
+            .unwrap();
+        assert_eq!(second.items.len(), 1);
+        assert_eq!(second.items[0].signature, "s1");
+        assert!(storage
+            .get_transactions_for_account("nobody", 2, None)
+            .await
+            .unwrap()
+            .items
+            .is_empty());
+
+        assert_eq!(storage.count_transactions_in_slot(2).await.unwrap(), 2);
+        let block = storage.get_transactions_in_slot(2, 1, None).await.unwrap();
+        assert_eq!(block.items[0].signature, "s2");
+        let rest = storage
+            .get_transactions_in_slot(2, 1, block.next_cursor)
+            .await
+            .unwrap();
+        assert_eq!(rest.items[0].signature, "s3");
+        assert!(rest.next_cursor.is_none());
+    }
+
+    #[tokio::test]
+    async fn sqlite_persists_transaction_details() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = SqliteStorage::new(dir.path().join("test.db"))
+            .await
+            .expect("sqlite should init");
+
+        storage
+            .store_transaction(Transaction {
+                signature: "detailed".into(),
+                slot: 3,
+                block_time: Some(1_700_000_000),
+                fee: 5000,
+                success: true,
+                accounts: vec!["payer".into()],
+                program: Some("11111111111111111111111111111111".into()),
+                instruction_count: Some(2),
+                compute_units: Some(150),
+            })
+            .await
+            .unwrap();
+
+        let loaded = storage
+            .get_transaction("detailed")
+            .await
+            .unwrap()
+            .expect("tx stored");
+        assert_eq!(
+            loaded.program.as_deref(),
+            Some("11111111111111111111111111111111")
+        );
+        assert_eq!(loaded.instruction_count, Some(2));
+        assert_eq!(loaded.compute_units, Some(150));
+        assert_eq!(loaded.block_time, Some(1_700_000_000));
+    }
+
+    #[tokio::test]
+    async fn sqlite_stores_instructions_in_execution_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = SqliteStorage::new(dir.path().join("test.db"))
+            .await
+            .expect("sqlite should init");
+
+        let ix = |index, inner_index, program_id: &str| Instruction {
+            signature: "withcpi".into(),
+            slot: 4,
+            index,
+            inner_index,
+            stack_height: inner_index.map(|_| 2),
+            program_id: program_id.into(),
+            accounts: vec![0, 1],
+            data: Some("3Bxs4h24hBtQy9rw".into()),
+            parsed: None,
+        };
+        let parsed = Instruction {
+            data: None,
+            accounts: vec![],
+            parsed: Some(serde_json::json!({ "type": "transfer" })),
+            ..ix(1, None, "11111111111111111111111111111111")
+        };
+        let instructions = vec![
+            ix(0, None, "Program1"),
+            ix(0, Some(0), "TokenProgram"),
+            ix(0, Some(1), "System"),
+            ix(0, Some(2), "TokenProgram"),
+            parsed,
+        ];
+        storage
+            .store_instructions("withcpi", &instructions)
+            .await
+            .unwrap();
+        // Re-ingesting the same transaction replaces rather than duplicates.
+        storage
+            .store_instructions("withcpi", &instructions)
+            .await
+            .unwrap();
+
+        assert_eq!(storage.get_instructions("withcpi").await.unwrap(), instructions);
+
+        storage
+            .store_transaction(Transaction {
+                signature: "withcpi".into(),
+                slot: 4,
+                block_time: None,
+                fee: 5000,
+                success: true,
+                accounts: vec![],
+                program: Some("Program1".into()),
+                instruction_count: Some(2),
+                compute_units: None,
+            })
+            .await
+            .unwrap();
+        // Programs reached only through CPI are indexed too, once per transaction.
+        let by_program = storage
+            .get_transactions_for_program("TokenProgram", 10, None)
+            .await
+            .unwrap();
+        assert_eq!(by_program.items.len(), 1);
+        assert!(storage
+            .get_transactions_for_program("Unrelated", 10, None)
+            .await
+            .unwrap()
+            .items
+            .is_empty());
+
+        storage.rollback_slot(4).await.unwrap();
+        assert!(storage.get_instructions("withcpi").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn sqlite_pages_token_balance_changes_by_owner() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = SqliteStorage::new(dir.path().join("test.db"))
+            .await
+            .expect("sqlite should init");
+
+        let change = |signature: &str, slot, account: &str, post| TokenBalanceChange {
+            signature: signature.into(),
+            slot,
+            account: account.into(),
+            owner: Some("wallet".into()),
+            mint: "Mint".into(),
+            pre: 0,
+            post,
+            decimals: 9,
+        };
+        storage
+            .store_token_balance_changes("s1", &[change("s1", 1, "ata1", 10)])
+            .await
+            .unwrap();
+        // u64::MAX does not fit BIGINT; amounts round-trip as text.
+        storage
+            .store_token_balance_changes(
+                "s2",
+                &[change("s2", 2, "ata1", 20), change("s2", 2, "ata2", u64::MAX)],
+            )
+            .await
+            .unwrap();
+
+        let first = storage
+            .get_token_balance_changes_for_owner("wallet", 2, None)
+            .await
+            .unwrap();
+        let accounts: Vec<_> = first.items.iter().map(|c| c.account.as_str()).collect();
+        assert_eq!(accounts, vec!["ata2", "ata1"]);
+        assert_eq!(first.items[0].post, u64::MAX);
+        assert_eq!(first.next_cursor.as_deref(), Some("2:s2:ata1"));
+
+        let cursor = first.next_cursor.unwrap().parse().unwrap();
+        let second = storage
+            .get_token_balance_changes_for_owner("wallet", 2, Some(cursor))
+            .await
+            .unwrap();
+        assert_eq!(second.items, vec![change("s1", 1, "ata1", 10)]);
+        assert!(second.next_cursor.is_none());
+
+        storage.rollback_slot(2).await.unwrap();
+        let remaining = storage
+            .get_token_balance_changes_for_owner("wallet", 10, None)
+            .await
+            .unwrap();
+        assert_eq!(remaining.items.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn sqlite_stores_sol_balance_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = SqliteStorage::new(dir.path().join("test.db"))
+            .await
+            .expect("sqlite should init");
+
+        let accounts = ["payer".to_string(), "dest".into(), "program".into()];
+        for (signature, slot) in [("s1", 1), ("s2", 2)] {
+            let changes = SolBalanceChange::from_balances(
+                signature,
+                slot,
+                &accounts,
+                &[10_000, 0, 1],
+                &[4_000, 5_000, 1],
+            );
+            storage
+                .store_sol_balance_changes(signature, &changes)
+                .await
+                .unwrap();
+        }
+
+        let changes = storage.get_sol_balance_changes("s1").await.unwrap();
+        let deltas: Vec<_> = changes.iter().map(|c| (c.account.as_str(), c.delta)).collect();
+        assert_eq!(deltas, vec![("payer", -6_000), ("dest", 5_000)]);
+
+        let page = storage.get_sol_balance_history("dest", 1, None).await.unwrap();
+        assert_eq!(page.items[0].signature, "s2");
+        assert_eq!(page.next_cursor.as_deref(), Some("2:s2"));
+
+        storage.rollback_slot(2).await.unwrap();
+        assert!(storage.get_sol_balance_changes("s2").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn sqlite_keeps_account_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = SqliteStorage::new(dir.path().join("test.db"))
+            .await
+            .expect("sqlite should init");
+
+        let version = |slot, write_version, lamports| AccountState {
+            slot,
+            write_version,
+            ..crate::testing::mock_sources::sample_account("wallet", lamports)
+        };
+        for account in [version(10, 1, 100), version(10, 2, 150), version(12, 0, 200)] {
+            storage.append_account_history(&account).await.unwrap();
+            storage.store_account(account).await.unwrap();
+        }
+        // Re-delivered updates are ignored.
+        storage
+            .append_account_history(&version(10, 2, 150))
+            .await
+            .unwrap();
+
+        let latest = storage.get_account("wallet").await.unwrap().unwrap();
+        assert_eq!((latest.lamports, latest.write_version), (200, 0));
+
+        let at_11 = storage.get_account_at_slot("wallet", 11).await.unwrap().unwrap();
+        assert_eq!(at_11.lamports, 150);
+        assert!(storage.get_account_at_slot("wallet", 9).await.unwrap().is_none());
+
+        let range = AccountHistoryRange {
+            from_slot: Some(10),
+            to_slot: Some(11),
+            limit: 1,
+            cursor: None,
+        };
+        let first = storage.get_account_history("wallet", range).await.unwrap();
+        assert_eq!(first.items[0].lamports, 150);
+        assert_eq!(first.next_cursor.as_deref(), Some("10:2"));
+        let second = storage
+            .get_account_history(
+                "wallet",
+                AccountHistoryRange {
+                    cursor: first.next_cursor.map(|c| c.parse().unwrap()),
+                    ..range
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(second.items[0].lamports, 100);
+        assert!(second.next_cursor.is_none());
+
+        storage.rollback_slot(12).await.unwrap();
+        let remaining = storage
+            .get_account_history("wallet", AccountHistoryRange { limit: 10, ..Default::default() })
+            .await
+            .unwrap();
+        assert_eq!(remaining.items.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn sqlite_queues_and_dead_letters_webhooks() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = SqliteStorage::new(dir.path().join("test.db"))
+            .await
+            .expect("sqlite should init");
+
+        let delivery = |id: &str, next_attempt_at| crate::core::types::WebhookDelivery {
+            id: id.into(),
+            url: "http://localhost/hook".into(),
+            event: "slot".into(),
+            payload: "{}".into(),
+            attempts: 0,
+            next_attempt_at,
+            last_error: None,
+            created_at: 100,
+        };
+        for d in [delivery("a", 100), delivery("b", 200), delivery("a", 300)] {
+            storage.enqueue_webhook(&d).await.unwrap();
+        }
+
+        let due = storage.get_due_webhooks(150, 10).await.unwrap();
+        assert_eq!(due, vec![delivery("a", 100)]);
+
+        storage.retry_webhook("a", 400, "HTTP 502").await.unwrap();
+        let due = storage.get_due_webhooks(1_000, 10).await.unwrap();
+        let ids: Vec<_> = due.iter().map(|d| (d.id.as_str(), d.attempts)).collect();
+        assert_eq!(ids, vec![("b", 0), ("a", 1)]);
+
+        storage.complete_webhook("b").await.unwrap();
+        storage.dead_letter_webhook("a", "timed out").await.unwrap();
+        assert!(storage.get_due_webhooks(i64::MAX, 10).await.unwrap().is_empty());
+
+        let dead = storage.get_webhook_dead_letters(10).await.unwrap();
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].attempts, 2);
+        assert_eq!(dead[0].last_error.as_deref(), Some("timed out"));
+    }
+
+    #[tokio::test]
+    async fn sqlite_rollback_marks_slot_dead_and_drops_transactions() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = SqliteStorage::new(dir.path().join("test.db"))
+            .await
+            .expect("sqlite should init");
+
+        storage
+            .store_slot(&crate::testing::fixtures::sample_slot(7))
+            .await
+            .unwrap();
+        storage
+            .store_transaction(Transaction {
+                signature: "orphan".into(),
+                slot: 7,
+                block_time: None,
+                fee: 5000,
+                success: true,
+                accounts: vec![],
+                program: None,
+                instruction_count: None,
+                compute_units: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(storage.rollback_slot(7).await.unwrap(), 1);
+        let loaded = storage.get_slot(7).await.unwrap().expect("slot kept");
+        assert!(matches!(loaded.status, SlotStatus::Dead));
+        assert!(storage.get_transaction("orphan").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn sqlite_slot_upsert_never_downgrades_status() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = SqliteStorage::new(dir.path().join("test.db"))
+            .await
+            .expect("sqlite should init");
+
+        let mut slot = crate::testing::fixtures::sample_slot(9);
+        slot.status = SlotStatus::Finalized;
+        storage.store_slot(&slot).await.unwrap();
+        slot.status = SlotStatus::Processed;
+        slot.block_height = Some(42);
+        storage.store_slot(&slot).await.unwrap();
+        let loaded = storage.get_slot(9).await.unwrap().expect("slot stored");
+        assert_eq!(loaded.status, SlotStatus::Finalized);
+        assert_eq!(loaded.block_height, Some(42));
+
+        storage.rollback_slot(9).await.unwrap();
+        slot.status = SlotStatus::Finalized;
+        storage.store_slot(&slot).await.unwrap();
+        let loaded = storage.get_slot(9).await.unwrap().expect("slot kept");
+        assert_eq!(loaded.status, SlotStatus::Dead);
+    }
+
+    #[tokio::test]
+    async fn sqlite_checkpoint_never_moves_backwards() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = SqliteStorage::new(dir.path().join("test.db"))
+            .await
+            .expect("sqlite should init");
+
+        storage.set_checkpoint(1_000).await.unwrap();
+        storage.set_checkpoint(1_000 - 32).await.unwrap();
+        assert_eq!(storage.get_checkpoint().await.unwrap(), Some(1_000));
+
+        storage.set_checkpoint(1_001).await.unwrap();
+        assert_eq!(storage.get_checkpoint().await.unwrap(), Some(1_001));
+    }
+
+    #[tokio::test]
+    async fn sqlite_stores_backfill_batches_and_cursor() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = SqliteStorage::new(dir.path().join("test.db"))
+            .await
+            .expect("sqlite should init");
+
+        let slots = [
+            crate::testing::fixtures::sample_slot(10),
+            crate::testing::fixtures::sample_slot(11),
+        ];
+        let tx = TransactionInfo {
+            signature: "historical".into(),
+            slot: 11,
+            success: true,
+            fee: 5000,
+            program: "11111111111111111111111111111111".into(),
+            instructions: 1,
+            compute_units: 150,
+            accounts: vec!["wallet".into()],
+            timestamp: 1,
+            block_time: Some(1_700_000_000),
+            instruction_details: vec![Instruction {
+                signature: "historical".into(),
+                slot: 11,
+                index: 0,
+                inner_index: None,
+                stack_height: None,
+                program_id: "11111111111111111111111111111111".into(),
+                accounts: vec![0],
+                data: Some("3Bxs4h24hBtQy9rw".into()),
+                parsed: None,
+            }],
+            token_balance_changes: vec![],
+            sol_balance_changes: vec![SolBalanceChange {
+                signature: "historical".into(),
+                slot: 11,
+                account: "wallet".into(),
+                pre: 10_000,
+                post: 5_000,
+                delta: -5_000,
+            }],
+        };
+        storage.store_block_batch(&slots, &[tx]).await.unwrap();
+
+        assert!(storage.get_slot(10).await.unwrap().is_some());
+        let page = storage
+            .get_transactions_for_account("wallet", 10, None)
+            .await
+            .unwrap();
+        assert_eq!(page.items[0].signature, "historical");
+        let instructions = storage.get_instructions("historical").await.unwrap();
+        assert_eq!(instructions.len(), 1);
+        assert_eq!(instructions[0].accounts, vec![0]);
+        let sol_changes = storage.get_sol_balance_changes("historical").await.unwrap();
+        assert_eq!(sol_changes[0].delta, -5_000);
+
+        assert_eq!(storage.get_backfill_cursor(10, 20).await.unwrap(), None);
+        storage.set_backfill_cursor(10, 20, 12).await.unwrap();
+        storage.set_backfill_cursor(10, 20, 14).await.unwrap();
+        assert_eq!(storage.get_backfill_cursor(10, 20).await.unwrap(), Some(14));
+        assert_eq!(storage.get_backfill_cursor(10, 30).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn sqlite_finds_slot_gaps_including_range_edges() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = SqliteStorage::new(dir.path().join("test.db"))
+            .await
+            .expect("sqlite should init");
+
+        for slot in [12, 13, 16, 20] {
+            storage
+                .store_slot(&crate::testing::fixtures::sample_slot(slot))
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(
+            storage.get_slot_gaps(10, 22).await.unwrap(),
+            vec![(10, 11), (14, 15), (17, 19), (21, 22)]
+        );
+        assert_eq!(storage.get_slot_gaps(12, 13).await.unwrap(), vec![]);
+        assert_eq!(storage.get_slot_gaps(30, 31).await.unwrap(), vec![(30, 31)]);
+    }
 }
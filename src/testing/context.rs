@@ -1,7 +1,9 @@
 use crate::context::AppContext;
 use crate::storage::cache::multi_cache::MultiCache;
 use crate::testing::mock_db::MockDatabase;
-use crate::utils::config::{CacheConfig, Config, RpcConfig, StorageConfig};
+use crate::utils::config::{
+    CacheConfig, Config, FailoverConfig, GapConfig, ReadinessConfig, RpcConfig, StorageConfig, WebhookConfig,
+};
 use crate::utils::metrics::IndexerMetrics;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -18,11 +20,14 @@ pub fn test_context(
     AppContext::from_parts(
         Config {
             rpc: RpcConfig {
-                solana_rpc_url: "http://localhost".into(),
-                enrichment_rpc_url: None,
-                yellowstone_grpc_url: None,
-                yellowstone_grpc_token: None,
+                solana_rpc_urls: vec!["http://localhost".into()],
+                enrichment_rpc_urls: vec![],
+                yellowstone_grpc_urls: vec![],
+                yellowstone_grpc_tokens: vec![],
+                yellowstone_merge_streams: false,
                 yellowstone_tx_accounts: vec![],
+                poll_lag_slots: 2,
+                poll_concurrency: 4,
             },
             storage: StorageConfig {
                 sqlite_path: PathBuf::from("test.db"),
@@ -33,7 +38,18 @@ pub fn test_context(
                 l2_size: 10,
                 l3_size: 10,
             },
+            webhooks: WebhookConfig {
+                urls: vec![],
+                secret: None,
+                events: vec![],
+                max_attempts: 8,
+            },
+            readiness: ReadinessConfig::default(),
+            gaps: GapConfig::default(),
+            failover: FailoverConfig::default(),
             watch_accounts,
+            index_programs: vec![],
+            anchor_idl_dir: None,
             api_port,
             api_key: None,
             api_bind_localhost: false,
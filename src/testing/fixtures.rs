@@ -1,4 +1,4 @@
-use crate::core::types::{Slot, SlotStatus};
+use crate::core::types::{Slot, SlotLifecycle, SlotStatus};
 
 /// Shared test slot fixture (parent defaults to `n - 1`).
 pub fn sample_slot(n: u64) -> Slot {
@@ -9,5 +9,6 @@ pub fn sample_slot(n: u64) -> Slot {
         timestamp: 1,
         block_hash: None,
         block_height: None,
+        lifecycle: SlotLifecycle::default(),
     }
 }
@@ -1,34 +1,43 @@
-use crate::core::types::{AccountState, Slot, Transaction};
-use crate::storage::database::DatabaseStorage;
+use crate::core::types::{
+    AccountState, Instruction, Slot, SlotStatus, SolBalanceChange, TokenBalanceChange,
+    Transaction, TransactionInfo, WebhookDelivery,
+};
+use crate::storage::database::{
+    AccountHistoryRange, AccountVersion, DatabaseStorage, Page, SlotRange, TokenChangeCursor,
+    TxCursor,
+};
 use crate::utils::errors::Result;
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::sync::Mutex;
 
 /// In-memory `DatabaseStorage` for unit tests.
+#[derive(Default)]
 pub struct MockDatabase {
     slots: Mutex<HashMap<u64, Slot>>,
     txs: Mutex<HashMap<String, Transaction>>,
+    accounts: Mutex<HashMap<String, AccountState>>,
+    account_history: Mutex<Vec<AccountState>>,
+    instructions: Mutex<HashMap<String, Vec<Instruction>>>,
+    token_changes: Mutex<HashMap<String, Vec<TokenBalanceChange>>>,
+    sol_changes: Mutex<HashMap<String, Vec<SolBalanceChange>>>,
+    webhooks: Mutex<Vec<WebhookDelivery>>,
+    webhook_dead_letters: Mutex<Vec<WebhookDelivery>>,
     wallets: Mutex<Vec<String>>,
-    checkpoint: Mutex<Option<u64>>,
+    /// `(slot, updated_at)`.
+    checkpoint: Mutex<Option<(u64, i64)>>,
+    backfill_cursors: Mutex<HashMap<(u64, u64), u64>>,
 }
 
 impl MockDatabase {
     pub fn new() -> Self {
-        Self {
-            slots: Mutex::new(HashMap::new()),
-            txs: Mutex::new(HashMap::new()),
-            wallets: Mutex::new(vec![]),
-            checkpoint: Mutex::new(None),
-        }
+        Self::default()
     }
 
     pub fn with_wallets(wallets: Vec<String>) -> Self {
         Self {
-            slots: Mutex::new(HashMap::new()),
-            txs: Mutex::new(HashMap::new()),
             wallets: Mutex::new(wallets),
-            checkpoint: Mutex::new(None),
+            ..Self::default()
         }
     }
 }
@@ -36,16 +45,115 @@ impl MockDatabase {
 #[async_trait]
 impl DatabaseStorage for MockDatabase {
     async fn store_slot(&self, slot: &Slot) -> Result<()> {
-        self.slots.lock().unwrap().insert(slot.slot, slot.clone());
+        let rank = |status: &SlotStatus| match status {
+            SlotStatus::Processed => 0,
+            SlotStatus::Confirmed => 1,
+            SlotStatus::Finalized => 2,
+            SlotStatus::Dead => 3,
+        };
+        let mut slots = self.slots.lock().unwrap();
+        let mut slot = slot.clone();
+        if let Some(existing) = slots.get(&slot.slot) {
+            if rank(&existing.status) > rank(&slot.status) {
+                slot.status = existing.status.clone();
+            }
+        }
+        slots.insert(slot.slot, slot);
         Ok(())
     }
 
-    async fn store_account(&self, _account: AccountState) -> Result<()> {
+    async fn store_account(&self, account: AccountState) -> Result<()> {
+        self.accounts
+            .lock()
+            .unwrap()
+            .insert(account.address.clone(), account);
         Ok(())
     }
 
-    async fn get_account(&self, _address: &str) -> Result<Option<AccountState>> {
-        Ok(None)
+    async fn get_account(&self, address: &str) -> Result<Option<AccountState>> {
+        Ok(self.accounts.lock().unwrap().get(address).cloned())
+    }
+
+    async fn get_accounts(&self, addresses: &[String]) -> Result<Vec<AccountState>> {
+        let accounts = self.accounts.lock().unwrap();
+        Ok(addresses
+            .iter()
+            .filter_map(|a| accounts.get(a).cloned())
+            .collect())
+    }
+
+    async fn get_program_accounts(
+        &self,
+        program: &str,
+        limit: u32,
+        cursor: Option<String>,
+    ) -> Result<Page<AccountState>> {
+        let mut accounts: Vec<AccountState> = self
+            .accounts
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|a| a.owner == program)
+            .filter(|a| cursor.as_ref().is_none_or(|c| &a.address > c))
+            .cloned()
+            .collect();
+        accounts.sort_by(|a, b| a.address.cmp(&b.address));
+        accounts.truncate(limit as usize + 1);
+        Ok(Page::from_overfetch(accounts, limit, |a| a.address.clone()))
+    }
+
+    async fn append_account_history(&self, account: &AccountState) -> Result<()> {
+        let mut history = self.account_history.lock().unwrap();
+        let exists = history.iter().any(|a| {
+            (&a.address, a.slot, a.write_version)
+                == (&account.address, account.slot, account.write_version)
+        });
+        if !exists {
+            history.push(account.clone());
+        }
+        Ok(())
+    }
+
+    async fn get_account_history(
+        &self,
+        address: &str,
+        range: AccountHistoryRange,
+    ) -> Result<Page<AccountState>> {
+        let mut versions: Vec<AccountState> = self
+            .account_history
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|a| a.address == address)
+            .filter(|a| {
+                range.from_slot.is_none_or(|f| a.slot >= f)
+                    && range.to_slot.is_none_or(|t| a.slot <= t)
+                    && range
+                        .cursor
+                        .is_none_or(|c| (a.slot, a.write_version) < (c.slot, c.write_version))
+            })
+            .cloned()
+            .collect();
+        versions.sort_by_key(|a| std::cmp::Reverse((a.slot, a.write_version)));
+        versions.truncate(range.limit as usize + 1);
+        Ok(Page::from_overfetch(versions, range.limit, |a| {
+            AccountVersion {
+                slot: a.slot,
+                write_version: a.write_version,
+            }
+            .to_string()
+        }))
+    }
+
+    async fn get_account_at_slot(&self, address: &str, slot: u64) -> Result<Option<AccountState>> {
+        Ok(self
+            .account_history
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|a| a.address == address && a.slot <= slot)
+            .max_by_key(|a| (a.slot, a.write_version))
+            .cloned())
     }
 
     async fn get_slot(&self, slot: u64) -> Result<Option<Slot>> {
@@ -60,10 +168,243 @@ impl DatabaseStorage for MockDatabase {
         Ok(())
     }
 
+    async fn store_block_batch(
+        &self,
+        slots: &[Slot],
+        transactions: &[TransactionInfo],
+    ) -> Result<()> {
+        for slot in slots {
+            self.store_slot(slot).await?;
+        }
+        for tx in transactions {
+            self.store_transaction(Transaction::from(tx.clone())).await?;
+            self.store_instructions(&tx.signature, &tx.instruction_details)
+                .await?;
+            self.store_token_balance_changes(&tx.signature, &tx.token_balance_changes)
+                .await?;
+            self.store_sol_balance_changes(&tx.signature, &tx.sol_balance_changes)
+                .await?;
+        }
+        Ok(())
+    }
+
     async fn get_transaction(&self, signature: &str) -> Result<Option<Transaction>> {
         Ok(self.txs.lock().unwrap().get(signature).cloned())
     }
 
+    async fn get_transactions_in_slot(
+        &self,
+        slot: u64,
+        limit: u32,
+        cursor: Option<String>,
+    ) -> Result<Page<Transaction>> {
+        let mut txs: Vec<Transaction> = self
+            .txs
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|tx| tx.slot == slot)
+            .filter(|tx| cursor.as_ref().is_none_or(|c| &tx.signature > c))
+            .cloned()
+            .collect();
+        txs.sort_by(|a, b| a.signature.cmp(&b.signature));
+        txs.truncate(limit as usize + 1);
+        Ok(Page::from_overfetch(txs, limit, |tx| tx.signature.clone()))
+    }
+
+    async fn count_transactions_in_slot(&self, slot: u64) -> Result<u64> {
+        Ok(self
+            .txs
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|tx| tx.slot == slot)
+            .count() as u64)
+    }
+
+    async fn get_transactions_for_account(
+        &self,
+        address: &str,
+        limit: u32,
+        cursor: Option<TxCursor>,
+    ) -> Result<Page<Transaction>> {
+        let mut txs: Vec<Transaction> = self
+            .txs
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|tx| tx.accounts.iter().any(|a| a == address))
+            .filter(|tx| {
+                cursor.as_ref().is_none_or(|c| {
+                    (tx.slot, tx.signature.as_str()) < (c.slot, c.signature.as_str())
+                })
+            })
+            .cloned()
+            .collect();
+        txs.sort_by(|a, b| (b.slot, &b.signature).cmp(&(a.slot, &a.signature)));
+        txs.truncate(limit as usize + 1);
+        Ok(Page::from_overfetch(txs, limit, |tx| {
+            TxCursor {
+                slot: tx.slot,
+                signature: tx.signature.clone(),
+            }
+            .to_string()
+        }))
+    }
+
+    async fn get_transactions_for_program(
+        &self,
+        program: &str,
+        limit: u32,
+        cursor: Option<TxCursor>,
+    ) -> Result<Page<Transaction>> {
+        let instructions = self.instructions.lock().unwrap();
+        let mut txs: Vec<Transaction> = self
+            .txs
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|tx| {
+                instructions
+                    .get(&tx.signature)
+                    .is_some_and(|ixs| ixs.iter().any(|ix| ix.program_id == program))
+            })
+            .filter(|tx| {
+                cursor.as_ref().is_none_or(|c| {
+                    (tx.slot, tx.signature.as_str()) < (c.slot, c.signature.as_str())
+                })
+            })
+            .cloned()
+            .collect();
+        txs.sort_by(|a, b| (b.slot, &b.signature).cmp(&(a.slot, &a.signature)));
+        txs.truncate(limit as usize + 1);
+        Ok(Page::from_overfetch(txs, limit, |tx| {
+            TxCursor {
+                slot: tx.slot,
+                signature: tx.signature.clone(),
+            }
+            .to_string()
+        }))
+    }
+
+    async fn store_instructions(&self, signature: &str, instructions: &[Instruction]) -> Result<()> {
+        self.instructions
+            .lock()
+            .unwrap()
+            .insert(signature.to_string(), instructions.to_vec());
+        Ok(())
+    }
+
+    async fn get_instructions(&self, signature: &str) -> Result<Vec<Instruction>> {
+        Ok(self
+            .instructions
+            .lock()
+            .unwrap()
+            .get(signature)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn store_token_balance_changes(
+        &self,
+        signature: &str,
+        changes: &[TokenBalanceChange],
+    ) -> Result<()> {
+        self.token_changes
+            .lock()
+            .unwrap()
+            .insert(signature.to_string(), changes.to_vec());
+        Ok(())
+    }
+
+    async fn get_token_balance_changes_for_owner(
+        &self,
+        owner: &str,
+        limit: u32,
+        cursor: Option<TokenChangeCursor>,
+    ) -> Result<Page<TokenBalanceChange>> {
+        let key = |c: &TokenBalanceChange| (c.slot, c.signature.clone(), c.account.clone());
+        let mut changes: Vec<TokenBalanceChange> = self
+            .token_changes
+            .lock()
+            .unwrap()
+            .values()
+            .flatten()
+            .filter(|c| c.owner.as_deref() == Some(owner))
+            .filter(|c| {
+                cursor.as_ref().is_none_or(|cur| {
+                    key(c) < (cur.tx.slot, cur.tx.signature.clone(), cur.account.clone())
+                })
+            })
+            .cloned()
+            .collect();
+        changes.sort_by_key(|c| std::cmp::Reverse(key(c)));
+        changes.truncate(limit as usize + 1);
+        Ok(Page::from_overfetch(changes, limit, |c| {
+            TokenChangeCursor {
+                tx: TxCursor {
+                    slot: c.slot,
+                    signature: c.signature.clone(),
+                },
+                account: c.account.clone(),
+            }
+            .to_string()
+        }))
+    }
+
+    async fn store_sol_balance_changes(
+        &self,
+        signature: &str,
+        changes: &[SolBalanceChange],
+    ) -> Result<()> {
+        self.sol_changes
+            .lock()
+            .unwrap()
+            .insert(signature.to_string(), changes.to_vec());
+        Ok(())
+    }
+
+    async fn get_sol_balance_changes(&self, signature: &str) -> Result<Vec<SolBalanceChange>> {
+        Ok(self
+            .sol_changes
+            .lock()
+            .unwrap()
+            .get(signature)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn get_sol_balance_history(
+        &self,
+        address: &str,
+        limit: u32,
+        cursor: Option<TxCursor>,
+    ) -> Result<Page<SolBalanceChange>> {
+        let mut changes: Vec<SolBalanceChange> = self
+            .sol_changes
+            .lock()
+            .unwrap()
+            .values()
+            .flatten()
+            .filter(|c| c.account == address)
+            .filter(|c| {
+                cursor.as_ref().is_none_or(|cur| {
+                    (c.slot, c.signature.as_str()) < (cur.slot, cur.signature.as_str())
+                })
+            })
+            .cloned()
+            .collect();
+        changes.sort_by(|a, b| (b.slot, &b.signature).cmp(&(a.slot, &a.signature)));
+        changes.truncate(limit as usize + 1);
+        Ok(Page::from_overfetch(changes, limit, |c| {
+            TxCursor {
+                slot: c.slot,
+                signature: c.signature.clone(),
+            }
+            .to_string()
+        }))
+    }
+
     async fn get_latest_slot(&self) -> Result<Option<Slot>> {
         Ok(self
             .slots
@@ -74,6 +415,97 @@ impl DatabaseStorage for MockDatabase {
             .cloned())
     }
 
+    async fn get_slots_range(&self, range: SlotRange) -> Result<Page<Slot>> {
+        let mut slots: Vec<Slot> = self
+            .slots
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|s| {
+                range.from.is_none_or(|f| s.slot >= f)
+                    && range.to.is_none_or(|t| s.slot <= t)
+                    && range.cursor.is_none_or(|c| s.slot < c)
+            })
+            .cloned()
+            .collect();
+        slots.sort_by_key(|s| std::cmp::Reverse(s.slot));
+        slots.truncate(range.limit as usize + 1);
+        Ok(Page::from_overfetch(slots, range.limit, |s| s.slot.to_string()))
+    }
+
+    async fn get_slot_gaps(&self, from: u64, to: u64) -> Result<Vec<(u64, u64)>> {
+        let slots = self.slots.lock().unwrap();
+        let mut gaps = Vec::new();
+        let mut start = None;
+        for slot in from..=to {
+            match (slots.contains_key(&slot), start) {
+                (false, None) => start = Some(slot),
+                (true, Some(s)) => {
+                    gaps.push((s, slot - 1));
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        if let Some(s) = start {
+            gaps.push((s, to));
+        }
+        Ok(gaps)
+    }
+
+    async fn enqueue_webhook(&self, delivery: &WebhookDelivery) -> Result<()> {
+        let mut queue = self.webhooks.lock().unwrap();
+        if !queue.iter().any(|d| d.id == delivery.id) {
+            queue.push(delivery.clone());
+        }
+        Ok(())
+    }
+
+    async fn get_due_webhooks(&self, now: i64, limit: u32) -> Result<Vec<WebhookDelivery>> {
+        let mut due: Vec<WebhookDelivery> = self
+            .webhooks
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|d| d.next_attempt_at <= now)
+            .cloned()
+            .collect();
+        due.sort_by(|a, b| (a.next_attempt_at, &a.id).cmp(&(b.next_attempt_at, &b.id)));
+        due.truncate(limit as usize);
+        Ok(due)
+    }
+
+    async fn complete_webhook(&self, id: &str) -> Result<()> {
+        self.webhooks.lock().unwrap().retain(|d| d.id != id);
+        Ok(())
+    }
+
+    async fn retry_webhook(&self, id: &str, next_attempt_at: i64, error: &str) -> Result<()> {
+        if let Some(d) = self.webhooks.lock().unwrap().iter_mut().find(|d| d.id == id) {
+            d.attempts += 1;
+            d.next_attempt_at = next_attempt_at;
+            d.last_error = Some(error.to_string());
+        }
+        Ok(())
+    }
+
+    async fn dead_letter_webhook(&self, id: &str, error: &str) -> Result<()> {
+        let mut queue = self.webhooks.lock().unwrap();
+        if let Some(pos) = queue.iter().position(|d| d.id == id) {
+            let mut d = queue.remove(pos);
+            d.attempts += 1;
+            d.next_attempt_at = chrono::Utc::now().timestamp();
+            d.last_error = Some(error.to_string());
+            self.webhook_dead_letters.lock().unwrap().push(d);
+        }
+        Ok(())
+    }
+
+    async fn get_webhook_dead_letters(&self, limit: u32) -> Result<Vec<WebhookDelivery>> {
+        let dead = self.webhook_dead_letters.lock().unwrap();
+        Ok(dead.iter().rev().take(limit as usize).cloned().collect())
+    }
+
     async fn add_wallet(&self, _address: String, _name: Option<String>) -> Result<()> {
         Ok(())
     }
@@ -89,16 +521,79 @@ impl DatabaseStorage for MockDatabase {
         Ok(vec![])
     }
 
+    async fn list_wallets_page(
+        &self,
+        _active_only: bool,
+        limit: u32,
+        cursor: Option<String>,
+    ) -> Result<Page<(String, Option<String>, i64)>> {
+        let mut wallets: Vec<_> = self
+            .wallets
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|a| cursor.as_ref().is_none_or(|c| *a > c))
+            .map(|a| (a.clone(), None, 0))
+            .collect();
+        wallets.sort();
+        wallets.truncate(limit as usize + 1);
+        Ok(Page::from_overfetch(wallets, limit, |w| w.0.clone()))
+    }
+
     async fn get_active_wallets(&self) -> Result<Vec<String>> {
         Ok(self.wallets.lock().unwrap().clone())
     }
 
     async fn get_checkpoint(&self) -> Result<Option<u64>> {
+        Ok(self.checkpoint.lock().unwrap().map(|(slot, _)| slot))
+    }
+
+    async fn get_checkpoint_status(&self) -> Result<Option<(u64, i64)>> {
         Ok(*self.checkpoint.lock().unwrap())
     }
 
     async fn set_checkpoint(&self, slot: u64) -> Result<()> {
-        *self.checkpoint.lock().unwrap() = Some(slot);
+        let mut checkpoint = self.checkpoint.lock().unwrap();
+        let slot = checkpoint.map_or(slot, |(stored, _)| stored.max(slot));
+        *checkpoint = Some((slot, chrono::Utc::now().timestamp()));
+        Ok(())
+    }
+
+    async fn get_backfill_cursor(&self, from: u64, to: u64) -> Result<Option<u64>> {
+        Ok(self.backfill_cursors.lock().unwrap().get(&(from, to)).copied())
+    }
+
+    async fn set_backfill_cursor(&self, from: u64, to: u64, next_slot: u64) -> Result<()> {
+        self.backfill_cursors
+            .lock()
+            .unwrap()
+            .insert((from, to), next_slot);
         Ok(())
     }
+
+    async fn rollback_slot(&self, slot: u64) -> Result<u64> {
+        if let Some(stored) = self.slots.lock().unwrap().get_mut(&slot) {
+            stored.status = SlotStatus::Dead;
+        }
+        self.instructions
+            .lock()
+            .unwrap()
+            .retain(|_, ixs| ixs.first().is_none_or(|ix| ix.slot != slot));
+        self.token_changes
+            .lock()
+            .unwrap()
+            .retain(|_, changes| changes.first().is_none_or(|c| c.slot != slot));
+        self.sol_changes
+            .lock()
+            .unwrap()
+            .retain(|_, changes| changes.first().is_none_or(|c| c.slot != slot));
+        self.account_history
+            .lock()
+            .unwrap()
+            .retain(|a| a.slot != slot);
+        let mut txs = self.txs.lock().unwrap();
+        let before = txs.len();
+        txs.retain(|_, tx| tx.slot != slot);
+        Ok((before - txs.len()) as u64)
+    }
 }
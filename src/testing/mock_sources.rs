@@ -1,21 +1,20 @@
-use crate::core::types::{AccountState, Slot, TransactionInfo};
-use crate::data_sources::{AccountSource, SlotSource};
-use crate::utils::errors::Result;
+use crate::core::types::{AccountState, Block, Slot, TransactionInfo};
+use crate::data_sources::{AccountSource, SlotSource, YellowstoneSource};
+use crate::utils::errors::{IndexerError, Result};
 use async_trait::async_trait;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::{Mutex};
 use tokio::sync::mpsc;
 
 /// Mock account fetcher for unit tests.
+#[derive(Default)]
 pub struct MockAccountSource {
     accounts: Mutex<HashMap<String, AccountState>>,
 }
 
 impl MockAccountSource {
     pub fn new() -> Self {
-        Self {
-            accounts: Mutex::new(HashMap::new()),
-        }
+        Self::default()
     }
 
     pub fn insert(&self, account: AccountState) {
@@ -46,49 +45,63 @@ impl AccountSource for MockAccountSource {
                 ))
             })
     }
+
+    async fn get_program_accounts(&self, program: &str) -> Result<Vec<AccountState>> {
+        Ok(self
+            .accounts
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|a| a.owner == program)
+            .cloned()
+            .collect())
+    }
 }
 
 /// Mock slot source for unit tests.
 pub struct MockSlotSource {
     leader: String,
-    slots: Vec<Slot>,
+    /// Blocks served by `get_block`; other slots are reported as skipped.
+    blocks: HashMap<u64, Block>,
 }
 
 impl MockSlotSource {
     pub fn new(leader: impl Into<String>) -> Self {
         Self {
             leader: leader.into(),
-            slots: vec![],
+            blocks: HashMap::new(),
         }
     }
 
-    pub fn with_slots(leader: impl Into<String>, slots: Vec<Slot>) -> Self {
+    pub fn with_blocks(blocks: Vec<Block>) -> Self {
         Self {
-            leader: leader.into(),
-            slots,
+            blocks: blocks.into_iter().map(|b| (b.slot.slot, b)).collect(),
+            ..Self::new("leader")
         }
     }
 }
 
 #[async_trait]
 impl SlotSource for MockSlotSource {
-    async fn subscribe_slots(&self) -> Result<mpsc::Receiver<Slot>> {
-        let (tx, rx) = mpsc::channel(self.slots.len().max(1));
-        let slots = self.slots.clone();
-        tokio::spawn(async move {
-            for slot in slots {
-                if tx.send(slot).await.is_err() {
-                    break;
-                }
-            }
-        });
-        Ok(rx)
-    }
-
     async fn get_block_with_transactions(&self, _slot: u64) -> Result<Vec<TransactionInfo>> {
         Ok(vec![])
     }
 
+    async fn get_block(&self, slot: u64) -> Result<Option<Block>> {
+        Ok(self.blocks.get(&slot).cloned())
+    }
+
+    async fn get_blocks(&self, start: u64, end: u64) -> Result<Vec<u64>> {
+        let mut produced: Vec<u64> = self
+            .blocks
+            .keys()
+            .copied()
+            .filter(|slot| (start..=end).contains(slot))
+            .collect();
+        produced.sort_unstable();
+        Ok(produced)
+    }
+
     async fn get_leader_at_slot(&self, _slot: u64) -> Result<String> {
         Ok(self.leader.clone())
     }
@@ -104,12 +117,7 @@ impl SlotSource for MockSlotSource {
     }
 
     async fn current_slot(&self) -> Result<u64> {
-        Ok(self
-            .slots
-            .iter()
-            .map(|s| s.slot)
-            .max()
-            .unwrap_or(0))
+        Ok(self.blocks.keys().copied().max().unwrap_or(0))
     }
 }
 
@@ -122,5 +130,76 @@ pub fn sample_account(address: &str, lamports: u64) -> AccountState {
         executable: false,
         data: vec![],
         rent_epoch: 0,
+        write_version: 0,
+        decoded: None,
+    }
+}
+
+/// Scripted Yellowstone source for reconnect tests. Each `subscribe_with_transactions` call
+/// takes the next connection: `None` fails to connect, `Some(slots)` streams the slots and then
+/// drops the stream. Once the script is used up, connections stay open and idle.
+#[derive(Default)]
+pub struct MockYellowstoneSource {
+    connections: Mutex<VecDeque<Option<Vec<Slot>>>>,
+    /// `from_slot` of every subscribe call, in order.
+    pub requests: Mutex<Vec<Option<u64>>>,
+    open: Mutex<Vec<(mpsc::Sender<Slot>, mpsc::Sender<TransactionInfo>)>>,
+}
+
+impl MockYellowstoneSource {
+    pub fn with_connections(connections: Vec<Option<Vec<Slot>>>) -> Self {
+        Self {
+            connections: Mutex::new(connections.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Sends `slot` on the open connections.
+    pub fn send_slot(&self, slot: Slot) {
+        for (slots, _) in self.open.lock().unwrap().iter() {
+            let _ = slots.try_send(slot.clone());
+        }
+    }
+
+    /// Drops the open connections, closing their streams.
+    pub fn disconnect(&self) {
+        self.open.lock().unwrap().clear();
+    }
+}
+
+#[async_trait]
+impl YellowstoneSource for MockYellowstoneSource {
+    async fn subscribe_with_transactions(
+        &self,
+        from_slot: Option<u64>,
+    ) -> Result<(mpsc::Receiver<Slot>, mpsc::Receiver<TransactionInfo>)> {
+        self.requests.lock().unwrap().push(from_slot);
+        let (slot_tx, slot_rx) = mpsc::channel(100);
+        let (tx_tx, tx_rx) = mpsc::channel(100);
+        match self.connections.lock().unwrap().pop_front() {
+            Some(None) => Err(IndexerError::EndpointUnavailable("mock connection refused".into())),
+            Some(Some(slots)) => {
+                for slot in slots {
+                    slot_tx.try_send(slot).expect("mock stream capacity");
+                }
+                Ok((slot_rx, tx_rx))
+            }
+            None => {
+                self.open.lock().unwrap().push((slot_tx, tx_tx));
+                Ok((slot_rx, tx_rx))
+            }
+        }
+    }
+
+    async fn subscribe_accounts(
+        &self,
+        _accounts: &[String],
+        _owners: &[String],
+    ) -> Result<mpsc::Receiver<AccountState>> {
+        Err(IndexerError::ConfigError("mock has no account stream".into()))
+    }
+
+    async fn health_ping(&self) -> Result<()> {
+        Ok(())
     }
 }
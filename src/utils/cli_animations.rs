@@ -23,7 +23,7 @@ fn format_number(n: u64) -> String {
     let s = n.to_string();
     let mut out = String::new();
     for (i, ch) in s.chars().enumerate() {
-        if i > 0 && (s.len() - i) % 3 == 0 {
+        if i > 0 && (s.len() - i).is_multiple_of(3) {
             out.push(',');
         }
         out.push(ch);
@@ -140,6 +140,7 @@ impl Cli {
             crate::core::types::SlotStatus::Confirmed => "Confirmed",
             crate::core::types::SlotStatus::Processed => "Processed",
             crate::core::types::SlotStatus::Finalized => "Finalized",
+            crate::core::types::SlotStatus::Dead => "Dead",
         };
         let parent = slot.parent.map(|p| p.to_string()).unwrap_or_else(|| "-".into());
         let time = format_timestamp(slot.timestamp);
@@ -7,8 +7,17 @@ pub struct Config {
     pub rpc: RpcConfig,
     pub storage: StorageConfig,
     pub cache: CacheConfig,
+    pub webhooks: WebhookConfig,
+    pub readiness: ReadinessConfig,
+    pub gaps: GapConfig,
+    pub failover: FailoverConfig,
     /// Extra comma-separated addresses to watch on `indexer start`.
     pub watch_accounts: Vec<String>,
+    /// Program ids to index (`INDEX_PROGRAMS`): transactions invoking them and accounts they own.
+    pub index_programs: Vec<String>,
+    /// Directory of Anchor IDL JSON files (`ANCHOR_IDL_DIR`) used to decode program accounts
+    /// and instructions.
+    pub anchor_idl_dir: Option<PathBuf>,
     /// HTTP query API port (`indexer serve`). Default 8080 when unset.
     pub api_port: Option<u16>,
     /// Optional bearer/API-key auth for the HTTP query API (`API_KEY`).
@@ -23,13 +32,25 @@ pub struct Config {
 
 #[derive(Debug, Clone)]
 pub struct RpcConfig {
-    pub solana_rpc_url: String,
-    /// Optional dedicated RPC for slot `get_block` enrichment (falls back to `solana_rpc_url`).
-    pub enrichment_rpc_url: Option<String>,
-    pub yellowstone_grpc_url: Option<String>,
-    pub yellowstone_grpc_token: Option<String>,
+    /// Comma-separated RPC endpoints (`SOLANA_RPC_URL`), pooled with failover.
+    pub solana_rpc_urls: Vec<String>,
+    /// Optional dedicated RPC endpoints for slot `get_block` enrichment (falls back to
+    /// `solana_rpc_urls`).
+    pub enrichment_rpc_urls: Vec<String>,
+    /// Comma-separated Geyser endpoints (`YELLOWSTONE_GRPC_URL`); empty disables gRPC.
+    pub yellowstone_grpc_urls: Vec<String>,
+    /// `x-token` per gRPC endpoint, by position (`YELLOWSTONE_GRPC_TOKEN`); a single token
+    /// applies to every endpoint.
+    pub yellowstone_grpc_tokens: Vec<Option<String>>,
+    /// Stream from every gRPC endpoint at once and keep the first delivery of each update
+    /// (`YELLOWSTONE_MERGE_STREAMS`), instead of failing over between them.
+    pub yellowstone_merge_streams: bool,
     /// Optional comma-separated accounts for Yellowstone tx subscription (empty = slots only).
     pub yellowstone_tx_accounts: Vec<String>,
+    /// Slots the RPC block poller stays behind the head (`RPC_POLL_LAG_SLOTS`).
+    pub poll_lag_slots: u64,
+    /// Concurrent `getBlock` requests of the RPC block poller (`RPC_POLL_CONCURRENCY`).
+    pub poll_concurrency: usize,
 }
 
 #[derive(Debug, Clone)]
@@ -45,27 +66,121 @@ pub struct CacheConfig {
     pub l3_size: usize,
 }
 
+#[derive(Debug, Clone)]
+pub struct WebhookConfig {
+    /// Endpoints POSTed for each event (`WEBHOOK_URLS`); empty disables webhooks.
+    pub urls: Vec<String>,
+    /// HMAC-SHA256 signing key (`WEBHOOK_SECRET`); requests are unsigned without it.
+    pub secret: Option<String>,
+    /// Event types to send (`WEBHOOK_EVENTS`: `account`, `transaction`, `slot`).
+    pub events: Vec<String>,
+    /// Attempts before a delivery moves to the dead-letter table (`WEBHOOK_MAX_ATTEMPTS`).
+    pub max_attempts: u32,
+}
+
+/// Ingestion thresholds for `/ready`; beyond either, the indexer reports not ready.
+#[derive(Debug, Clone, Copy)]
+pub struct ReadinessConfig {
+    /// Max slots the checkpoint may trail the RPC chain head (`READY_MAX_SLOT_LAG`).
+    pub max_slot_lag: u64,
+    /// Max seconds since a slot was last stored (`READY_MAX_STALL_SECS`).
+    pub max_stall_secs: u64,
+}
+
+impl Default for ReadinessConfig {
+    fn default() -> Self {
+        Self {
+            max_slot_lag: 150,
+            max_stall_secs: 120,
+        }
+    }
+}
+
+/// Background gap detection and repair on `indexer start`.
+#[derive(Debug, Clone, Copy)]
+pub struct GapConfig {
+    /// Seconds between scans (`GAP_SCAN_INTERVAL_SECS`); 0 disables the repairer.
+    pub scan_interval_secs: u64,
+    /// Finalized slots below the checkpoint covered by each scan (`GAP_SCAN_WINDOW`).
+    pub window: u64,
+    /// Max `getBlock` requests per second for repairs (`GAP_REPAIR_RPS`).
+    pub repair_rps: u32,
+}
+
+impl Default for GapConfig {
+    fn default() -> Self {
+        Self {
+            scan_interval_secs: 60,
+            window: 5000,
+            repair_rps: 5,
+        }
+    }
+}
+
+impl RpcConfig {
+    pub fn yellowstone_grpc_token(&self, index: usize) -> Option<String> {
+        match self.yellowstone_grpc_tokens.as_slice() {
+            [token] => token.clone(),
+            tokens => tokens.get(index).cloned().flatten(),
+        }
+    }
+}
+
+/// Health ranking and circuit breaking of pooled RPC and gRPC endpoints.
+#[derive(Debug, Clone, Copy)]
+pub struct FailoverConfig {
+    /// Consecutive failures that open an endpoint's circuit (`ENDPOINT_FAILURE_THRESHOLD`).
+    pub failure_threshold: u32,
+    /// Seconds an open circuit skips its endpoint before a trial request
+    /// (`ENDPOINT_COOLDOWN_SECS`).
+    pub cooldown_secs: u64,
+    /// Milliseconds before a slow `getBlock` is also sent to the next endpoint
+    /// (`RPC_HEDGE_AFTER_MS`); `None` disables hedging.
+    pub hedge_after_ms: Option<u64>,
+}
+
+impl Default for FailoverConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 3,
+            cooldown_secs: 30,
+            hedge_after_ms: None,
+        }
+    }
+}
+
 impl Config {
     pub fn load() -> Result<Self> {
         dotenvy::dotenv().ok();
 
         Ok(Self {
             rpc: RpcConfig {
-                solana_rpc_url: std::env::var("SOLANA_RPC_URL")
-                    .unwrap_or_else(|_| "https://api.mainnet-beta.solana.com".into()),
-                enrichment_rpc_url: std::env::var("ENRICHMENT_RPC_URL").ok().filter(|s| !s.is_empty()),
-                yellowstone_grpc_url: std::env::var("YELLOWSTONE_GRPC_URL").ok(),
-                yellowstone_grpc_token: std::env::var("YELLOWSTONE_GRPC_TOKEN").ok(),
-                yellowstone_tx_accounts: std::env::var("YELLOWSTONE_TX_ACCOUNTS")
-                    .ok()
+                solana_rpc_urls: Some(list_var("SOLANA_RPC_URL"))
+                    .filter(|urls| !urls.is_empty())
+                    .unwrap_or_else(|| vec!["https://api.mainnet-beta.solana.com".into()]),
+                enrichment_rpc_urls: list_var("ENRICHMENT_RPC_URL"),
+                yellowstone_grpc_urls: list_var("YELLOWSTONE_GRPC_URL"),
+                yellowstone_grpc_tokens: std::env::var("YELLOWSTONE_GRPC_TOKEN")
                     .map(|s| {
                         s.split(',')
                             .map(str::trim)
-                            .filter(|a| !a.is_empty())
-                            .map(String::from)
+                            .map(|t| Some(t.to_string()).filter(|t| !t.is_empty()))
                             .collect()
                     })
                     .unwrap_or_default(),
+                yellowstone_merge_streams: std::env::var("YELLOWSTONE_MERGE_STREAMS")
+                    .ok()
+                    .is_some_and(|v| v == "1" || v.eq_ignore_ascii_case("true")),
+                yellowstone_tx_accounts: list_var("YELLOWSTONE_TX_ACCOUNTS"),
+                poll_lag_slots: std::env::var("RPC_POLL_LAG_SLOTS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(2),
+                poll_concurrency: std::env::var("RPC_POLL_CONCURRENCY")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .filter(|&n| n > 0)
+                    .unwrap_or(4),
             },
             storage: StorageConfig {
                 sqlite_path: std::env::var("SQLITE_DB_PATH")
@@ -87,16 +202,65 @@ impl Config {
                     .and_then(|v| v.parse().ok())
                     .unwrap_or(5000),
             },
-            watch_accounts: std::env::var("WATCH_ACCOUNTS")
+            webhooks: WebhookConfig {
+                urls: list_var("WEBHOOK_URLS"),
+                secret: std::env::var("WEBHOOK_SECRET").ok().filter(|s| !s.is_empty()),
+                events: Some(list_var("WEBHOOK_EVENTS"))
+                    .filter(|events| !events.is_empty())
+                    .unwrap_or_else(|| vec!["account".into(), "transaction".into()]),
+                max_attempts: std::env::var("WEBHOOK_MAX_ATTEMPTS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .filter(|&n| n > 0)
+                    .unwrap_or(8),
+            },
+            readiness: ReadinessConfig {
+                max_slot_lag: std::env::var("READY_MAX_SLOT_LAG")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(ReadinessConfig::default().max_slot_lag),
+                max_stall_secs: std::env::var("READY_MAX_STALL_SECS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(ReadinessConfig::default().max_stall_secs),
+            },
+            gaps: GapConfig {
+                scan_interval_secs: std::env::var("GAP_SCAN_INTERVAL_SECS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(GapConfig::default().scan_interval_secs),
+                window: std::env::var("GAP_SCAN_WINDOW")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .filter(|&n| n > 0)
+                    .unwrap_or(GapConfig::default().window),
+                repair_rps: std::env::var("GAP_REPAIR_RPS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .filter(|&n| n > 0)
+                    .unwrap_or(GapConfig::default().repair_rps),
+            },
+            failover: FailoverConfig {
+                failure_threshold: std::env::var("ENDPOINT_FAILURE_THRESHOLD")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .filter(|&n| n > 0)
+                    .unwrap_or(FailoverConfig::default().failure_threshold),
+                cooldown_secs: std::env::var("ENDPOINT_COOLDOWN_SECS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(FailoverConfig::default().cooldown_secs),
+                hedge_after_ms: std::env::var("RPC_HEDGE_AFTER_MS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .filter(|&ms| ms > 0),
+            },
+            watch_accounts: list_var("WATCH_ACCOUNTS"),
+            index_programs: list_var("INDEX_PROGRAMS"),
+            anchor_idl_dir: std::env::var("ANCHOR_IDL_DIR")
                 .ok()
-                .map(|s| {
-                    s.split(',')
-                        .map(str::trim)
-                        .filter(|a| !a.is_empty())
-                        .map(String::from)
-                        .collect()
-                })
-                .unwrap_or_default(),
+                .filter(|s| !s.is_empty())
+                .map(PathBuf::from),
             api_port: std::env::var("API_PORT")
                 .ok()
                 .and_then(|v| v.parse().ok()),
@@ -117,7 +281,7 @@ impl Config {
 
     /// Log warnings for common misconfiguration (non-fatal).
     pub fn warn_if_misconfigured(&self) {
-        if let Some(url) = &self.rpc.yellowstone_grpc_url {
+        for url in &self.rpc.yellowstone_grpc_urls {
             let lower = url.to_lowercase();
             if lower.contains("mainnet-beta.solana.com")
                 || lower.contains("/v1/")
@@ -135,14 +299,57 @@ impl Config {
             );
         }
 
+        if self.rpc.yellowstone_grpc_tokens.len() > 1
+            && self.rpc.yellowstone_grpc_tokens.len() != self.rpc.yellowstone_grpc_urls.len()
+        {
+            tracing::warn!(
+                "YELLOWSTONE_GRPC_TOKEN lists a different number of tokens than \
+                 YELLOWSTONE_GRPC_URL has endpoints; tokens are matched by position"
+            );
+        }
+
+        if self.rpc.yellowstone_merge_streams && self.rpc.yellowstone_grpc_urls.len() < 2 {
+            tracing::warn!(
+                "YELLOWSTONE_MERGE_STREAMS needs two or more YELLOWSTONE_GRPC_URL endpoints; \
+                 streaming from one"
+            );
+        }
+
         if !self.rpc.yellowstone_tx_accounts.is_empty() {
             tracing::info!(
                 count = self.rpc.yellowstone_tx_accounts.len(),
                 "YELLOWSTONE_TX_ACCOUNTS set — enabling filtered Yellowstone tx stream"
             );
         }
+
+        if !self.webhooks.urls.is_empty() && self.webhooks.secret.is_none() {
+            tracing::warn!(
+                "WEBHOOK_URLS is set but WEBHOOK_SECRET is not — webhook requests are unsigned"
+            );
+        }
+
+        if !self.index_programs.is_empty() {
+            tracing::info!(
+                count = self.index_programs.len(),
+                "INDEX_PROGRAMS set — indexing program transactions and owned accounts"
+            );
+        }
     }
 }
+
+/// Comma-separated env list; blank entries are dropped.
+fn list_var(name: &str) -> Vec<String> {
+    std::env::var(name)
+        .ok()
+        .map(|s| {
+            s.split(',')
+                .map(str::trim)
+                .filter(|a| !a.is_empty())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -150,7 +357,7 @@ mod tests {
     #[test]
     fn loads_with_defaults() {
         let config = Config::load().expect("config should load");
-        assert!(config.rpc.solana_rpc_url.contains("solana.com"));
+        assert!(config.rpc.solana_rpc_urls[0].contains("solana.com"));
         assert_eq!(config.cache.l1_size, 1000);
         assert_eq!(config.cache.l2_size, 10000);
         assert_eq!(config.cache.l3_size, 5000);
@@ -5,6 +5,11 @@ pub enum IndexerError {
     #[error("RPC error: {0}")]
     RpcError(String),
 
+    /// The endpoint could not serve the request (transport failure, timeout, HTTP 5xx or 429).
+    /// Pooled sources count these against the endpoint and fail over.
+    #[error("Endpoint unavailable: {0}")]
+    EndpointUnavailable(String),
+
     #[error("Database error: {0}")]
     DatabaseError(String),
 
@@ -16,6 +21,9 @@ pub enum IndexerError {
 
     #[error("Channel error: {0}")]
     ChannelError(String),
+
+    #[error("Decode error: {0}")]
+    DecodeError(String),
 }
 
 impl From<sqlx::Error> for IndexerError {
@@ -1,7 +1,126 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
-/// In-process counters exposed via structured `tracing` logs (no Prometheus dependency).
+/// Histogram bucket upper bounds, in seconds (shared by every latency histogram).
+const LATENCY_BUCKETS: [f64; 14] = [
+    0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0,
+];
+
+/// Latency histogram with fixed [`LATENCY_BUCKETS`], rendered in Prometheus format.
+#[derive(Debug, Default)]
+pub struct Histogram {
+    /// Per-bucket (non-cumulative) counts; observations above the last bound only count in `count`.
+    buckets: [AtomicU64; LATENCY_BUCKETS.len()],
+    count: AtomicU64,
+    sum_micros: AtomicU64,
+}
+
+impl Histogram {
+    pub fn observe(&self, elapsed: Duration) {
+        let secs = elapsed.as_secs_f64();
+        if let Some(i) = LATENCY_BUCKETS.iter().position(|&bound| secs <= bound) {
+            self.buckets[i].fetch_add(1, Ordering::Relaxed);
+        }
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum_micros
+            .fetch_add(elapsed.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    fn render(&self, out: &mut String, name: &str, help: &str) {
+        let _ = writeln!(out, "# HELP {name} {help}");
+        let _ = writeln!(out, "# TYPE {name} histogram");
+        self.render_series(out, name, "");
+    }
+
+    /// Bucket, sum and count lines; `labels` (e.g. `source="a"`) are added to each.
+    fn render_series(&self, out: &mut String, name: &str, labels: &str) {
+        let bucket_labels = if labels.is_empty() {
+            String::new()
+        } else {
+            format!("{labels},")
+        };
+        let mut cumulative = 0;
+        for (bound, bucket) in LATENCY_BUCKETS.iter().zip(&self.buckets) {
+            cumulative += bucket.load(Ordering::Relaxed);
+            let _ = writeln!(out, "{name}_bucket{{{bucket_labels}le=\"{bound}\"}} {cumulative}");
+        }
+        let count = self.count.load(Ordering::Relaxed);
+        let _ = writeln!(out, "{name}_bucket{{{bucket_labels}le=\"+Inf\"}} {count}");
+        let labels = if labels.is_empty() {
+            String::new()
+        } else {
+            format!("{{{labels}}}")
+        };
+        let sum = self.sum_micros.load(Ordering::Relaxed) as f64 / 1e6;
+        let _ = writeln!(out, "{name}_sum{labels} {sum}");
+        let _ = writeln!(out, "{name}_count{labels} {count}");
+    }
+}
+
+/// Delivery stats of one merged Yellowstone stream.
+#[derive(Debug, Default)]
+pub struct StreamSourceMetrics {
+    /// Slot updates and transactions this source delivered before any other.
+    pub first_deliveries: AtomicU64,
+    /// How far behind the first delivery this source delivered the same update.
+    pub lag: Histogram,
+}
+
+/// Per-endpoint stats of merged Yellowstone streams, keyed by endpoint host.
+#[derive(Debug, Default)]
+pub struct StreamSources {
+    sources: Mutex<BTreeMap<String, Arc<StreamSourceMetrics>>>,
+}
+
+impl StreamSources {
+    pub fn source(&self, label: &str) -> Arc<StreamSourceMetrics> {
+        self.sources
+            .lock()
+            .unwrap()
+            .entry(label.to_string())
+            .or_default()
+            .clone()
+    }
+
+    fn render(&self, out: &mut String) {
+        let sources = self.sources.lock().unwrap();
+        if sources.is_empty() {
+            return;
+        }
+        let name = "solana_indexer_stream_first_deliveries_total";
+        let _ = writeln!(
+            out,
+            "# HELP {name} Updates a merged Yellowstone stream delivered before the others."
+        );
+        let _ = writeln!(out, "# TYPE {name} counter");
+        for (source, metrics) in sources.iter() {
+            let first = metrics.first_deliveries.load(Ordering::Relaxed);
+            let _ = writeln!(out, "{name}{{source=\"{source}\"}} {first}");
+        }
+
+        let name = "solana_indexer_stream_lag_seconds";
+        let _ = writeln!(
+            out,
+            "# HELP {name} Delay behind the first merged stream to deliver the same update."
+        );
+        let _ = writeln!(out, "# TYPE {name} histogram");
+        for (source, metrics) in sources.iter() {
+            metrics
+                .lag
+                .render_series(out, name, &format!("source=\"{source}\""));
+        }
+    }
+}
+
+/// In-process counters, latency histograms and gauges. Logged periodically via `tracing` and
+/// served in Prometheus text format at the API's `/metrics`.
 #[derive(Debug, Default)]
 pub struct IndexerMetrics {
     pub slots_ingested: AtomicU64,
@@ -15,6 +134,35 @@ pub struct IndexerMetrics {
     pub rpc_errors: AtomicU64,
     pub enrich_rate_limited: AtomicU64,
     pub enrich_success: AtomicU64,
+    pub slots_orphaned: AtomicU64,
+    /// Missed slots fetched and stored by the gap repairer.
+    pub slots_repaired: AtomicU64,
+    /// Yellowstone reconnect attempts after the stream or a connection attempt failed.
+    pub yellowstone_reconnects: AtomicU64,
+    /// Requests retried on another pooled RPC or gRPC endpoint after one failed.
+    pub endpoint_failovers: AtomicU64,
+    /// `getBlock` requests also sent to a second RPC endpoint because the first was slow.
+    pub rpc_hedged_requests: AtomicU64,
+    /// Indexer events the webhook notifier missed because it fell behind the broadcast.
+    pub webhook_events_lagged: AtomicU64,
+    /// Solana RPC request latency.
+    pub rpc_latency: Histogram,
+    /// Database write latency (slots, transactions, accounts and their detail rows).
+    pub db_write_latency: Histogram,
+    /// Slot block-metadata enrichment latency.
+    pub enrich_latency: Histogram,
+    /// Time from the slot tracker receiving a slot until it is stored (enrichment included).
+    pub slot_store_lag: Histogram,
+    /// Merged Yellowstone streams, per endpoint.
+    pub stream_sources: StreamSources,
+    /// Slots queued for the display consumer.
+    pub slot_channel_depth: AtomicU64,
+    /// Transactions queued for the display consumer.
+    pub tx_channel_depth: AtomicU64,
+    /// Slots the checkpoint trailed the chain head at the last readiness check.
+    pub chain_head_lag_slots: AtomicU64,
+    /// Seconds since a slot was stored, as of the last readiness check.
+    pub last_slot_age_secs: AtomicU64,
 }
 
 impl IndexerMetrics {
@@ -35,14 +183,188 @@ impl IndexerMetrics {
             rpc_errors = self.rpc_errors.load(Ordering::Relaxed),
             enrich_rate_limited = self.enrich_rate_limited.load(Ordering::Relaxed),
             enrich_success = self.enrich_success.load(Ordering::Relaxed),
+            slots_orphaned = self.slots_orphaned.load(Ordering::Relaxed),
+            slots_repaired = self.slots_repaired.load(Ordering::Relaxed),
+            yellowstone_reconnects = self.yellowstone_reconnects.load(Ordering::Relaxed),
+            endpoint_failovers = self.endpoint_failovers.load(Ordering::Relaxed),
+            rpc_hedged_requests = self.rpc_hedged_requests.load(Ordering::Relaxed),
+            webhook_events_lagged = self.webhook_events_lagged.load(Ordering::Relaxed),
             "indexer metrics snapshot"
         );
     }
 
+    /// Prometheus text exposition of every metric. `yellowstone_connected` is omitted when the
+    /// caller does not know it (e.g. a standalone `serve`).
+    pub fn render_prometheus(&self, yellowstone_connected: Option<bool>) -> String {
+        let mut out = String::new();
+        let counters = [
+            ("slots_ingested", &self.slots_ingested, "Slots stored."),
+            ("txs_ingested", &self.txs_ingested, "Transactions stored."),
+            ("l1_hits", &self.l1_hits, "Slot cache hits."),
+            ("l1_misses", &self.l1_misses, "Slot cache misses."),
+            ("l2_hits", &self.l2_hits, "Transaction cache hits."),
+            ("l2_misses", &self.l2_misses, "Transaction cache misses."),
+            ("l3_hits", &self.l3_hits, "Account cache hits."),
+            ("l3_misses", &self.l3_misses, "Account cache misses."),
+            (
+                "rpc_errors",
+                &self.rpc_errors,
+                "Failed Solana RPC requests.",
+            ),
+            (
+                "enrich_rate_limited",
+                &self.enrich_rate_limited,
+                "Slot enrichments skipped by the rate limiter.",
+            ),
+            (
+                "enrich_success",
+                &self.enrich_success,
+                "Slots enriched with block metadata.",
+            ),
+            (
+                "slots_orphaned",
+                &self.slots_orphaned,
+                "Slots rolled back as orphaned.",
+            ),
+            (
+                "slots_repaired",
+                &self.slots_repaired,
+                "Missed slots fetched and stored by the gap repairer.",
+            ),
+            (
+                "yellowstone_reconnects",
+                &self.yellowstone_reconnects,
+                "Yellowstone reconnect attempts.",
+            ),
+            (
+                "endpoint_failovers",
+                &self.endpoint_failovers,
+                "Requests retried on another RPC or gRPC endpoint.",
+            ),
+            (
+                "rpc_hedged_requests",
+                &self.rpc_hedged_requests,
+                "getBlock requests hedged to a second RPC endpoint.",
+            ),
+            (
+                "webhook_events_lagged",
+                &self.webhook_events_lagged,
+                "Events the webhook notifier dropped after falling behind.",
+            ),
+        ];
+        for (name, value, help) in counters {
+            let name = format!("solana_indexer_{name}_total");
+            let _ = writeln!(out, "# HELP {name} {help}");
+            let _ = writeln!(out, "# TYPE {name} counter");
+            let _ = writeln!(out, "{name} {}", value.load(Ordering::Relaxed));
+        }
+
+        let histograms = [
+            (
+                "rpc_latency",
+                &self.rpc_latency,
+                "Solana RPC request latency.",
+            ),
+            (
+                "db_write_latency",
+                &self.db_write_latency,
+                "Database write latency.",
+            ),
+            (
+                "enrich_latency",
+                &self.enrich_latency,
+                "Slot metadata enrichment latency.",
+            ),
+            (
+                "slot_store_lag",
+                &self.slot_store_lag,
+                "Time from receiving a slot to storing it.",
+            ),
+        ];
+        for (name, histogram, help) in histograms {
+            histogram.render(&mut out, &format!("solana_indexer_{name}_seconds"), help);
+        }
+        self.stream_sources.render(&mut out);
+
+        let mut gauges = vec![
+            (
+                "slot_channel_depth",
+                self.slot_channel_depth.load(Ordering::Relaxed),
+                "Slots queued for display.",
+            ),
+            (
+                "tx_channel_depth",
+                self.tx_channel_depth.load(Ordering::Relaxed),
+                "Transactions queued for display.",
+            ),
+            (
+                "chain_head_lag_slots",
+                self.chain_head_lag_slots.load(Ordering::Relaxed),
+                "Slots the checkpoint trailed the chain head at the last /ready check.",
+            ),
+            (
+                "last_slot_age_seconds",
+                self.last_slot_age_secs.load(Ordering::Relaxed),
+                "Seconds since a slot was stored, as of the last /ready check.",
+            ),
+        ];
+        if let Some(connected) = yellowstone_connected {
+            gauges.push((
+                "yellowstone_connected",
+                connected as u64,
+                "1 while the Yellowstone gRPC stream is active.",
+            ));
+        }
+        for (name, value, help) in gauges {
+            let name = format!("solana_indexer_{name}");
+            let _ = writeln!(out, "# HELP {name} {help}");
+            let _ = writeln!(out, "# TYPE {name} gauge");
+            let _ = writeln!(out, "{name} {value}");
+        }
+        out
+    }
+
     pub fn maybe_log_periodic(&self, every: u64) {
         let n = self.slots_ingested.load(Ordering::Relaxed);
-        if n > 0 && n % every == 0 {
+        if n > 0 && n.is_multiple_of(every) {
             self.log_snapshot();
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_counters_histograms_and_gauges() {
+        let metrics = IndexerMetrics::default();
+        metrics.slots_ingested.store(3, Ordering::Relaxed);
+        metrics.rpc_latency.observe(Duration::from_millis(20));
+        metrics.rpc_latency.observe(Duration::from_millis(200));
+        metrics.rpc_latency.observe(Duration::from_secs(120));
+
+        let text = metrics.render_prometheus(Some(true));
+        assert!(text.contains("solana_indexer_slots_ingested_total 3\n"));
+        assert!(text.contains("solana_indexer_rpc_latency_seconds_bucket{le=\"0.025\"} 1\n"));
+        assert!(text.contains("solana_indexer_rpc_latency_seconds_bucket{le=\"60\"} 2\n"));
+        assert!(text.contains("solana_indexer_rpc_latency_seconds_bucket{le=\"+Inf\"} 3\n"));
+        assert!(text.contains("solana_indexer_rpc_latency_seconds_sum 120.22\n"));
+        assert!(text.contains("solana_indexer_yellowstone_connected 1\n"));
+        assert!(!metrics
+            .render_prometheus(None)
+            .contains("yellowstone_connected"));
+
+        metrics
+            .stream_sources
+            .source("a")
+            .lag
+            .observe(Duration::from_millis(3));
+        let text = metrics.render_prometheus(None);
+        assert!(text.contains("solana_indexer_stream_first_deliveries_total{source=\"a\"} 0\n"));
+        assert!(text.contains(
+            "solana_indexer_stream_lag_seconds_bucket{source=\"a\",le=\"0.005\"} 1\n"
+        ));
+        assert!(text.contains("solana_indexer_stream_lag_seconds_count{source=\"a\"} 1\n"));
+    }
+}
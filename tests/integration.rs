@@ -9,7 +9,7 @@
 //!   YELLOWSTONE_GRPC_TOKEN  — optional
 //!   DATABASE_URL            — for PostgreSQL test
 
-use solana_stream_indexer::core::types::{Slot, SlotStatus};
+use solana_stream_indexer::core::types::{Slot, SlotLifecycle, SlotStatus};
 use solana_stream_indexer::data_sources::solana_rpc::SolanaRpc;
 use solana_stream_indexer::data_sources::yellowstone_grpc::YellowstoneGrpc;
 use solana_stream_indexer::data_sources::{AccountSource, YellowstoneSource};
@@ -66,7 +66,7 @@ async fn yellowstone_streams_a_slot() {
     let grpc = Arc::new(client) as Arc<dyn YellowstoneSource>;
 
     let (mut slot_rx, _tx_rx) = grpc
-        .subscribe_with_transactions()
+        .subscribe_with_transactions(None)
         .await
         .expect("Yellowstone subscribe should connect");
 
@@ -98,6 +98,7 @@ async fn postgres_stores_and_reads_slot() {
         timestamp: 1,
         block_hash: Some("test-hash".into()),
         block_height: Some(1),
+        lifecycle: SlotLifecycle::default(),
     };
 
     storage.store_slot(&slot).await.expect("store_slot should succeed");